@@ -0,0 +1,426 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::Emitter;
+use tokio::sync::Semaphore;
+use walkdir::WalkDir;
+
+use crate::error::ExportDoctorError;
+use crate::rules::{PresetResult, Verdict};
+use crate::{evaluate_and_record, license, presets, AnalyzeResult};
+
+/// Extensions picked up when a directory is scanned. Files passed explicitly
+/// are analyzed whatever their extension.
+pub const MEDIA_EXTENSIONS: &[&str] = &[
+    "mp4", "mov", "mkv", "avi", "wmv", "flv", "webm", "mts", "m2ts", "mxf", "ts", "jpg", "jpeg",
+    "png", "webp", "tiff", "bmp",
+];
+
+/// Emitted once per file as soon as it has been analyzed.
+pub const FILE_EVENT: &str = "batch://file";
+
+const MAX_WORKERS: usize = 8;
+
+// ── Structs ──
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchFileResult {
+    pub index: usize,
+    pub total: usize,
+    pub path: String,
+    pub analysis: Option<AnalyzeResult>,
+    pub results: Vec<PresetResult>,
+    pub error: Option<ExportDoctorError>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetTally {
+    pub platform_id: String,
+    pub platform_name: String,
+    pub perfect: usize,
+    pub flawed: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchFailure {
+    pub path: String,
+    pub error: ExportDoctorError,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSummary {
+    pub total: usize,
+    pub analyzed: usize,
+    /// Files that passed every selected preset.
+    pub perfect_files: usize,
+    /// Files that failed at least one selected preset.
+    pub flawed_files: usize,
+    pub presets: Vec<PresetTally>,
+    pub failures: Vec<BatchFailure>,
+}
+
+impl BatchSummary {
+    fn new(total: usize, catalog: &[presets::Preset], preset_ids: &[String]) -> Self {
+        let presets = preset_ids
+            .iter()
+            .filter_map(|id| catalog.iter().find(|p| &p.id == id))
+            .map(|p| PresetTally {
+                platform_id: p.id.clone(),
+                platform_name: p.name.clone(),
+                perfect: 0,
+                flawed: 0,
+            })
+            .collect();
+        BatchSummary {
+            total,
+            analyzed: 0,
+            perfect_files: 0,
+            flawed_files: 0,
+            presets,
+            failures: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, file: &BatchFileResult) {
+        if let Some(error) = &file.error {
+            self.failures.push(BatchFailure {
+                path: file.path.clone(),
+                error: error.clone(),
+            });
+            return;
+        }
+        self.analyzed += 1;
+        let mut flawed = false;
+        for result in &file.results {
+            let Some(tally) = self
+                .presets
+                .iter_mut()
+                .find(|t| t.platform_id == result.platform_id)
+            else {
+                continue;
+            };
+            match result.result.verdict {
+                Verdict::Perfect => tally.perfect += 1,
+                Verdict::Flawed => {
+                    tally.flawed += 1;
+                    flawed = true;
+                }
+            }
+        }
+        if flawed {
+            self.flawed_files += 1;
+        } else {
+            self.perfect_files += 1;
+        }
+    }
+}
+
+// ── Commands ──
+
+/// Analyze many files and/or directories against the selected presets.
+/// Directories are scanned for media files (recursively unless `recursive`
+/// is false). At most `concurrency` ffprobe processes run at once; each
+/// result is emitted as a `batch://file` event while the batch runs. `deep`
+/// adds the black/freeze/silence scan to every file.
+#[tauri::command]
+pub async fn analyze_batch(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    preset_ids: Vec<String>,
+    recursive: Option<bool>,
+    concurrency: Option<usize>,
+    deep: Option<bool>,
+) -> Result<BatchSummary, ExportDoctorError> {
+    license::check_license_valid(&app)?;
+
+    let catalog = presets::catalog(&app);
+    if let Some(unknown) = preset_ids
+        .iter()
+        .find(|id| !catalog.iter().any(|p| &p.id == *id))
+    {
+        return Err(ExportDoctorError::UnknownPreset {
+            id: unknown.clone(),
+        });
+    }
+
+    let recursive = recursive.unwrap_or(true);
+    let files = tauri::async_runtime::spawn_blocking(move || collect_files(&paths, recursive))
+        .await
+        .map_err(|e| ExportDoctorError::InvalidInput {
+            detail: format!("Could not scan the selection: {}", e),
+        })?;
+    if files.is_empty() {
+        return Err(ExportDoctorError::NoMediaFiles);
+    }
+
+    let total = files.len();
+    let workers = concurrency
+        .unwrap_or_else(default_workers)
+        .clamp(1, MAX_WORKERS);
+    eprintln!(
+        "[export-doctor] batch: {} files, {} workers",
+        total, workers
+    );
+
+    let semaphore = Arc::new(Semaphore::new(workers));
+    let catalog = Arc::new(catalog);
+    let preset_ids = Arc::new(preset_ids);
+    let deep = deep.unwrap_or(false);
+
+    let handles: Vec<_> = files
+        .into_iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let app = app.clone();
+            let semaphore = semaphore.clone();
+            let catalog = catalog.clone();
+            let preset_ids = preset_ids.clone();
+            tauri::async_runtime::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.ok();
+                let outcome = evaluate_and_record(&app, &path, &catalog, &preset_ids, deep).await;
+                let (analysis, results, error) = match outcome {
+                    Ok((analysis, results)) => (Some(analysis), results, None),
+                    Err(e) => (None, Vec::new(), Some(e)),
+                };
+                let file = BatchFileResult {
+                    index,
+                    total,
+                    path,
+                    analysis,
+                    results,
+                    error,
+                };
+                if let Err(e) = app.emit(FILE_EVENT, &file) {
+                    eprintln!("[export-doctor] failed to emit batch result: {}", e);
+                }
+                file
+            })
+        })
+        .collect();
+
+    let mut summary = BatchSummary::new(total, &catalog, &preset_ids);
+    for handle in handles {
+        match handle.await {
+            Ok(file) => summary.add(&file),
+            Err(e) => eprintln!("[export-doctor] batch worker failed: {}", e),
+        }
+    }
+    Ok(summary)
+}
+
+// ── Helpers ──
+
+fn default_workers() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+}
+
+/// Expand the selection into a de-duplicated list of files. Directory
+/// entries are sorted so file indices are stable between runs.
+fn collect_files(paths: &[String], recursive: bool) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for path in paths {
+        let root = Path::new(path);
+        if !root.is_dir() {
+            // Missing or unreadable paths still get a per-file error.
+            if seen.insert(path.clone()) {
+                files.push(path.clone());
+            }
+            continue;
+        }
+        let walker = WalkDir::new(root)
+            .max_depth(if recursive { usize::MAX } else { 1 })
+            .sort_by_file_name();
+        for entry in walker.into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_file() && is_media_file(entry.path()) {
+                let file = entry.path().to_string_lossy().to_string();
+                if seen.insert(file.clone()) {
+                    files.push(file);
+                }
+            }
+        }
+    }
+    files
+}
+
+pub(crate) fn is_media_file(path: &Path) -> bool {
+    // Skip dotfiles such as macOS "._clip.mov" resource forks.
+    let hidden = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.'));
+    let media = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| MEDIA_EXTENSIONS.iter().any(|m| m.eq_ignore_ascii_case(e)));
+    media && !hidden
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::PerfectResult;
+    use serde_json::json;
+
+    fn tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("sub/deeper")).unwrap();
+        for file in [
+            "a.mov",
+            "b.MP4",
+            "._a.mov",
+            ".hidden.mp4",
+            "notes.txt",
+            "sub/c.mkv",
+            "sub/deeper/d.mxf",
+        ] {
+            std::fs::write(root.join(file), b"").unwrap();
+        }
+        dir
+    }
+
+    fn under(root: &Path, files: &[&str]) -> Vec<String> {
+        files
+            .iter()
+            .map(|f| root.join(f).to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn collects_media_files_in_nested_folders() {
+        let dir = tree();
+        let root = dir.path();
+        let paths = vec![root.to_string_lossy().to_string()];
+        assert_eq!(
+            collect_files(&paths, true),
+            under(root, &["a.mov", "b.MP4", "sub/c.mkv", "sub/deeper/d.mxf"])
+        );
+        assert_eq!(
+            collect_files(&paths, false),
+            under(root, &["a.mov", "b.MP4"])
+        );
+    }
+
+    #[test]
+    fn collects_each_file_once() {
+        let dir = tree();
+        let root = dir.path();
+        let mut paths = under(root, &["a.mov", "sub", "notes.txt", "missing.mov"]);
+        paths.push(root.to_string_lossy().to_string());
+        paths.extend(under(root, &["missing.mov", "sub/c.mkv"]));
+        // Files named explicitly are kept whatever their extension, and
+        // missing ones are left for the per-file error.
+        assert_eq!(
+            collect_files(&paths, true),
+            under(
+                root,
+                &[
+                    "a.mov",
+                    "sub/c.mkv",
+                    "sub/deeper/d.mxf",
+                    "notes.txt",
+                    "missing.mov",
+                    "b.MP4",
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn media_files() {
+        assert!(is_media_file(Path::new("/renders/final.mov")));
+        assert!(is_media_file(Path::new("/renders/FINAL.MXF")));
+        assert!(is_media_file(Path::new("poster.jpeg")));
+        assert!(!is_media_file(Path::new("/renders/._final.mov")));
+        assert!(!is_media_file(Path::new("/renders/.final.mov")));
+        assert!(!is_media_file(Path::new("/renders/final.mov.txt")));
+        assert!(!is_media_file(Path::new("/renders/mov")));
+        assert!(!is_media_file(Path::new("/renders/final")));
+    }
+
+    fn catalog() -> Vec<presets::Preset> {
+        ["youtube", "netflix"]
+            .into_iter()
+            .map(|id| {
+                serde_json::from_value(json!({
+                    "id": id,
+                    "name": id.to_uppercase(),
+                    "title": id,
+                    "category": "social",
+                    "checks": [],
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn file(path: &str, verdicts: &[(&str, Verdict)]) -> BatchFileResult {
+        BatchFileResult {
+            index: 0,
+            total: 1,
+            path: path.to_string(),
+            analysis: None,
+            results: verdicts
+                .iter()
+                .map(|(id, verdict)| PresetResult {
+                    platform_id: id.to_string(),
+                    platform_name: id.to_uppercase(),
+                    result: PerfectResult {
+                        verdict: *verdict,
+                        checks: Vec::new(),
+                        reasons: Vec::new(),
+                        remediation: None,
+                    },
+                })
+                .collect(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn summary_tallies_files_and_presets() {
+        let ids = vec![
+            "youtube".to_string(),
+            "netflix".to_string(),
+            "gone".to_string(),
+        ];
+        let mut summary = BatchSummary::new(4, &catalog(), &ids);
+        assert_eq!(summary.presets.len(), 2);
+
+        summary.add(&file(
+            "a.mov",
+            &[("youtube", Verdict::Perfect), ("netflix", Verdict::Perfect)],
+        ));
+        summary.add(&file(
+            "b.mov",
+            &[("youtube", Verdict::Perfect), ("netflix", Verdict::Flawed)],
+        ));
+        // A result for a preset that isn't in the summary is ignored.
+        summary.add(&file("c.mov", &[("other", Verdict::Flawed)]));
+        let mut failed = file("d.mov", &[]);
+        failed.error = Some(ExportDoctorError::NoVideoStream);
+        summary.add(&failed);
+
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.analyzed, 3);
+        assert_eq!(summary.perfect_files, 2);
+        assert_eq!(summary.flawed_files, 1);
+        let tallies: Vec<(&str, usize, usize)> = summary
+            .presets
+            .iter()
+            .map(|t| (t.platform_id.as_str(), t.perfect, t.flawed))
+            .collect();
+        assert_eq!(tallies, [("youtube", 2, 0), ("netflix", 1, 1)]);
+        assert_eq!(summary.failures.len(), 1);
+        assert_eq!(summary.failures[0].path, "d.mov");
+        assert_eq!(summary.failures[0].error, ExportDoctorError::NoVideoStream);
+    }
+}
@@ -0,0 +1,547 @@
+//! Probe results cached on disk by file fingerprint, so re-checking an
+//! unchanged file (a 40 GB master checked against one more preset, say)
+//! skips ffprobe and every analysis pass that already ran on it.
+//!
+//! One JSON file per source path under the app cache directory. An entry is
+//! used only when the file's size, modification time and sample hash all
+//! still match, and it was written by this version of the app. Entries
+//! unused for `MAX_AGE` are dropped, and beyond `MAX_ENTRIES` the least
+//! recently used go first.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::Manager;
+
+use crate::error::ExportDoctorError;
+use crate::{bundle_identifier, ExtendedMetadata, Host};
+
+const CACHE_DIR: &str = "probe-cache";
+
+/// Entries kept at most.
+const MAX_ENTRIES: usize = 500;
+
+/// Entries unused for this long are dropped.
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Bytes read from each of the start, middle and end of a file for its
+/// sample hash.
+const HASH_CHUNK: u64 = 1024 * 1024;
+
+/// Half-written entries older than this were left by an interrupted write.
+const MAX_TMP_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Analysis code changes between releases; never reuse another version's
+/// results.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// ── Structs ──
+
+/// What identifies one version of a file's contents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fingerprint {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub sample_hash: String,
+}
+
+/// Analysis passes whose results are in the cached metadata. A pass that
+/// ran and found nothing (no crop measured, say) still counts as done.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Passes {
+    pub packets: bool,
+    pub hdr_frame: bool,
+    pub loudness: bool,
+    pub qc: bool,
+    pub crop: bool,
+    pub interlace: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    version: String,
+    path: String,
+    fingerprint: Fingerprint,
+    passes: Passes,
+    metadata: ExtendedMetadata,
+    /// Whole-file hash, once the history has computed it for this version.
+    #[serde(default)]
+    content_hash: Option<String>,
+}
+
+/// Result of looking a file up.
+pub struct Lookup {
+    /// `None` when the file couldn't be read; nothing is cached then.
+    pub fingerprint: Option<Fingerprint>,
+    pub hit: Option<Hit>,
+}
+
+/// What the cache had for the file as it is now.
+pub struct Hit {
+    pub passes: Passes,
+    pub metadata: ExtendedMetadata,
+    pub content_hash: Option<String>,
+}
+
+// ── Commands ──
+
+/// Delete every cached probe, and any half-written ones. Returns how many
+/// entries were removed.
+#[tauri::command]
+pub async fn clear_probe_cache(app: tauri::AppHandle) -> Result<u32, ExportDoctorError> {
+    let Some(dir) = cache_dir(Host::App(&app)) else {
+        return Ok(0);
+    };
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => {
+            return Err(ExportDoctorError::FileWriteFailed {
+                path: dir.to_string_lossy().to_string(),
+                detail: e.to_string(),
+            })
+        }
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(kind) = file_kind(&path) else {
+            continue;
+        };
+        std::fs::remove_file(&path).map_err(|e| ExportDoctorError::FileWriteFailed {
+            path: path.to_string_lossy().to_string(),
+            detail: e.to_string(),
+        })?;
+        if kind == FileKind::Entry {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+// ── Lookup and store ──
+
+/// Fingerprint `path` and return its cached metadata if the fingerprint
+/// still matches.
+pub async fn lookup(host: Host<'_>, path: &str) -> Lookup {
+    let dir = cache_dir(host);
+    let path = path.to_string();
+    // Hashing and reading the entry are blocking file I/O.
+    tauri::async_runtime::spawn_blocking(move || lookup_blocking(dir.as_deref(), &path))
+        .await
+        .unwrap_or(Lookup {
+            fingerprint: None,
+            hit: None,
+        })
+}
+
+/// Save `metadata` with the passes that produced it. Failures are logged:
+/// a cache that can't be written only costs time.
+pub async fn store(
+    host: Host<'_>,
+    path: &str,
+    fingerprint: &Fingerprint,
+    passes: Passes,
+    metadata: &ExtendedMetadata,
+    content_hash: Option<&str>,
+) {
+    let Some(dir) = cache_dir(host) else {
+        return;
+    };
+    let entry = CacheEntry {
+        version: VERSION.to_string(),
+        path: path.to_string(),
+        fingerprint: fingerprint.clone(),
+        passes,
+        metadata: metadata.clone(),
+        content_hash: content_hash.map(str::to_string),
+    };
+    let outcome = tauri::async_runtime::spawn_blocking(move || {
+        write_entry(&dir, &entry)?;
+        prune(&dir)
+    })
+    .await;
+    match outcome {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => eprintln!("[export-doctor] failed to write probe cache: {}", e),
+        Err(e) => eprintln!("[export-doctor] probe cache task failed: {}", e),
+    }
+}
+
+/// Add the whole-file hash to the entry for `path`, so later analyses of
+/// the same version don't read the file again. Blocking; failures are
+/// logged.
+pub fn remember_content_hash(
+    host: Host<'_>,
+    path: &str,
+    fingerprint: &Fingerprint,
+    content_hash: &str,
+) {
+    let Some(dir) = cache_dir(host) else {
+        return;
+    };
+    let Some(mut entry) = read_matching(&dir, path, fingerprint) else {
+        return;
+    };
+    entry.content_hash = Some(content_hash.to_string());
+    if let Err(e) = write_entry(&dir, &entry) {
+        eprintln!("[export-doctor] failed to write probe cache: {}", e);
+    }
+}
+
+fn lookup_blocking(dir: Option<&Path>, path: &str) -> Lookup {
+    let fingerprint = fingerprint(Path::new(path)).ok();
+    let hit = match (dir, &fingerprint) {
+        (Some(dir), Some(fingerprint)) => read_matching(dir, path, fingerprint).map(|entry| {
+            touch(&entry_path(dir, path));
+            Hit {
+                passes: entry.passes,
+                metadata: entry.metadata,
+                content_hash: entry.content_hash,
+            }
+        }),
+        _ => None,
+    };
+    Lookup { fingerprint, hit }
+}
+
+/// The entry for `path`, if this version of the app wrote it for the file
+/// as `fingerprint` describes it.
+fn read_matching(dir: &Path, path: &str, fingerprint: &Fingerprint) -> Option<CacheEntry> {
+    read_entry(dir, path).filter(|entry| {
+        entry.version == VERSION && entry.path == path && &entry.fingerprint == fingerprint
+    })
+}
+
+fn fingerprint(path: &Path) -> std::io::Result<Fingerprint> {
+    let meta = std::fs::metadata(path)?;
+    Ok(Fingerprint {
+        size: meta.len(),
+        modified: meta.modified().ok(),
+        sample_hash: sample_hash(path)?,
+    })
+}
+
+/// Entries that can't be read or parsed (an older format) are misses.
+fn read_entry(dir: &Path, path: &str) -> Option<CacheEntry> {
+    let contents = std::fs::read_to_string(entry_path(dir, path)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Write via a temporary file so a concurrent lookup never reads half an
+/// entry.
+fn write_entry(dir: &Path, entry: &CacheEntry) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let target = entry_path(dir, &entry.path);
+    let tmp = target.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(entry)?)?;
+    std::fs::rename(&tmp, &target)
+}
+
+/// Mark an entry as used now; its file's modification time is what `prune`
+/// goes by. Best effort: at worst the entry is dropped early.
+fn touch(entry: &Path) {
+    let touched = File::options()
+        .write(true)
+        .open(entry)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    if let Err(e) = touched {
+        eprintln!("[export-doctor] failed to touch probe cache entry: {}", e);
+    }
+}
+
+/// Drop entries unused for `MAX_AGE`, then the least recently used beyond
+/// `MAX_ENTRIES`. Temporary files older than `MAX_TMP_AGE` go too.
+fn prune(dir: &Path) -> std::io::Result<()> {
+    let now = SystemTime::now();
+    let mut kept = Vec::new();
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        let Some(kind) = file_kind(&path) else {
+            continue;
+        };
+        let Ok(used) = entry.metadata().and_then(|m| m.modified()) else {
+            continue;
+        };
+        let max_age = match kind {
+            FileKind::Entry => MAX_AGE,
+            FileKind::Temporary => MAX_TMP_AGE,
+        };
+        if now.duration_since(used).unwrap_or_default() > max_age {
+            std::fs::remove_file(&path)?;
+        } else if kind == FileKind::Entry {
+            kept.push((used, path));
+        }
+    }
+    if kept.len() > MAX_ENTRIES {
+        kept.sort_by_key(|(used, _)| std::cmp::Reverse(*used));
+        for (_, path) in &kept[MAX_ENTRIES..] {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Headless runs resolve the directory the way Tauri does, so the CLI and
+/// the app share one cache.
+fn cache_dir(host: Host<'_>) -> Option<PathBuf> {
+    let base = match host {
+        Host::App(app) => app.path().app_cache_dir().ok()?,
+        Host::Headless => dirs::cache_dir()?.join(bundle_identifier()?),
+    };
+    Some(base.join(CACHE_DIR))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Entry,
+    /// Left by `write_entry`, normally renamed straight away.
+    Temporary,
+}
+
+/// What a file in the cache directory is; `None` for anything else.
+fn file_kind(path: &Path) -> Option<FileKind> {
+    let name = path.file_name()?.to_str()?;
+    if name.ends_with(".json.tmp") {
+        Some(FileKind::Temporary)
+    } else if name.ends_with(".json") {
+        Some(FileKind::Entry)
+    } else {
+        None
+    }
+}
+
+/// One entry per source path; re-analyzing a changed file replaces it.
+fn entry_path(dir: &Path, path: &str) -> PathBuf {
+    let digest = Sha256::digest(path.as_bytes());
+    dir.join(format!("{:x}.json", digest))
+}
+
+/// SHA-256 of the file size plus 1 MiB from its start, middle and end.
+/// Cheap on multi-gigabyte masters and still changes with any re-export,
+/// but not a hash of the whole file.
+fn sample_hash(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    let mut hasher = Sha256::new();
+    hasher.update(size.to_le_bytes());
+
+    let mut offsets = vec![
+        0,
+        size.saturating_sub(HASH_CHUNK) / 2,
+        size.saturating_sub(HASH_CHUNK),
+    ];
+    offsets.dedup();
+    let mut buf = Vec::with_capacity(HASH_CHUNK as usize);
+    for offset in offsets {
+        file.seek(SeekFrom::Start(offset))?;
+        buf.clear();
+        (&mut file).take(HASH_CHUNK).read_to_end(&mut buf)?;
+        hasher.update(&buf);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata() -> ExtendedMetadata {
+        let probe = json!({
+            "streams": [{ "index": 0, "codec_type": "video", "codec_name": "h264" }],
+            "format": { "format_name": "mov,mp4", "duration": "1.0" },
+        });
+        crate::extract_metadata(&probe).unwrap()
+    }
+
+    fn entry(path: &str, fingerprint: &Fingerprint) -> CacheEntry {
+        CacheEntry {
+            version: VERSION.to_string(),
+            path: path.to_string(),
+            fingerprint: fingerprint.clone(),
+            passes: Passes {
+                loudness: true,
+                ..Default::default()
+            },
+            metadata: metadata(),
+            content_hash: None,
+        }
+    }
+
+    fn fp(size: u64) -> Fingerprint {
+        Fingerprint {
+            size,
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            sample_hash: "abc".to_string(),
+        }
+    }
+
+    fn age(path: &Path, by: Duration) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - by)
+            .unwrap();
+    }
+
+    fn names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn small_files_hash_their_bytes_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clip.mov");
+        std::fs::write(&path, b"tiny file").unwrap();
+
+        // Start, middle and end are all offset 0: the bytes go in once.
+        let mut expected = Sha256::new();
+        expected.update(9u64.to_le_bytes());
+        expected.update(b"tiny file");
+        assert_eq!(
+            sample_hash(&path).unwrap(),
+            format!("{:x}", expected.finalize())
+        );
+
+        std::fs::write(&path, b"").unwrap();
+        assert!(sample_hash(&path).is_ok());
+        assert!(sample_hash(&dir.path().join("missing.mov")).is_err());
+    }
+
+    #[test]
+    fn large_files_hash_three_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("master.mov");
+        let size = (HASH_CHUNK * 4) as usize;
+        let mut bytes = vec![0u8; size];
+        std::fs::write(&path, &bytes).unwrap();
+        let original = sample_hash(&path).unwrap();
+
+        // A change inside the middle sample is seen...
+        bytes[size / 2] = 1;
+        std::fs::write(&path, &bytes).unwrap();
+        let changed = sample_hash(&path).unwrap();
+        assert_ne!(changed, original);
+
+        // ...one between samples is not; size and mtime cover the rest.
+        bytes[HASH_CHUNK as usize + 10] = 1;
+        std::fs::write(&path, &bytes).unwrap();
+        assert_eq!(sample_hash(&path).unwrap(), changed);
+    }
+
+    #[test]
+    fn read_matching_checks_version_path_and_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        write_entry(dir, &entry("/media/a.mov", &fp(10))).unwrap();
+
+        let hit = read_matching(dir, "/media/a.mov", &fp(10)).unwrap();
+        assert!(hit.passes.loudness);
+        assert!(read_matching(dir, "/media/a.mov", &fp(11)).is_none());
+        let mut touched = fp(10);
+        touched.modified = Some(SystemTime::UNIX_EPOCH);
+        assert!(read_matching(dir, "/media/a.mov", &touched).is_none());
+        assert!(read_matching(dir, "/media/b.mov", &fp(10)).is_none());
+
+        // Another release's entry is a miss.
+        let mut old = entry("/media/a.mov", &fp(10));
+        old.version = "0.0.1".to_string();
+        write_entry(dir, &old).unwrap();
+        assert!(read_matching(dir, "/media/a.mov", &fp(10)).is_none());
+
+        // An entry file that belongs to another path is a miss too.
+        write_entry(dir, &entry("/media/b.mov", &fp(10))).unwrap();
+        std::fs::copy(
+            entry_path(dir, "/media/b.mov"),
+            entry_path(dir, "/media/a.mov"),
+        )
+        .unwrap();
+        assert!(read_matching(dir, "/media/a.mov", &fp(10)).is_none());
+
+        // So is one that doesn't parse.
+        std::fs::write(entry_path(dir, "/media/a.mov"), "{").unwrap();
+        assert!(read_matching(dir, "/media/a.mov", &fp(10)).is_none());
+    }
+
+    #[test]
+    fn prune_drops_old_entries_and_stale_temporary_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        for path in ["/a.mov", "/b.mov"] {
+            write_entry(dir, &entry(path, &fp(1))).unwrap();
+        }
+        age(
+            &entry_path(dir, "/a.mov"),
+            MAX_AGE + Duration::from_secs(60),
+        );
+        let stale = dir.join("stale.json.tmp");
+        let fresh = dir.join("fresh.json.tmp");
+        std::fs::write(&stale, "{").unwrap();
+        std::fs::write(&fresh, "{").unwrap();
+        age(&stale, MAX_TMP_AGE + Duration::from_secs(60));
+        std::fs::write(dir.join("notes.txt"), "keep").unwrap();
+
+        prune(dir).unwrap();
+        let b = entry_path(dir, "/b.mov");
+        let mut expected = vec![
+            b.file_name().unwrap().to_string_lossy().to_string(),
+            "fresh.json.tmp".to_string(),
+            "notes.txt".to_string(),
+        ];
+        expected.sort();
+        assert_eq!(names(dir), expected);
+    }
+
+    #[test]
+    fn prune_keeps_the_most_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let extra = 3;
+        for i in 0..MAX_ENTRIES + extra {
+            let path = format!("/clip{}.mov", i);
+            write_entry(dir, &entry(&path, &fp(1))).unwrap();
+            // clip0 is the least recently used.
+            age(
+                &entry_path(dir, &path),
+                Duration::from_secs((MAX_ENTRIES + extra - i) as u64 * 60),
+            );
+        }
+        // A lookup hit makes clip1 the most recent.
+        touch(&entry_path(dir, "/clip1.mov"));
+
+        prune(dir).unwrap();
+        assert_eq!(names(dir).len(), MAX_ENTRIES);
+        let exists = |i: usize| entry_path(dir, &format!("/clip{}.mov", i)).exists();
+        assert!(!exists(0));
+        assert!(exists(1));
+        assert!(!exists(2));
+        assert!(!exists(3));
+        assert!(exists(4));
+        assert!(exists(MAX_ENTRIES + extra - 1));
+    }
+
+    #[test]
+    fn file_kinds() {
+        assert_eq!(file_kind(Path::new("/c/ab.json")), Some(FileKind::Entry));
+        assert_eq!(
+            file_kind(Path::new("/c/ab.json.tmp")),
+            Some(FileKind::Temporary)
+        );
+        assert_eq!(file_kind(Path::new("/c/ab.txt")), None);
+        assert_eq!(file_kind(Path::new("/c/ab.tmp")), None);
+    }
+}
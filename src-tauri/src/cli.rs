@@ -0,0 +1,440 @@
+//! Headless mode: `export-doctor check <file...> --preset <id> [--format json]`.
+//!
+//! Runs the same probe and rule engine as the app without starting Tauri, so
+//! it works on render nodes with no display. Custom presets and the license
+//! are read straight from the app's store files.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::error::ExportDoctorError;
+use crate::rules::{PresetResult, Verdict};
+use crate::{bundle_identifier, evaluate_headless, license, presets, AnalyzeResult};
+
+const EXIT_OK: i32 = 0;
+const EXIT_FLAWED: i32 = 1;
+const EXIT_ERROR: i32 = 2;
+
+const USAGE: &str = "\
+Usage: export-doctor check <file>... --preset <id> [--preset <id>...] [--format text|json] [--deep]
+
+Checks each file against the given presets without opening a window.
+
+Options:
+  -p, --preset <id>    Preset to evaluate (repeatable, or comma-separated)
+  -f, --format <fmt>   Output format: text (default) or json
+      --deep           Also scan for black gaps, frozen frames, silence, black
+                       bars and interlacing
+      --list-presets   Print the available preset ids and exit
+  -h, --help           Show this help
+
+Exit status: 0 if every preset is PERFECT, 1 if any is FLAWED,
+2 on usage errors or files that could not be analyzed.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug)]
+struct Options {
+    files: Vec<String>,
+    preset_ids: Vec<String>,
+    format: Format,
+    list_presets: bool,
+    deep: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FileReport {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    analysis: Option<AnalyzeResult>,
+    results: Vec<PresetResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ExportDoctorError>,
+}
+
+/// True when the process arguments (without argv[0]) ask for headless mode.
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    args.first().is_some_and(|a| a == "check")
+}
+
+/// Run `check` with the arguments following the subcommand. Returns the
+/// process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let args = args.strip_prefix(&["check".to_string()]).unwrap_or(args);
+    let opts = match parse_args(args) {
+        Ok(Some(opts)) => opts,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return EXIT_OK;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return EXIT_ERROR;
+        }
+    };
+
+    let data_dir = app_data_dir();
+    let mut catalog = presets::builtin_presets().to_vec();
+    if let Some(val) = data_dir
+        .as_deref()
+        .and_then(|dir| read_store_value(dir, presets::STORE_FILE, presets::STORE_KEY))
+    {
+        catalog.extend(presets::custom_presets_from_value(val));
+    }
+
+    if opts.list_presets {
+        for preset in &catalog {
+            println!("{:<20} {}", preset.id, preset.name);
+        }
+        return EXIT_OK;
+    }
+
+    if let Some(unknown) = opts
+        .preset_ids
+        .iter()
+        .find(|id| !catalog.iter().any(|p| &p.id == *id))
+    {
+        eprintln!("error: unknown preset \"{}\" (see --list-presets)", unknown);
+        return EXIT_ERROR;
+    }
+
+    let stored_license = data_dir.as_deref().and_then(|dir| {
+        read_store_value(dir, license::STORE_FILE, license::STORE_KEY).or_else(|| start_trial(dir))
+    });
+    if let Err(e) = license::check_stored_license(stored_license) {
+        eprintln!("error: {}", e);
+        return EXIT_ERROR;
+    }
+
+    let reports: Vec<FileReport> = opts
+        .files
+        .iter()
+        .map(|path| check_file(path, &catalog, &opts.preset_ids, opts.deep))
+        .collect();
+
+    match opts.format {
+        Format::Json => match serde_json::to_string_pretty(&reports) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return EXIT_ERROR;
+            }
+        },
+        Format::Text => print_text(&reports),
+    }
+
+    if reports.iter().any(|r| r.error.is_some()) {
+        EXIT_ERROR
+    } else if reports
+        .iter()
+        .flat_map(|r| &r.results)
+        .any(|r| r.result.verdict == Verdict::Flawed)
+    {
+        EXIT_FLAWED
+    } else {
+        EXIT_OK
+    }
+}
+
+fn check_file(
+    path: &str,
+    catalog: &[presets::Preset],
+    preset_ids: &[String],
+    deep: bool,
+) -> FileReport {
+    match evaluate_headless(path, catalog, preset_ids, deep) {
+        Ok((analysis, results)) => FileReport {
+            path: path.to_string(),
+            analysis: Some(analysis),
+            results,
+            error: None,
+        },
+        Err(e) => FileReport {
+            path: path.to_string(),
+            analysis: None,
+            results: Vec::new(),
+            error: Some(e),
+        },
+    }
+}
+
+/// `Ok(None)` means help was requested.
+fn parse_args(args: &[String]) -> Result<Option<Options>, String> {
+    let mut opts = Options {
+        files: Vec::new(),
+        preset_ids: Vec::new(),
+        format: Format::Text,
+        list_presets: false,
+        deep: false,
+    };
+
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        // The next option is never taken as a value: `--preset --deep`
+        // is a missing preset id, not a preset called "--deep".
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| iter.next_if(|next| !next.starts_with("--")).cloned())
+                .ok_or_else(|| format!("{} needs a value", name))
+        };
+        match flag {
+            "-h" | "--help" => return Ok(None),
+            "--list-presets" => opts.list_presets = true,
+            "--deep" => opts.deep = true,
+            "-p" | "--preset" => opts.preset_ids.extend(
+                value("--preset")?
+                    .split(',')
+                    .map(|id| id.trim().to_string())
+                    .filter(|id| !id.is_empty()),
+            ),
+            "-f" | "--format" => {
+                opts.format = match value("--format")?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    other => return Err(format!("unknown format \"{}\"", other)),
+                }
+            }
+            _ if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(format!("unknown option \"{}\"", flag))
+            }
+            _ => opts.files.push(arg.clone()),
+        }
+    }
+
+    if opts.list_presets {
+        return Ok(Some(opts));
+    }
+    if opts.files.is_empty() {
+        return Err("no input files".to_string());
+    }
+    if opts.preset_ids.is_empty() {
+        return Err("at least one --preset is required".to_string());
+    }
+    Ok(Some(opts))
+}
+
+fn print_text(reports: &[FileReport]) {
+    for report in reports {
+        let Some(analysis) = &report.analysis else {
+            println!("{}", report.path);
+            match &report.error {
+                Some(e) => println!("  ERROR  {}", e),
+                None => println!("  ERROR  unknown error"),
+            }
+            println!();
+            continue;
+        };
+        let m = &analysis.metadata;
+        println!(
+            "{}  ({}x{}, {}, {:.2} fps, {:.2}s)",
+            report.path,
+            m.display_width,
+            m.display_height,
+            m.video_codec,
+            m.fps_avg,
+            m.duration_sec
+        );
+        for preset in &report.results {
+            let verdict = match preset.result.verdict {
+                Verdict::Perfect => "PERFECT",
+                Verdict::Flawed => "FLAWED",
+            };
+            println!("  {:<8} {}", verdict, preset.platform_name);
+            for check in preset.result.checks.iter().filter(|c| !c.ok) {
+                println!(
+                    "    x {}: {} (expected {}) - {}",
+                    check.field, check.value, check.expected, check.reason
+                );
+            }
+            if let Some(fix) = &preset.result.remediation {
+                let output = fix.default_output(&report.path);
+                println!("    fix: {}", fix.shell_command(&report.path, &output));
+                if !fix.unresolved.is_empty() {
+                    println!("    not fixed: {}", fix.unresolved.join(", "));
+                }
+            }
+        }
+        if let Some(crop) = &m.crop {
+            println!(
+                "  Active   {}x{} at {},{} of {}x{}",
+                crop.width, crop.height, crop.x, crop.y, crop.frame_width, crop.frame_height
+            );
+        }
+        if let Some(stats) = &m.interlace {
+            println!(
+                "  Scan     {} (TFF {}, BFF {}, progressive {}, undetermined {})",
+                stats.scan(),
+                stats.tff,
+                stats.bff,
+                stats.progressive,
+                stats.undetermined
+            );
+        }
+        if let Some(qc) = &m.qc {
+            println!("  QC       {} issue(s)", qc.events.len());
+            for event in &qc.events {
+                println!(
+                    "    ! {:?} {:.2}s-{:.2}s ({:.2}s)",
+                    event.kind, event.start_sec, event.end_sec, event.duration_sec
+                );
+            }
+        }
+        println!();
+    }
+}
+
+/// Where the app's store files live: the same directory Tauri resolves as
+/// `app_data_dir`, keyed by the bundle identifier.
+fn app_data_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(bundle_identifier()?))
+}
+
+fn read_store_value(dir: &Path, file: &str, key: &str) -> Option<serde_json::Value> {
+    let contents = std::fs::read_to_string(dir.join(file)).ok()?;
+    let mut store: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&contents).ok()?;
+    store.remove(key)
+}
+
+/// Put `value` under `key`, keeping the file's other keys. A file that
+/// exists but doesn't parse is left alone.
+fn write_store_value(
+    dir: &Path,
+    file: &str,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    let path = dir.join(file);
+    let mut store: serde_json::Map<String, serde_json::Value> = match std::fs::read_to_string(&path)
+    {
+        Ok(contents) => serde_json::from_str(&contents).map_err(|e| e.to_string())?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::Map::new(),
+        Err(e) => return Err(e.to_string()),
+    };
+    store.insert(key.to_string(), value);
+    let json = serde_json::to_vec_pretty(&store).map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+/// First headless run on a machine where the app was never opened: save the
+/// trial start the way the app's first launch does, so later runs count
+/// from it. `None` when it couldn't be saved.
+fn start_trial(dir: &Path) -> Option<serde_json::Value> {
+    let trial = license::new_trial_value()?;
+    match write_store_value(dir, license::STORE_FILE, license::STORE_KEY, trial.clone()) {
+        Ok(()) => Some(trial),
+        Err(e) => {
+            eprintln!("error: could not save the trial license: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        parse_args(&args)
+    }
+
+    fn options(args: &[&str]) -> Options {
+        parse(args).unwrap().unwrap()
+    }
+
+    #[test]
+    fn files_and_repeated_presets() {
+        let opts = options(&[
+            "a.mov",
+            "-p",
+            "youtube",
+            "b.mp4",
+            "--preset",
+            "netflix,  vimeo,",
+        ]);
+        assert_eq!(opts.files, ["a.mov", "b.mp4"]);
+        assert_eq!(opts.preset_ids, ["youtube", "netflix", "vimeo"]);
+        assert_eq!(opts.format, Format::Text);
+        assert!(!opts.deep);
+        assert!(!opts.list_presets);
+
+        let opts = options(&["--preset=youtube", "a.mov", "--preset=youtube"]);
+        assert_eq!(opts.preset_ids, ["youtube", "youtube"]);
+    }
+
+    #[test]
+    fn json_format_and_deep() {
+        for args in [
+            ["a.mov", "-p", "youtube", "--format", "json"].as_slice(),
+            &["a.mov", "-p", "youtube", "-f", "json"],
+            &["a.mov", "-p", "youtube", "--format=json"],
+        ] {
+            assert_eq!(options(args).format, Format::Json, "{:?}", args);
+        }
+        assert_eq!(
+            options(&["a.mov", "-p", "youtube", "-f", "text"]).format,
+            Format::Text
+        );
+        assert_eq!(
+            parse(&["a.mov", "-p", "youtube", "--format", "xml"]).unwrap_err(),
+            "unknown format \"xml\""
+        );
+        assert!(options(&["--deep", "a.mov", "-p", "youtube"]).deep);
+    }
+
+    #[test]
+    fn usage_errors() {
+        assert_eq!(
+            parse(&["a.mov", "-p", "youtube", "--verbose"]).unwrap_err(),
+            "unknown option \"--verbose\""
+        );
+        assert_eq!(
+            parse(&["a.mov", "-p", "youtube", "--verbose=2"]).unwrap_err(),
+            "unknown option \"--verbose\""
+        );
+        assert_eq!(
+            parse(&["a.mov", "--preset"]).unwrap_err(),
+            "--preset needs a value"
+        );
+        assert_eq!(
+            parse(&["a.mov", "--preset", "--deep"]).unwrap_err(),
+            "--preset needs a value"
+        );
+        assert_eq!(
+            parse(&["a.mov", "--format"]).unwrap_err(),
+            "--format needs a value"
+        );
+        assert_eq!(parse(&["-p", "youtube"]).unwrap_err(), "no input files");
+        assert_eq!(parse(&[]).unwrap_err(), "no input files");
+        assert_eq!(
+            parse(&["a.mov"]).unwrap_err(),
+            "at least one --preset is required"
+        );
+        // Commas alone name no preset.
+        assert_eq!(
+            parse(&["a.mov", "-p", ","]).unwrap_err(),
+            "at least one --preset is required"
+        );
+    }
+
+    #[test]
+    fn help_and_listing_need_no_files() {
+        assert!(parse(&["a.mov", "--help"]).unwrap().is_none());
+        assert!(parse(&["-h"]).unwrap().is_none());
+        assert!(options(&["--list-presets"]).list_presets);
+        // A lone "-" is a file name, not an option.
+        assert_eq!(options(&["-", "-p", "youtube"]).files, ["-"]);
+    }
+}
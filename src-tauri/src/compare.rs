@@ -0,0 +1,468 @@
+//! Side-by-side comparison of two analyses: every metadata field that
+//! differs, and for each preset which checks went from failing to passing
+//! or back. Either side can be a file on disk or a history entry, so a
+//! re-export can be held against what was actually delivered.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::ExportDoctorError;
+use crate::presets::Metric;
+use crate::rules::{self, FieldCheck, PresetResult, Verdict};
+use crate::{analyze_and_evaluate, history, license, presets, AnalyzeResult, ExtendedMetadata};
+
+// ── Structs ──
+
+/// One side of a comparison, as sent by the frontend:
+/// `{ "file": "/path" }` or `{ "history": 12 }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompareSource {
+    File(String),
+    History(i64),
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareSide {
+    pub path: String,
+    /// Set when this side came from the history.
+    pub history_id: Option<i64>,
+    /// When the history entry was recorded, in Unix milliseconds.
+    pub analyzed_at: Option<i64>,
+    pub analysis: AnalyzeResult,
+    pub results: Vec<PresetResult>,
+}
+
+/// A metadata field that differs. `field` is a dotted path into
+/// `ExtendedMetadata` ("hdr.maxCll", "streams[1].codecName"); a side that
+/// lacks the field has `null`. Streams are matched by container index, so
+/// `streams[1]` is the stream with index 1 on both sides, and GOP keyframes
+/// are compared as a summary ("gop.keyframes.maxInterval").
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckFlip {
+    pub field: String,
+    pub before: FieldCheck,
+    pub after: FieldCheck,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetDiff {
+    pub platform_id: String,
+    pub platform_name: String,
+    pub before: Verdict,
+    pub after: Verdict,
+    /// Checks that failed in `a` and pass in `b`.
+    pub fixed: Vec<CheckFlip>,
+    /// Checks that passed in `a` and fail in `b`.
+    pub broken: Vec<CheckFlip>,
+    /// Checks left out of `fixed` and `broken` because one side lacks the
+    /// measurement they read, e.g. a history entry recorded when no preset
+    /// asked for loudness.
+    pub not_measured: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareResult {
+    pub a: CompareSide,
+    pub b: CompareSide,
+    pub changes: Vec<FieldChange>,
+    pub presets: Vec<PresetDiff>,
+}
+
+// ── Commands ──
+
+/// Compare `a` (before) with `b` (after) under `preset_ids`. History
+/// entries are evaluated again against today's presets, so a flipped check
+/// means the file changed rather than the preset.
+#[tauri::command]
+pub async fn compare_files(
+    app: tauri::AppHandle,
+    a: CompareSource,
+    b: CompareSource,
+    preset_ids: Vec<String>,
+    deep: Option<bool>,
+) -> Result<CompareResult, ExportDoctorError> {
+    license::check_license_valid(&app)?;
+    let catalog = presets::catalog(&app);
+    let deep = deep.unwrap_or(false);
+    let a = load_side(&app, a, &catalog, &preset_ids, deep).await?;
+    let b = load_side(&app, b, &catalog, &preset_ids, deep).await?;
+
+    let before = comparable(&a.analysis.metadata);
+    let after = comparable(&b.analysis.metadata);
+    let mut changes = Vec::new();
+    diff_values(String::new(), &before, &after, &mut changes);
+
+    let presets = a
+        .results
+        .iter()
+        .filter_map(|before| {
+            let after = b
+                .results
+                .iter()
+                .find(|r| r.platform_id == before.platform_id)?;
+            let preset = catalog.iter().find(|p| p.id == before.platform_id)?;
+            let measured = |field: &str| {
+                preset
+                    .checks
+                    .iter()
+                    .find(|c| c.field == field)
+                    .is_none_or(|c| {
+                        measured(&a.analysis.metadata, c.metric)
+                            && measured(&b.analysis.metadata, c.metric)
+                    })
+            };
+            Some(diff_preset(before, after, measured))
+        })
+        .collect();
+
+    Ok(CompareResult {
+        a,
+        b,
+        changes,
+        presets,
+    })
+}
+
+// ── Helpers ──
+
+async fn load_side(
+    app: &tauri::AppHandle,
+    source: CompareSource,
+    catalog: &[presets::Preset],
+    preset_ids: &[String],
+    deep: bool,
+) -> Result<CompareSide, ExportDoctorError> {
+    match source {
+        CompareSource::File(path) => {
+            let (analysis, results) =
+                analyze_and_evaluate(app, &path, catalog, preset_ids, deep).await?;
+            Ok(CompareSide {
+                path,
+                history_id: None,
+                analyzed_at: None,
+                analysis,
+                results,
+            })
+        }
+        CompareSource::History(id) => {
+            let entry = history::load_entry(app, id).await?;
+            let results = rules::evaluate_presets(&entry.analysis.metadata, catalog, preset_ids)?;
+            Ok(CompareSide {
+                path: entry.summary.path,
+                history_id: Some(id),
+                analyzed_at: Some(entry.summary.analyzed_at),
+                analysis: entry.analysis,
+                results,
+            })
+        }
+    }
+}
+
+/// The metadata as it is diffed. The keyframe list holds a timestamp per
+/// GOP, and one inserted keyframe would shift every later entry, so it is
+/// replaced by a summary.
+fn comparable(meta: &ExtendedMetadata) -> Value {
+    let mut value = serde_json::to_value(meta).unwrap_or(Value::Null);
+    if let Some(gop) = &meta.gop {
+        value["gop"]["keyframes"] = keyframe_summary(&gop.keyframes);
+    }
+    value
+}
+
+/// Count, first and last keyframe and interval statistics, in seconds
+/// rounded to the millisecond so timestamp noise doesn't show up.
+fn keyframe_summary(keyframes: &[f64]) -> Value {
+    let round = |secs: f64| (secs * 1000.0).round() / 1000.0;
+    let intervals: Vec<f64> = keyframes.windows(2).map(|w| w[1] - w[0]).collect();
+    let min = intervals.iter().copied().reduce(f64::min);
+    let max = intervals.iter().copied().reduce(f64::max);
+    let avg =
+        (!intervals.is_empty()).then(|| intervals.iter().sum::<f64>() / intervals.len() as f64);
+    json!({
+        "count": keyframes.len(),
+        "first": keyframes.first().copied().map(round),
+        "last": keyframes.last().copied().map(round),
+        "minInterval": min.map(round),
+        "maxInterval": max.map(round),
+        "avgInterval": avg.map(round),
+    })
+}
+
+/// Arrays whose items all carry a numeric `index` (streams) are matched by
+/// it rather than by position, so a stream added or dropped in front
+/// doesn't make every later one look changed.
+fn keyed_by_index(items: &[Value]) -> bool {
+    items
+        .iter()
+        .all(|item| item.get("index").is_some_and(Value::is_u64))
+}
+
+/// Walk both values together, recording every leaf that differs. Arrays
+/// are compared by index, or by their items' `index` field where they have
+/// one; an object or array on only one side is reported whole.
+fn diff_values(field: String, before: &Value, after: &Value, out: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = if field.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", field, key)
+                };
+                diff_values(
+                    child,
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if keyed_by_index(a) && keyed_by_index(b) => {
+            let mut keys: Vec<u64> = a
+                .iter()
+                .chain(b)
+                .filter_map(|item| item["index"].as_u64())
+                .collect();
+            keys.sort_unstable();
+            keys.dedup();
+            fn find(items: &[Value], key: u64) -> Option<&Value> {
+                items
+                    .iter()
+                    .find(|item| item["index"].as_u64() == Some(key))
+            }
+            for key in keys {
+                diff_values(
+                    format!("{}[{}]", field, key),
+                    find(a, key).unwrap_or(&Value::Null),
+                    find(b, key).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_values(
+                    format!("{}[{}]", field, i),
+                    a.get(i).unwrap_or(&Value::Null),
+                    b.get(i).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        _ if before != after => out.push(FieldChange {
+            field,
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Whether `meta` has what `metric` is read from. The slower passes only run
+/// when a preset (or a deep check) asks for them, so a side may never have
+/// had them. A file without audio has nothing to measure for loudness.
+fn measured(meta: &ExtendedMetadata, metric: Metric) -> bool {
+    if metric.is_loudness() {
+        meta.loudness.is_some() || !meta.streams.iter().any(|s| s.codec_type == "audio")
+    } else if metric.is_frame_timing() {
+        meta.frame_timing.is_some()
+    } else if metric.needs_packets() {
+        meta.gop.is_some()
+    } else if metric.is_qc() {
+        meta.qc.is_some()
+    } else if metric.is_crop() {
+        meta.crop.is_some()
+    } else if metric.is_interlace() {
+        meta.interlace.is_some()
+    } else {
+        true
+    }
+}
+
+/// `measured` tells whether both sides have what the check named `field`
+/// reads.
+fn diff_preset(
+    before: &PresetResult,
+    after: &PresetResult,
+    measured: impl Fn(&str) -> bool,
+) -> PresetDiff {
+    let mut fixed = Vec::new();
+    let mut broken = Vec::new();
+    let mut not_measured = Vec::new();
+    for old in &before.result.checks {
+        let Some(new) = after.result.checks.iter().find(|c| c.field == old.field) else {
+            continue;
+        };
+        if !measured(&old.field) {
+            not_measured.push(old.field.clone());
+            continue;
+        }
+        if old.ok == new.ok {
+            continue;
+        }
+        let flip = CheckFlip {
+            field: old.field.clone(),
+            before: old.clone(),
+            after: new.clone(),
+        };
+        if new.ok {
+            fixed.push(flip);
+        } else {
+            broken.push(flip);
+        }
+    }
+    PresetDiff {
+        platform_id: before.platform_id.clone(),
+        platform_name: before.platform_name.clone(),
+        before: before.result.verdict,
+        after: after.result.verdict,
+        fixed,
+        broken,
+        not_measured,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::PerfectResult;
+
+    fn changes(before: Value, after: Value) -> Vec<String> {
+        let mut out = Vec::new();
+        diff_values(String::new(), &before, &after, &mut out);
+        out.into_iter().map(|c| c.field).collect()
+    }
+
+    /// A clip with a video stream and, when `audio` is set, an AAC track.
+    fn meta(audio: bool, extra: Value) -> ExtendedMetadata {
+        let mut streams = vec![json!({ "index": 0, "codecType": "video", "disposition": [] })];
+        if audio {
+            streams.push(json!({ "index": 1, "codecType": "audio", "disposition": [] }));
+        }
+        let mut base = json!({
+            "width": 1920, "height": 1080, "fps": 25.0, "fpsAvg": 25.0, "fpsR": 25.0,
+            "videoCodec": "h264", "container": "mp4", "durationSec": 10.0,
+            "streams": streams,
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            base[key] = value.clone();
+        }
+        serde_json::from_value(base).unwrap()
+    }
+
+    fn check(field: &str, ok: bool) -> FieldCheck {
+        FieldCheck {
+            field: field.to_string(),
+            value: String::new(),
+            expected: String::new(),
+            ok,
+            reason: String::new(),
+        }
+    }
+
+    fn result(checks: Vec<FieldCheck>) -> PresetResult {
+        PresetResult {
+            platform_id: "p".to_string(),
+            platform_name: "P".to_string(),
+            result: PerfectResult {
+                verdict: if checks.iter().all(|c| c.ok) {
+                    Verdict::Perfect
+                } else {
+                    Verdict::Flawed
+                },
+                checks,
+                reasons: Vec::new(),
+                remediation: None,
+            },
+        }
+    }
+
+    #[test]
+    fn streams_are_matched_by_index() {
+        let video = json!({ "index": 0, "codecName": "h264" });
+        let cover = json!({ "index": 1, "codecName": "mjpeg" });
+        let audio = json!({ "index": 2, "codecName": "aac" });
+
+        // Same streams in another order: nothing changed.
+        let before = json!({ "streams": [video, cover, audio] });
+        let after = json!({ "streams": [audio, video, cover] });
+        assert!(changes(before.clone(), after).is_empty());
+
+        // Dropping the cover art reports only that stream.
+        let after = json!({ "streams": [video, audio] });
+        assert_eq!(changes(before, after), ["streams[1]"]);
+    }
+
+    #[test]
+    fn other_arrays_are_compared_by_position() {
+        let before = json!({ "atoms": ["ftyp", "moov", "mdat"] });
+        let after = json!({ "atoms": ["ftyp", "mdat", "moov"] });
+        assert_eq!(changes(before, after), ["atoms[1]", "atoms[2]"]);
+    }
+
+    #[test]
+    fn keyframes_are_summarized() {
+        let summary = keyframe_summary(&[0.0, 2.0, 4.0, 7.0]);
+        assert_eq!(
+            summary,
+            json!({
+                "count": 4, "first": 0.0, "last": 7.0,
+                "minInterval": 2.0, "maxInterval": 3.0, "avgInterval": 2.333,
+            })
+        );
+        let summary = keyframe_summary(&[0.0]);
+        assert_eq!(summary["count"], 1);
+        assert_eq!(summary["maxInterval"], Value::Null);
+
+        // Timestamp noise below a millisecond is not a change.
+        let before = json!({ "gop": { "keyframes": keyframe_summary(&[0.0, 2.0]) } });
+        let after = json!({ "gop": { "keyframes": keyframe_summary(&[0.0, 2.0001]) } });
+        assert!(changes(before, after).is_empty());
+    }
+
+    #[test]
+    fn missing_passes_are_not_measured() {
+        let loudness = json!({ "loudness": {
+            "integratedLufs": -23.0, "loudnessRangeLu": 5.0, "truePeakDbtp": -2.0,
+        }});
+        assert!(!measured(
+            &meta(true, json!({})),
+            Metric::IntegratedLoudness
+        ));
+        assert!(measured(
+            &meta(false, json!({})),
+            Metric::IntegratedLoudness
+        ));
+        assert!(measured(&meta(true, loudness), Metric::IntegratedLoudness));
+        assert!(!measured(&meta(true, json!({})), Metric::Letterbox));
+        assert!(!measured(&meta(true, json!({})), Metric::GopLength));
+        assert!(measured(&meta(true, json!({})), Metric::Container));
+    }
+
+    #[test]
+    fn unmeasured_checks_are_not_flips() {
+        let before = result(vec![check("Container", false), check("Loudness", true)]);
+        let after = result(vec![check("Container", true), check("Loudness", false)]);
+        let diff = diff_preset(&before, &after, |field| field != "Loudness");
+        assert_eq!(diff.fixed.len(), 1);
+        assert_eq!(diff.fixed[0].field, "Container");
+        assert!(diff.broken.is_empty());
+        assert_eq!(diff.not_measured, ["Loudness"]);
+    }
+}
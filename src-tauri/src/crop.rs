@@ -0,0 +1,197 @@
+//! Active picture area from ffmpeg's `cropdetect`, sampled at a few points
+//! through the file rather than over every frame. Burned-in bars (a 16:9
+//! picture padded into a 9:16 frame, say) pass every header check but are
+//! plain to see once the non-black area is measured.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ExportDoctorError;
+use crate::run_ffmpeg;
+
+/// Points sampled across the duration; each decodes `SAMPLE_FRAMES` frames.
+const SAMPLES: u32 = 5;
+const SAMPLE_FRAMES: &str = "8";
+/// Luma below this share of the range counts as black. Relative, so it
+/// scales with bit depth.
+const BLACK_LIMIT: f64 = 24.0 / 255.0;
+/// Bars thinner than this share of the frame on either side are ignored
+/// (encoder padding, soft edges).
+const BAR_MIN_SHARE: f64 = 0.02;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CropInfo {
+    /// Active picture size and offset, in displayed (rotated) orientation.
+    pub width: u32,
+    pub height: u32,
+    pub x: u32,
+    pub y: u32,
+    /// Full frame size the active area is measured against.
+    pub frame_width: u32,
+    pub frame_height: u32,
+    /// Black bars above and below the picture.
+    pub letterbox: bool,
+    /// Black bars left and right of the picture.
+    pub pillarbox: bool,
+    /// Sample points that produced a measurement.
+    pub samples: u32,
+}
+
+impl CropInfo {
+    pub fn aspect(&self) -> f64 {
+        f64::from(self.width) / f64::from(self.height)
+    }
+}
+
+/// Measure the active area of video stream `stream` (its container index).
+/// `frame` is its size after rotation, which is how ffmpeg decodes it. `None` when no sample
+/// had anything but black.
+pub fn detect(
+    path: &str,
+    stream: u32,
+    duration: f64,
+    frame: (u32, u32),
+) -> Result<Option<CropInfo>, ExportDoctorError> {
+    // Stills and streams without a duration get one sample at the start.
+    let points: Vec<f64> = if duration > 0.0 {
+        (1..=SAMPLES)
+            .map(|i| duration * f64::from(i) / f64::from(SAMPLES + 1))
+            .collect()
+    } else {
+        vec![0.0]
+    };
+
+    let map = format!("0:{}", stream);
+    let filter = format!("cropdetect=limit={:.4}:round=2:skip=0", BLACK_LIMIT);
+    let mut areas = Vec::new();
+    for point in points {
+        let start = format!("{:.3}", point);
+        let stderr = run_ffmpeg(&[
+            "-hide_banner",
+            "-nostats",
+            "-ss",
+            &start,
+            "-i",
+            path,
+            "-map",
+            &map,
+            "-vf",
+            &filter,
+            "-frames:v",
+            SAMPLE_FRAMES,
+            "-an",
+            "-f",
+            "null",
+            "-",
+        ])?;
+        // A failed sample (seek past the end, unsupported codec) just has
+        // no cropdetect lines; the others still count.
+        if let Some(area) = stderr.lines().rev().find_map(parse_crop) {
+            areas.push(area);
+        }
+    }
+    Ok(combine(&areas, frame))
+}
+
+/// Union of the sampled areas, so a dark scene in one sample doesn't
+/// shrink the picture.
+fn combine(areas: &[[u32; 4]], (frame_width, frame_height): (u32, u32)) -> Option<CropInfo> {
+    if areas.is_empty() || frame_width == 0 || frame_height == 0 {
+        return None;
+    }
+    let left = areas.iter().map(|a| a[2]).min()?;
+    let top = areas.iter().map(|a| a[3]).min()?;
+    let right = areas.iter().map(|a| a[2] + a[0]).max()?.min(frame_width);
+    let bottom = areas.iter().map(|a| a[3] + a[1]).max()?.min(frame_height);
+    if right <= left || bottom <= top {
+        return None;
+    }
+
+    let bars = |near: u32, far: u32, size: u32| {
+        let min = f64::from(size) * BAR_MIN_SHARE;
+        f64::from(near) >= min && f64::from(size - far) >= min
+    };
+    Some(CropInfo {
+        width: right - left,
+        height: bottom - top,
+        x: left,
+        y: top,
+        frame_width,
+        frame_height,
+        letterbox: bars(top, bottom, frame_height),
+        pillarbox: bars(left, right, frame_width),
+        samples: areas.len() as u32,
+    })
+}
+
+/// `[w, h, x, y]` from a cropdetect log line ending in "crop=w:h:x:y".
+/// Fully black frames report a negative size and are skipped.
+fn parse_crop(line: &str) -> Option<[u32; 4]> {
+    if !line.contains("[Parsed_cropdetect") {
+        return None;
+    }
+    let (_, crop) = line.rsplit_once("crop=")?;
+    let mut values = crop.trim().split(':').map(|v| v.parse::<i64>().ok());
+    let mut next = || values.next().flatten().and_then(|v| u32::try_from(v).ok());
+    let area = [next()?, next()?, next()?, next()?];
+    (area[0] > 0 && area[1] > 0).then_some(area)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cropdetect_lines() {
+        let line = "[Parsed_cropdetect_0 @ 0x7f] x1:0 x2:1919 y1:140 y2:939 w:1920 h:800 x:0 y:140 pts:5 t:0.2 limit:0.0941 crop=1920:800:0:140";
+        assert_eq!(parse_crop(line), Some([1920, 800, 0, 140]));
+        // Fully black frames report an inverted, negative area.
+        let black = "[Parsed_cropdetect_0 @ 0x7f] x1:1919 x2:0 y1:1079 y2:0 w:-1904 h:-1072 x:1912 y:1076 pts:1 t:0.04 limit:0.0941 crop=-1904:-1072:1912:1076";
+        assert_eq!(parse_crop(black), None);
+        assert_eq!(parse_crop("[Parsed_scale_1 @ 0x7f] crop=1:1:0:0"), None);
+    }
+
+    #[test]
+    fn letterbox() {
+        let info = combine(&[[1920, 800, 0, 140]], (1920, 1080)).unwrap();
+        assert!(info.letterbox);
+        assert!(!info.pillarbox);
+        assert!((info.aspect() - 2.4).abs() < 1e-9);
+        assert_eq!(info.samples, 1);
+    }
+
+    #[test]
+    fn bars_on_either_axis() {
+        // 16:9 picture padded into 9:16.
+        let info = combine(&[[1080, 608, 0, 656]], (1080, 1920)).unwrap();
+        assert!(info.letterbox);
+        assert!(!info.pillarbox);
+        let info = combine(&[[608, 1080, 236, 0]], (1080, 1080)).unwrap();
+        assert!(info.pillarbox);
+        assert!(!info.letterbox);
+    }
+
+    #[test]
+    fn samples_are_unioned() {
+        // A dark scene shrinks one sample; the union keeps the full frame.
+        let info = combine(&[[1920, 1080, 0, 0], [1600, 900, 160, 90]], (1920, 1080)).unwrap();
+        assert_eq!(
+            (info.width, info.height, info.x, info.y),
+            (1920, 1080, 0, 0)
+        );
+        assert!(!info.letterbox && !info.pillarbox);
+        assert_eq!(info.samples, 2);
+    }
+
+    #[test]
+    fn thin_bars_are_ignored() {
+        let info = combine(&[[1920, 1072, 0, 4]], (1920, 1080)).unwrap();
+        assert!(!info.letterbox);
+    }
+
+    #[test]
+    fn nothing_measured() {
+        assert!(combine(&[], (1920, 1080)).is_none());
+        assert!(combine(&[[1920, 1080, 0, 0]], (0, 0)).is_none());
+    }
+}
@@ -0,0 +1,172 @@
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::fmt;
+
+/// Error returned by every command. Serializes as
+/// `{ "code": "FfprobeNotFound", "message": "...", ...fields }` so the
+/// frontend can branch on `code` and still show `message` as-is.
+/// Codes are part of the frontend contract: add new ones, don't rename.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportDoctorError {
+    FfprobeNotFound { detail: String },
+    FfprobeFailed { stderr: String },
+    FfmpegNotFound { detail: String },
+    LoudnessFailed { detail: String },
+    QcFailed { detail: String },
+    CropFailed { detail: String },
+    InterlaceFailed { detail: String },
+    NotMediaFile { detail: String },
+    NoVideoStream,
+    FileUnreadable { path: String, detail: String },
+    FileWriteFailed { path: String, detail: String },
+    NoMediaFiles,
+    LicenseExpired,
+    LicenseInvalid,
+    NoLicenseKey,
+    Network { detail: String },
+    Server { detail: String },
+    OpenUrlFailed { detail: String },
+    UnknownPreset { id: String },
+    InvalidPreset { detail: String },
+    PresetConflict { id: String },
+    InvalidInput { detail: String },
+    WatchFailed { detail: String },
+    Storage { detail: String },
+    FixFailed { detail: String },
+    FixCancelled,
+    History { detail: String },
+}
+
+impl ExportDoctorError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::FfprobeNotFound { .. } => "FfprobeNotFound",
+            Self::FfprobeFailed { .. } => "FfprobeFailed",
+            Self::FfmpegNotFound { .. } => "FfmpegNotFound",
+            Self::LoudnessFailed { .. } => "LoudnessFailed",
+            Self::QcFailed { .. } => "QcFailed",
+            Self::CropFailed { .. } => "CropFailed",
+            Self::InterlaceFailed { .. } => "InterlaceFailed",
+            Self::NotMediaFile { .. } => "NotMediaFile",
+            Self::NoVideoStream => "NoVideoStream",
+            Self::FileUnreadable { .. } => "FileUnreadable",
+            Self::FileWriteFailed { .. } => "FileWriteFailed",
+            Self::NoMediaFiles => "NoMediaFiles",
+            Self::LicenseExpired => "LicenseExpired",
+            Self::LicenseInvalid => "LicenseInvalid",
+            Self::NoLicenseKey => "NoLicenseKey",
+            Self::Network { .. } => "Network",
+            Self::Server { .. } => "Server",
+            Self::OpenUrlFailed { .. } => "OpenUrlFailed",
+            Self::UnknownPreset { .. } => "UnknownPreset",
+            Self::InvalidPreset { .. } => "InvalidPreset",
+            Self::PresetConflict { .. } => "PresetConflict",
+            Self::InvalidInput { .. } => "InvalidInput",
+            Self::WatchFailed { .. } => "WatchFailed",
+            Self::Storage { .. } => "Storage",
+            Self::FixFailed { .. } => "FixFailed",
+            Self::FixCancelled => "FixCancelled",
+            Self::History { .. } => "History",
+        }
+    }
+
+    pub fn invalid_preset(detail: impl fmt::Display) -> Self {
+        Self::InvalidPreset {
+            detail: detail.to_string(),
+        }
+    }
+
+    pub fn storage(detail: impl fmt::Display) -> Self {
+        Self::Storage {
+            detail: detail.to_string(),
+        }
+    }
+
+    pub fn history(detail: impl fmt::Display) -> Self {
+        Self::History {
+            detail: detail.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ExportDoctorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FfprobeNotFound { detail } => write!(
+                f,
+                "ffprobe not found. Please reinstall the application. Error: {}",
+                detail
+            ),
+            Self::FfprobeFailed { stderr } => write!(f, "ffprobe failed: {}", stderr),
+            Self::FfmpegNotFound { detail } => write!(
+                f,
+                "ffmpeg is needed for loudness, QC and fixes but was not found. Error: {}",
+                detail
+            ),
+            Self::LoudnessFailed { detail } => {
+                write!(f, "Loudness measurement failed: {}", detail)
+            }
+            Self::QcFailed { detail } => write!(f, "QC analysis failed: {}", detail),
+            Self::CropFailed { detail } => write!(f, "Crop detection failed: {}", detail),
+            Self::InterlaceFailed { detail } => {
+                write!(f, "Interlace detection failed: {}", detail)
+            }
+            Self::NotMediaFile { detail } => {
+                write!(f, "{} The file may not be a valid media file.", detail)
+            }
+            Self::NoVideoStream => write!(f, "No video stream found"),
+            Self::FileUnreadable { detail, .. } => write!(f, "Cannot access file: {}", detail),
+            Self::FileWriteFailed { path, detail } => {
+                write!(f, "Cannot write {}: {}", path, detail)
+            }
+            Self::NoMediaFiles => write!(f, "No media files found in the selection"),
+            Self::LicenseExpired => write!(
+                f,
+                "Your license has expired. Please subscribe to continue using Export Doctor."
+            ),
+            Self::LicenseInvalid => write!(
+                f,
+                "Invalid license. Please subscribe or enter a valid license key."
+            ),
+            Self::NoLicenseKey => write!(f, "No license key stored"),
+            Self::Network { detail } => write!(f, "Network error: {}", detail),
+            Self::Server { detail } => write!(f, "Server error: {}", detail),
+            Self::OpenUrlFailed { detail } => write!(f, "Failed to open browser: {}", detail),
+            Self::UnknownPreset { id } => write!(f, "Unknown preset: {}", id),
+            Self::InvalidPreset { detail } => write!(f, "{}", detail),
+            Self::PresetConflict { id } => {
+                write!(
+                    f,
+                    "A preset with id \"{}\" already exists; choose another id",
+                    id
+                )
+            }
+            Self::InvalidInput { detail } => write!(f, "{}", detail),
+            Self::WatchFailed { detail } => write!(f, "Folder watcher failed: {}", detail),
+            Self::Storage { detail } => write!(f, "Failed to save settings: {}", detail),
+            Self::FixFailed { detail } => write!(f, "Fix failed: {}", detail),
+            Self::FixCancelled => write!(f, "Fix cancelled"),
+            Self::History { detail } => write!(f, "History database error: {}", detail),
+        }
+    }
+}
+
+impl std::error::Error for ExportDoctorError {}
+
+impl Serialize for ExportDoctorError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("message", &self.to_string())?;
+        match self {
+            Self::FfprobeFailed { stderr } => map.serialize_entry("stderr", stderr)?,
+            Self::FileUnreadable { path, .. } | Self::FileWriteFailed { path, .. } => {
+                map.serialize_entry("path", path)?
+            }
+            Self::UnknownPreset { id } | Self::PresetConflict { id } => {
+                map.serialize_entry("id", id)?
+            }
+            _ => {}
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,449 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Emitter;
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+use crate::error::ExportDoctorError;
+use crate::rules::{PresetResult, Verdict};
+use crate::{
+    analyze_and_evaluate, evaluate_and_record, license, presets, resolve_bundled_binary,
+    AnalyzeResult,
+};
+
+/// Emitted while ffmpeg runs, a few times per second.
+pub const PROGRESS_EVENT: &str = "fix://progress";
+
+/// stderr lines kept for the error message when ffmpeg fails.
+const STDERR_TAIL: usize = 5;
+
+// ── Structs ──
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FixProgress {
+    pub output_path: String,
+    /// 0–100, from ffmpeg's output position against the input duration.
+    pub percent: f64,
+    pub out_time_sec: f64,
+    /// Encoding speed as a multiple of real time.
+    pub speed: Option<f64>,
+    pub eta_sec: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FixResult {
+    pub output_path: String,
+    /// The ffmpeg arguments that were run.
+    pub args: Vec<String>,
+    /// The output file, analyzed and evaluated against the same preset.
+    pub analysis: AnalyzeResult,
+    pub result: PresetResult,
+}
+
+/// Managed state: running ffmpeg processes by output path, so
+/// `cancel_fix` can stop them. A job missing from the map when ffmpeg exits
+/// was cancelled.
+#[derive(Default)]
+pub struct FixState {
+    running: Mutex<HashMap<String, CommandChild>>,
+}
+
+// ── Commands ──
+
+/// Run the remediation for `preset_id` on `path`, writing `output_path`,
+/// then analyze the output against the same preset.
+#[tauri::command]
+pub async fn fix_for_preset(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, FixState>,
+    path: String,
+    preset_id: String,
+    output_path: String,
+) -> Result<FixResult, ExportDoctorError> {
+    license::check_license_valid(&app)?;
+    if same_file(&path, &output_path) {
+        return Err(ExportDoctorError::InvalidInput {
+            detail: "Choose an output file other than the original".to_string(),
+        });
+    }
+    let catalog = presets::catalog(&app);
+    let ids = [preset_id];
+    let (analysis, results) = analyze_and_evaluate(&app, &path, &catalog, &ids, false).await?;
+    let result = results
+        .into_iter()
+        .next()
+        .map(|r| r.result)
+        .ok_or_else(|| ExportDoctorError::UnknownPreset { id: ids[0].clone() })?;
+    if result.verdict == Verdict::Perfect {
+        return Err(ExportDoctorError::FixFailed {
+            detail: "The file already passes this preset".to_string(),
+        });
+    }
+    let remediation = result
+        .remediation
+        .ok_or_else(|| ExportDoctorError::FixFailed {
+            detail: "None of the failed checks can be fixed by re-encoding".to_string(),
+        })?;
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-nostats".to_string(),
+        "-progress".to_string(),
+        "pipe:1".to_string(),
+    ];
+    args.extend(remediation.command_line(&path, &output_path));
+    let mut events = {
+        // Checked under the lock that records the job, so two requests for
+        // the same output can't both start.
+        let mut running = state.running.lock().map_err(lock_failed)?;
+        if running.contains_key(&output_path) {
+            return Err(ExportDoctorError::FixFailed {
+                detail: format!("A fix is already writing {}", output_path),
+            });
+        }
+        let (events, child) = spawn_ffmpeg(&app, &args)?;
+        running.insert(output_path.clone(), child);
+        events
+    };
+
+    let duration = analysis.metadata.duration_sec;
+    let mut progress = FixProgress {
+        output_path: output_path.clone(),
+        percent: 0.0,
+        out_time_sec: 0.0,
+        speed: None,
+        eta_sec: None,
+    };
+    let mut stderr_tail = Vec::new();
+    let mut exit_code = None;
+    while let Some(event) = events.recv().await {
+        match event {
+            CommandEvent::Stdout(line) => {
+                let line = String::from_utf8_lossy(&line);
+                if apply_progress_line(&mut progress, line.trim(), duration) {
+                    if let Err(e) = app.emit(PROGRESS_EVENT, &progress) {
+                        eprintln!("[export-doctor] failed to emit fix progress: {}", e);
+                    }
+                }
+            }
+            CommandEvent::Stderr(line) => {
+                let line = String::from_utf8_lossy(&line).trim().to_string();
+                if !line.is_empty() {
+                    stderr_tail.push(line);
+                    if stderr_tail.len() > STDERR_TAIL {
+                        stderr_tail.remove(0);
+                    }
+                }
+            }
+            CommandEvent::Terminated(payload) => exit_code = payload.code,
+            CommandEvent::Error(e) => stderr_tail.push(e),
+            _ => {}
+        }
+    }
+
+    let cancelled = state
+        .running
+        .lock()
+        .map_err(lock_failed)?
+        .remove(&output_path)
+        .is_none();
+    if cancelled || exit_code != Some(0) {
+        // Don't leave a half-written file that looks like a finished export.
+        let _ = std::fs::remove_file(&output_path);
+    }
+    if cancelled {
+        return Err(ExportDoctorError::FixCancelled);
+    }
+    if exit_code != Some(0) {
+        return Err(ExportDoctorError::FixFailed {
+            detail: stderr_tail
+                .last()
+                .cloned()
+                .unwrap_or_else(|| format!("ffmpeg exited with {:?}", exit_code)),
+        });
+    }
+
+    let (analysis, results) =
+        evaluate_and_record(&app, &output_path, &catalog, &ids, false).await?;
+    let result = results
+        .into_iter()
+        .next()
+        .ok_or_else(|| ExportDoctorError::UnknownPreset { id: ids[0].clone() })?;
+    Ok(FixResult {
+        output_path,
+        args,
+        analysis,
+        result,
+    })
+}
+
+/// Stop the fix writing `output_path`. Does nothing if it already finished.
+#[tauri::command]
+pub async fn cancel_fix(
+    state: tauri::State<'_, FixState>,
+    output_path: String,
+) -> Result<(), ExportDoctorError> {
+    let child = state
+        .running
+        .lock()
+        .map_err(lock_failed)?
+        .remove(&output_path);
+    if let Some(child) = child {
+        // The process may have exited on its own in the meantime.
+        let _ = child.kill();
+    }
+    Ok(())
+}
+
+// ── Helpers ──
+
+/// Start ffmpeg: the bundled sidecar, then a binary next to the executable,
+/// then PATH.
+fn spawn_ffmpeg(
+    app: &tauri::AppHandle,
+    args: &[String],
+) -> Result<(tauri::async_runtime::Receiver<CommandEvent>, CommandChild), ExportDoctorError> {
+    if let Ok(cmd) = app.shell().sidecar("ffmpeg") {
+        match cmd.args(args).spawn() {
+            Ok(spawned) => return Ok(spawned),
+            Err(e) => eprintln!("[export-doctor] sidecar ffmpeg spawn failed: {}", e),
+        }
+    }
+    let program = resolve_bundled_binary("ffmpeg")
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "ffmpeg".to_string());
+    app.shell()
+        .command(program)
+        .args(args)
+        .spawn()
+        .map_err(|e| ExportDoctorError::FfmpegNotFound {
+            detail: e.to_string(),
+        })
+}
+
+/// Fold one `-progress` line ("key=value") into `progress`. True at the
+/// end of each block, when the update is complete.
+fn apply_progress_line(progress: &mut FixProgress, line: &str, duration: f64) -> bool {
+    let Some((key, value)) = line.split_once('=') else {
+        return false;
+    };
+    match key {
+        // Both are microseconds, despite the name; older builds only print
+        // `out_time_ms`.
+        "out_time_us" | "out_time_ms" => {
+            if let Ok(us) = value.parse::<f64>() {
+                progress.out_time_sec = (us / 1_000_000.0).max(0.0);
+            }
+        }
+        "speed" => {
+            progress.speed = value
+                .trim_end_matches('x')
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|s| *s > 0.0);
+        }
+        "progress" => {
+            if duration > 0.0 {
+                progress.percent = (progress.out_time_sec / duration * 100.0).min(100.0);
+                progress.eta_sec = progress
+                    .speed
+                    .map(|speed| ((duration - progress.out_time_sec) / speed).max(0.0));
+            }
+            if value == "end" {
+                progress.percent = 100.0;
+                progress.eta_sec = Some(0.0);
+            }
+            return true;
+        }
+        _ => {}
+    }
+    false
+}
+
+fn lock_failed<T>(e: std::sync::PoisonError<T>) -> ExportDoctorError {
+    ExportDoctorError::FixFailed {
+        detail: e.to_string(),
+    }
+}
+
+fn same_file(a: &str, b: &str) -> bool {
+    match (resolve(a), resolve(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Absolute form of `path`. A file that doesn't exist yet (the output) is
+/// resolved through its directory, so `./a.mov` and `a.mov` still match.
+fn resolve(path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    if let Ok(full) = path.canonicalize() {
+        return Some(full);
+    }
+    let name = path.file_name()?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    Some(dir.canonicalize().ok()?.join(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress() -> FixProgress {
+        FixProgress {
+            output_path: "out.mp4".to_string(),
+            percent: 0.0,
+            out_time_sec: 0.0,
+            speed: None,
+            eta_sec: None,
+        }
+    }
+
+    /// Feed a `-progress pipe:1` block; the state after its last line and
+    /// how many updates were emitted.
+    fn feed(progress: &mut FixProgress, block: &str, duration: f64) -> usize {
+        block
+            .lines()
+            .filter(|line| apply_progress_line(progress, line.trim(), duration))
+            .count()
+    }
+
+    const BLOCK: &str = "\
+frame=250
+fps=50.00
+stream_0_0_q=28.0
+bitrate=2100.5kbits/s
+total_size=2621440
+out_time_us=10000000
+out_time_ms=10000000
+out_time=00:00:10.000000
+dup_frames=0
+drop_frames=0
+speed=2.5x
+progress=continue";
+
+    #[test]
+    fn captured_block() {
+        let mut p = progress();
+        assert_eq!(feed(&mut p, BLOCK, 40.0), 1);
+        assert_eq!(p.out_time_sec, 10.0);
+        assert_eq!(p.percent, 25.0);
+        assert_eq!(p.speed, Some(2.5));
+        assert_eq!(p.eta_sec, Some(12.0));
+    }
+
+    #[test]
+    fn out_time_ms_is_microseconds_too() {
+        let mut p = progress();
+        feed(&mut p, "out_time_ms=5000000\nprogress=continue", 20.0);
+        assert_eq!(p.out_time_sec, 5.0);
+        assert_eq!(p.percent, 25.0);
+        // A later `out_time_us` in the same block agrees with it.
+        feed(
+            &mut p,
+            "out_time_us=6000000\nout_time_ms=6000000\nprogress=continue",
+            20.0,
+        );
+        assert_eq!(p.out_time_sec, 6.0);
+        // Not yet known at the start of a run.
+        feed(&mut p, "out_time_us=N/A\nprogress=continue", 20.0);
+        assert_eq!(p.out_time_sec, 6.0);
+    }
+
+    #[test]
+    fn unknown_or_stalled_speed_has_no_eta() {
+        let mut p = progress();
+        feed(
+            &mut p,
+            "out_time_us=1000000\nspeed=N/A\nprogress=continue",
+            10.0,
+        );
+        assert_eq!(p.speed, None);
+        assert_eq!(p.eta_sec, None);
+        assert_eq!(p.percent, 10.0);
+        feed(&mut p, "speed=   0x\nprogress=continue", 10.0);
+        assert_eq!(p.speed, None);
+        assert_eq!(p.eta_sec, None);
+    }
+
+    #[test]
+    fn zero_duration_reports_no_percent_until_the_end() {
+        let mut p = progress();
+        feed(
+            &mut p,
+            "out_time_us=3000000\nspeed=1x\nprogress=continue",
+            0.0,
+        );
+        assert_eq!(p.percent, 0.0);
+        assert_eq!(p.eta_sec, None);
+        feed(&mut p, "progress=end", 0.0);
+        assert_eq!(p.percent, 100.0);
+        assert_eq!(p.eta_sec, Some(0.0));
+    }
+
+    #[test]
+    fn percent_is_clamped_and_end_completes() {
+        let mut p = progress();
+        // Output runs past the probed duration (audio padding, say).
+        feed(
+            &mut p,
+            "out_time_us=10500000\nspeed=4x\nprogress=continue",
+            10.0,
+        );
+        assert_eq!(p.percent, 100.0);
+        assert_eq!(p.eta_sec, Some(0.0));
+
+        let mut p = progress();
+        assert_eq!(
+            feed(&mut p, "out_time_us=9900000\nspeed=1x\nprogress=end", 10.0),
+            1
+        );
+        assert_eq!(p.percent, 100.0);
+        assert_eq!(p.eta_sec, Some(0.0));
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        let mut p = progress();
+        assert!(!apply_progress_line(&mut p, "", 10.0));
+        assert!(!apply_progress_line(&mut p, "garbage", 10.0));
+        assert!(!apply_progress_line(&mut p, "frame=12", 10.0));
+        assert_eq!(p.out_time_sec, 0.0);
+    }
+
+    #[test]
+    fn same_file_resolves_outputs_that_do_not_exist_yet() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("a.mov");
+        std::fs::write(&input, b"x").unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        let input = input.to_str().unwrap();
+        let through_sub = dir.path().join("sub").join("..").join("a.mov");
+
+        assert!(same_file(input, input));
+        assert!(same_file(input, through_sub.to_str().unwrap()));
+        let other = dir.path().join("a_fixed.mp4");
+        assert!(!same_file(input, other.to_str().unwrap()));
+        // Only the directory has to exist.
+        let missing = dir.path().join("nowhere").join("a.mov");
+        assert!(!same_file(input, missing.to_str().unwrap()));
+    }
+
+    #[test]
+    fn same_file_relative_to_the_working_directory() {
+        let name = "Cargo.toml";
+        assert!(same_file(name, "./Cargo.toml"));
+        assert!(same_file(name, "src/../Cargo.toml"));
+        // Neither exists: the directory still resolves both the same way.
+        assert!(same_file("./not-here.mov", "not-here.mov"));
+        assert!(!same_file("not-here.mov", "not-there.mov"));
+    }
+}
@@ -0,0 +1,191 @@
+//! GOP structure from packet flags and timestamps: keyframe positions, GOP
+//! length, open vs closed GOPs and the B-frame run length.
+
+use serde::{Deserialize, Serialize};
+
+use crate::packets::Packet;
+
+/// How much of the first GOP's decode-order pattern to report.
+const PATTERN_LEN: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GopInfo {
+    /// Keyframe presentation times in seconds.
+    pub keyframes: Vec<f64>,
+    pub gop_count: u64,
+    /// GOP lengths in frames, over complete GOPs (the trailing one counts
+    /// only if it is the only GOP).
+    pub avg_gop_frames: f64,
+    pub max_gop_frames: u64,
+    /// Longest keyframe-to-keyframe distance in seconds.
+    pub max_gop_seconds: f64,
+    /// GOPs whose leading frames display before their keyframe and so
+    /// reference the previous GOP.
+    pub open_gops: u64,
+    /// Longest run of reordered (B) frames between reference frames.
+    pub max_consecutive_b_frames: u64,
+    /// First GOP in decode order, e.g. "IPBBPBBPBB". I-frame-only streams
+    /// are "I".
+    pub pattern: String,
+}
+
+impl GopInfo {
+    pub fn closed(&self) -> bool {
+        self.open_gops == 0
+    }
+}
+
+pub fn analyze(packets: &[Packet]) -> Option<GopInfo> {
+    let key_indices: Vec<usize> = packets
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.key)
+        .map(|(i, _)| i)
+        .collect();
+    let first_key = *key_indices.first()?;
+
+    // A packet displayed before an earlier-decoded packet was reordered,
+    // which is what a B-frame is from the container's point of view.
+    let mut kinds = Vec::with_capacity(packets.len());
+    let mut latest = f64::NEG_INFINITY;
+    for packet in packets {
+        let time = packet.time().unwrap_or(latest);
+        kinds.push(if packet.key {
+            'I'
+        } else if time < latest {
+            'B'
+        } else {
+            'P'
+        });
+        latest = latest.max(time);
+    }
+
+    let mut lengths: Vec<u64> = key_indices
+        .windows(2)
+        .map(|w| (w[1] - w[0]) as u64)
+        .collect();
+    if lengths.is_empty() {
+        lengths.push((packets.len() - first_key) as u64);
+    }
+
+    let open_gops = key_indices
+        .iter()
+        .enumerate()
+        .filter(|(n, &start)| {
+            let end = key_indices.get(n + 1).copied().unwrap_or(packets.len());
+            let Some(key_time) = packets[start].time() else {
+                return false;
+            };
+            packets[start + 1..end]
+                .iter()
+                .take_while(|p| !p.key)
+                .any(|p| p.time().is_some_and(|t| t < key_time))
+        })
+        .count() as u64;
+
+    let mut max_b = 0;
+    let mut run = 0;
+    for kind in &kinds {
+        if *kind == 'B' {
+            run += 1;
+            max_b = max_b.max(run);
+        } else {
+            run = 0;
+        }
+    }
+
+    let mut keyframes: Vec<f64> = key_indices
+        .iter()
+        .filter_map(|&i| packets[i].time())
+        .collect();
+    keyframes.sort_by(f64::total_cmp);
+    let max_gop_seconds = if keyframes.len() > 1 {
+        keyframes
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .fold(0.0, f64::max)
+    } else {
+        let last = packets.iter().filter_map(Packet::time).fold(0.0, f64::max);
+        keyframes.first().map_or(0.0, |k| last - k)
+    };
+
+    let pattern_end = key_indices.get(1).copied().unwrap_or(packets.len());
+    Some(GopInfo {
+        gop_count: key_indices.len() as u64,
+        avg_gop_frames: lengths.iter().sum::<u64>() as f64 / lengths.len() as f64,
+        max_gop_frames: lengths.iter().copied().max().unwrap_or(0),
+        max_gop_seconds,
+        open_gops,
+        max_consecutive_b_frames: max_b,
+        pattern: kinds[first_key..pattern_end]
+            .iter()
+            .take(PATTERN_LEN)
+            .collect(),
+        keyframes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Packets in decode order from presentation frame numbers at 25 fps;
+    /// a `K` prefix marks a keyframe.
+    fn packets(frames: &str) -> Vec<Packet> {
+        frames
+            .split_whitespace()
+            .map(|f| {
+                let frame = f.trim_start_matches('K').parse::<f64>().unwrap();
+                Packet {
+                    pts: Some(frame / 25.0),
+                    dts: None,
+                    key: f.starts_with('K'),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn closed_gops_with_b_frames() {
+        let gop = analyze(&packets("K0 3 1 2 6 4 5 K7 10 8 9 13 11 12 K14 15")).unwrap();
+        assert_eq!(gop.gop_count, 3);
+        assert_eq!(gop.keyframes, vec![0.0, 0.28, 0.56]);
+        assert_eq!(gop.max_gop_frames, 7);
+        assert_eq!(gop.avg_gop_frames, 7.0);
+        assert!((gop.max_gop_seconds - 0.28).abs() < 1e-9);
+        assert!(gop.closed());
+        assert_eq!(gop.max_consecutive_b_frames, 2);
+        assert_eq!(gop.pattern, "IPBBPBB");
+    }
+
+    #[test]
+    fn leading_b_frames_make_open_gops() {
+        let gop = analyze(&packets("K2 0 1 5 3 4 K8 6 7 11 9 10")).unwrap();
+        assert_eq!(gop.gop_count, 2);
+        assert_eq!(gop.open_gops, 2);
+        assert!(!gop.closed());
+    }
+
+    #[test]
+    fn intra_only() {
+        let gop = analyze(&packets("K0 K1 K2 K3")).unwrap();
+        assert_eq!(gop.gop_count, 4);
+        assert_eq!(gop.max_gop_frames, 1);
+        assert_eq!(gop.max_consecutive_b_frames, 0);
+        assert_eq!(gop.pattern, "I");
+    }
+
+    #[test]
+    fn single_gop_runs_to_the_last_frame() {
+        let gop = analyze(&packets("K0 1 2 3 4")).unwrap();
+        assert_eq!(gop.gop_count, 1);
+        assert_eq!(gop.max_gop_frames, 5);
+        assert!((gop.max_gop_seconds - 0.16).abs() < 1e-9);
+    }
+
+    #[test]
+    fn no_keyframes() {
+        assert!(analyze(&packets("1 2 3")).is_none());
+    }
+}
@@ -0,0 +1,431 @@
+//! HDR signalling from ffprobe side data: mastering display colour volume,
+//! content light level and the Dolby Vision configuration record.
+//!
+//! MP4 (`mdcv`/`clli`) and Matroska carry the static metadata at stream
+//! level. HEVC and AV1 files that only have it in SEI/OBUs expose it on the
+//! first frame instead, as do HDR10+ dynamic metadata, so PQ streams get a
+//! one-frame probe when a preset asks.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ExtendedMetadata;
+
+const MASTERING_DISPLAY: &str = "Mastering display metadata";
+const CONTENT_LIGHT: &str = "Content light level metadata";
+const DOVI_CONFIG: &str = "DOVI configuration record";
+const HDR10_PLUS: &str = "SMPTE2094-40";
+
+/// Chromaticity tolerance when naming mastering display primaries.
+const PRIMARIES_TOLERANCE: f64 = 0.005;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HdrInfo {
+    /// "HDR10", "HDR10+", "HLG" or "Dolby Vision".
+    pub format: String,
+    pub mastering_display: Option<MasteringDisplay>,
+    pub content_light: Option<ContentLight>,
+    pub dolby_vision: Option<DolbyVision>,
+    /// HDR10+ (SMPTE ST 2094-40) dynamic metadata on the first frame.
+    pub hdr10_plus: bool,
+}
+
+/// SMPTE ST 2086 mastering display colour volume. Chromaticities are CIE
+/// 1931 xy, luminance is in cd/m² (nits).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MasteringDisplay {
+    /// "BT.2020", "P3-D65", "P3-DCI" or "BT.709" when the primaries and white
+    /// point match one of those.
+    pub primaries: Option<String>,
+    pub red: [f64; 2],
+    pub green: [f64; 2],
+    pub blue: [f64; 2],
+    pub white_point: [f64; 2],
+    pub min_luminance: f64,
+    pub max_luminance: f64,
+}
+
+/// CTA-861.3 content light level, in nits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentLight {
+    pub max_cll: u32,
+    pub max_fall: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DolbyVision {
+    pub version: String,
+    pub profile: u32,
+    pub level: u32,
+    pub rpu: bool,
+    pub enhancement_layer: bool,
+    pub base_layer: bool,
+    /// Base layer compatibility: 1 = HDR10, 2 = SDR, 4 = HLG.
+    pub compatibility_id: u32,
+}
+
+impl DolbyVision {
+    /// Profile as usually written, e.g. "5", "7" or "8.1".
+    pub fn profile_name(&self) -> String {
+        if self.profile == 8 || self.profile == 10 {
+            format!("{}.{}", self.profile, self.compatibility_id)
+        } else {
+            self.profile.to_string()
+        }
+    }
+}
+
+/// Read HDR signalling from a video stream. `None` for SDR streams.
+pub fn from_stream(stream: &Value) -> Option<HdrInfo> {
+    let transfer = stream["color_transfer"].as_str().unwrap_or("");
+    let mut info = HdrInfo {
+        format: String::new(),
+        mastering_display: None,
+        content_light: None,
+        dolby_vision: None,
+        hdr10_plus: false,
+    };
+    merge_side_data(&mut info, &stream["side_data_list"]);
+    info.format = match transfer {
+        _ if info.dolby_vision.is_some() => "Dolby Vision",
+        "smpte2084" => "HDR10",
+        "arib-std-b67" => "HLG",
+        _ => return None,
+    }
+    .to_string();
+    Some(info)
+}
+
+/// Whether the first frame may add to what the stream header said.
+pub fn needs_frame_probe(meta: &ExtendedMetadata) -> bool {
+    meta.hdr.as_ref().is_some_and(|h| h.format != "HLG")
+}
+
+/// Arguments for an ffprobe run that prints the side data of the first frame
+/// of the stream at container index `stream` as JSON.
+pub fn frame_probe_args<'a>(path: &'a str, stream: &'a str) -> [&'a str; 11] {
+    [
+        "-v",
+        "quiet",
+        "-select_streams",
+        stream,
+        "-read_intervals",
+        "%+#1",
+        "-show_entries",
+        "frame=side_data_list",
+        "-of",
+        "json",
+        path,
+    ]
+}
+
+/// Merge the first frame's side data (`frame_probe_args` output) into
+/// `meta.hdr`. Stream-level values win when both are present.
+pub fn apply_frame(meta: &mut ExtendedMetadata, json: &str) {
+    let Some(info) = meta.hdr.as_mut() else {
+        return;
+    };
+    let Ok(probe) = serde_json::from_str::<Value>(json) else {
+        return;
+    };
+    let Some(frame) = probe["frames"].as_array().and_then(|f| f.first()) else {
+        return;
+    };
+    merge_side_data(info, &frame["side_data_list"]);
+    if info.hdr10_plus && info.format == "HDR10" {
+        info.format = "HDR10+".to_string();
+    }
+}
+
+fn merge_side_data(info: &mut HdrInfo, list: &Value) {
+    for entry in list.as_array().into_iter().flatten() {
+        let kind = entry["side_data_type"].as_str().unwrap_or("");
+        if kind == MASTERING_DISPLAY {
+            if info.mastering_display.is_none() {
+                info.mastering_display = mastering_display(entry);
+            }
+        } else if kind == CONTENT_LIGHT {
+            if info.content_light.is_none() {
+                info.content_light = content_light(entry);
+            }
+        } else if kind == DOVI_CONFIG {
+            if info.dolby_vision.is_none() {
+                info.dolby_vision = dolby_vision(entry);
+            }
+        } else if kind.contains(HDR10_PLUS) {
+            info.hdr10_plus = true;
+        }
+    }
+}
+
+fn mastering_display(entry: &Value) -> Option<MasteringDisplay> {
+    let xy = |name: &str| {
+        Some([
+            rational(&entry[format!("{}_x", name)])?,
+            rational(&entry[format!("{}_y", name)])?,
+        ])
+    };
+    let mut display = MasteringDisplay {
+        primaries: None,
+        red: xy("red")?,
+        green: xy("green")?,
+        blue: xy("blue")?,
+        white_point: xy("white_point")?,
+        min_luminance: rational(&entry["min_luminance"])?,
+        max_luminance: rational(&entry["max_luminance"])?,
+    };
+    display.primaries = name_primaries(&display);
+    Some(display)
+}
+
+fn content_light(entry: &Value) -> Option<ContentLight> {
+    Some(ContentLight {
+        max_cll: entry["max_content"].as_u64()? as u32,
+        max_fall: entry["max_average"].as_u64()? as u32,
+    })
+}
+
+fn dolby_vision(entry: &Value) -> Option<DolbyVision> {
+    let int = |key: &str| entry[key].as_u64().map(|v| v as u32);
+    let flag = |key: &str| int(key) == Some(1);
+    Some(DolbyVision {
+        version: format!(
+            "{}.{}",
+            int("dv_version_major")?,
+            int("dv_version_minor").unwrap_or(0)
+        ),
+        profile: int("dv_profile")?,
+        level: int("dv_level").unwrap_or(0),
+        rpu: flag("rpu_present_flag"),
+        enhancement_layer: flag("el_present_flag"),
+        base_layer: flag("bl_present_flag"),
+        compatibility_id: int("dv_bl_signal_compatibility_id").unwrap_or(0),
+    })
+}
+
+/// ffprobe prints side data values as "num/den" strings.
+fn rational(value: &Value) -> Option<f64> {
+    if let Some(n) = value.as_f64() {
+        return Some(n);
+    }
+    let (num, den) = value.as_str()?.split_once('/')?;
+    let (num, den) = (
+        num.trim().parse::<f64>().ok()?,
+        den.trim().parse::<f64>().ok()?,
+    );
+    (den != 0.0).then(|| num / den)
+}
+
+fn name_primaries(display: &MasteringDisplay) -> Option<String> {
+    const D65: [f64; 2] = [0.3127, 0.3290];
+    const DCI_WHITE: [f64; 2] = [0.314, 0.351];
+    const BT2020: [[f64; 2]; 3] = [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]];
+    const P3: [[f64; 2]; 3] = [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]];
+    const BT709: [[f64; 2]; 3] = [[0.640, 0.330], [0.300, 0.600], [0.150, 0.060]];
+    let known = [
+        ("BT.2020", BT2020, D65),
+        ("P3-D65", P3, D65),
+        ("P3-DCI", P3, DCI_WHITE),
+        ("BT.709", BT709, D65),
+    ];
+    let near = |a: [f64; 2], b: [f64; 2]| {
+        (a[0] - b[0]).abs() <= PRIMARIES_TOLERANCE && (a[1] - b[1]).abs() <= PRIMARIES_TOLERANCE
+    };
+    let rgb = [display.red, display.green, display.blue];
+    known
+        .iter()
+        .find(|(_, primaries, white)| {
+            rgb.iter().zip(primaries).all(|(a, b)| near(*a, *b))
+                && near(display.white_point, *white)
+        })
+        .map(|(name, ..)| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// ffprobe's rendering of an HDR10 grade on a P3-D65 1000-nit monitor.
+    fn mastering() -> Value {
+        json!({
+            "side_data_type": "Mastering display metadata",
+            "red_x": "34000/50000", "red_y": "16000/50000",
+            "green_x": "13250/50000", "green_y": "34500/50000",
+            "blue_x": "7500/50000", "blue_y": "3000/50000",
+            "white_point_x": "15635/50000", "white_point_y": "16450/50000",
+            "min_luminance": "50/10000", "max_luminance": "10000000/10000",
+        })
+    }
+
+    fn light() -> Value {
+        json!({ "side_data_type": "Content light level metadata", "max_content": 1000, "max_average": 400 })
+    }
+
+    fn dovi(profile: u32, compatibility: u32) -> Value {
+        json!({
+            "side_data_type": "DOVI configuration record",
+            "dv_version_major": 1, "dv_version_minor": 0,
+            "dv_profile": profile, "dv_level": 6,
+            "rpu_present_flag": 1, "el_present_flag": 0, "bl_present_flag": 1,
+            "dv_bl_signal_compatibility_id": compatibility,
+        })
+    }
+
+    fn stream(transfer: &str, side_data: Value) -> Value {
+        json!({ "color_transfer": transfer, "side_data_list": side_data })
+    }
+
+    #[test]
+    fn rationals() {
+        assert_eq!(rational(&json!("34000/50000")), Some(0.68));
+        assert_eq!(rational(&json!(" 50 / 10000 ")), Some(0.005));
+        assert_eq!(rational(&json!(0.3127)), Some(0.3127));
+        assert_eq!(rational(&json!("0/0")), None);
+        assert_eq!(rational(&json!("1/0")), None);
+        assert_eq!(rational(&json!("n/a")), None);
+        assert_eq!(rational(&Value::Null), None);
+    }
+
+    #[test]
+    fn mastering_display_and_content_light() {
+        let info = from_stream(&stream("smpte2084", json!([mastering(), light()]))).unwrap();
+        assert_eq!(info.format, "HDR10");
+        let display = info.mastering_display.unwrap();
+        assert_eq!(display.primaries.as_deref(), Some("P3-D65"));
+        assert_eq!(display.red, [0.68, 0.32]);
+        assert_eq!(display.white_point, [0.3127, 0.329]);
+        assert_eq!(display.min_luminance, 0.005);
+        assert_eq!(display.max_luminance, 1000.0);
+        let light = info.content_light.unwrap();
+        assert_eq!((light.max_cll, light.max_fall), (1000, 400));
+    }
+
+    #[test]
+    fn zero_denominator_drops_the_mastering_display() {
+        let mut entry = mastering();
+        entry["min_luminance"] = json!("0/0");
+        let info = from_stream(&stream("smpte2084", json!([entry]))).unwrap();
+        assert!(info.mastering_display.is_none());
+    }
+
+    #[test]
+    fn names_primaries() {
+        let display = |red: [f64; 2], white: [f64; 2]| MasteringDisplay {
+            primaries: None,
+            red,
+            green: [0.170, 0.797],
+            blue: [0.131, 0.046],
+            white_point: white,
+            min_luminance: 0.0001,
+            max_luminance: 1000.0,
+        };
+        let d65 = [0.3127, 0.3290];
+        assert_eq!(
+            name_primaries(&display([0.708, 0.292], d65)).as_deref(),
+            Some("BT.2020")
+        );
+        // Within tolerance of BT.2020 red.
+        assert_eq!(
+            name_primaries(&display([0.704, 0.296], d65)).as_deref(),
+            Some("BT.2020")
+        );
+        assert_eq!(name_primaries(&display([0.690, 0.292], d65)), None);
+        // Right primaries, wrong white point.
+        assert_eq!(
+            name_primaries(&display([0.708, 0.292], [0.314, 0.351])),
+            None
+        );
+    }
+
+    #[test]
+    fn sdr_and_hlg() {
+        assert!(from_stream(&stream("bt709", json!([light()]))).is_none());
+        assert!(from_stream(&json!({})).is_none());
+        let hlg = from_stream(&stream("arib-std-b67", json!([]))).unwrap();
+        assert_eq!(hlg.format, "HLG");
+        assert!(hlg.mastering_display.is_none());
+    }
+
+    #[test]
+    fn dolby_vision_profiles() {
+        let info = from_stream(&stream("smpte2084", json!([dovi(8, 1)]))).unwrap();
+        assert_eq!(info.format, "Dolby Vision");
+        let dv = info.dolby_vision.unwrap();
+        assert_eq!(dv.version, "1.0");
+        assert_eq!((dv.profile, dv.level), (8, 6));
+        assert!(dv.rpu && dv.base_layer && !dv.enhancement_layer);
+        assert_eq!(dv.profile_name(), "8.1");
+
+        let name = |profile, compatibility| {
+            dolby_vision(&dovi(profile, compatibility))
+                .unwrap()
+                .profile_name()
+        };
+        assert_eq!(name(8, 4), "8.4");
+        assert_eq!(name(10, 2), "10.2");
+        // Profiles 5 and 7 carry no compatible base layer id in the name.
+        assert_eq!(name(5, 0), "5");
+        assert_eq!(name(7, 6), "7");
+
+        // Dolby Vision wins over the transfer, even on an SDR-tagged stream.
+        let info = from_stream(&stream("bt709", json!([dovi(5, 0)]))).unwrap();
+        assert_eq!(info.format, "Dolby Vision");
+        assert!(dolby_vision(&json!({ "dv_profile": 5 })).is_none());
+    }
+
+    /// A PQ stream whose header has no side data; `side_data` is on the
+    /// first frame.
+    fn with_frame(stream_side_data: Value, frame_side_data: Value) -> ExtendedMetadata {
+        let probe = json!({
+            "streams": [{
+                "index": 0, "codec_type": "video", "codec_name": "hevc",
+                "width": 3840, "height": 2160, "color_transfer": "smpte2084",
+                "side_data_list": stream_side_data,
+            }],
+            "format": { "format_name": "matroska,webm", "duration": "10.0" },
+        });
+        let mut meta = crate::extract_metadata(&probe).unwrap();
+        let frame = json!({ "frames": [{ "side_data_list": frame_side_data }] });
+        apply_frame(&mut meta, &frame.to_string());
+        meta
+    }
+
+    #[test]
+    fn frame_side_data_fills_in_the_stream() {
+        let meta = with_frame(json!([]), json!([mastering(), light()]));
+        let info = meta.hdr.unwrap();
+        assert_eq!(info.format, "HDR10");
+        assert_eq!(info.mastering_display.unwrap().max_luminance, 1000.0);
+        assert_eq!(info.content_light.unwrap().max_cll, 1000);
+    }
+
+    #[test]
+    fn stream_side_data_wins_over_the_frame() {
+        let mut frame_light = light();
+        frame_light["max_content"] = json!(4000);
+        let meta = with_frame(json!([light()]), json!([frame_light, mastering()]));
+        let info = meta.hdr.unwrap();
+        assert_eq!(info.content_light.unwrap().max_cll, 1000);
+        // What the header lacked still comes from the frame.
+        assert!(info.mastering_display.is_some());
+    }
+
+    #[test]
+    fn hdr10_plus_on_the_frame() {
+        let plus = json!({ "side_data_type": "HDR Dynamic Metadata SMPTE2094-40 (HDR10+)" });
+        let info = with_frame(json!([mastering()]), json!([plus])).hdr.unwrap();
+        assert!(info.hdr10_plus);
+        assert_eq!(info.format, "HDR10+");
+
+        // Unparsable or empty frame output leaves the stream's values alone.
+        let mut meta = with_frame(json!([mastering()]), json!([]));
+        apply_frame(&mut meta, "not json");
+        apply_frame(&mut meta, r#"{"frames": []}"#);
+        assert_eq!(meta.hdr.unwrap().format, "HDR10");
+    }
+}
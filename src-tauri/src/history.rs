@@ -0,0 +1,606 @@
+//! Local record of every analysis, in an SQLite database under the app data
+//! directory. Each entry keeps the metadata and preset results exactly as
+//! they were, so a delivered file can be shown later as it looked then,
+//! even after the file has changed or the presets have been edited.
+
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::Manager;
+
+use crate::error::ExportDoctorError;
+use crate::rules::{PresetResult, Verdict};
+use crate::{cache, AnalyzeResult, Evaluation, ExtendedMetadata, Host};
+
+const DB_FILE: &str = "history.sqlite3";
+
+const DEFAULT_LIMIT: u32 = 200;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS analyses (
+        id            INTEGER PRIMARY KEY AUTOINCREMENT,
+        path          TEXT NOT NULL,
+        file_name     TEXT NOT NULL,
+        file_size     INTEGER NOT NULL,
+        content_hash  TEXT NOT NULL,
+        analyzed_at   INTEGER NOT NULL,
+        verdict       TEXT NOT NULL,
+        metadata      TEXT NOT NULL,
+        results       TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS analyses_analyzed_at ON analyses (analyzed_at);
+    CREATE INDEX IF NOT EXISTS analyses_content_hash ON analyses (content_hash);
+    CREATE TABLE IF NOT EXISTS analysis_presets (
+        analysis_id  INTEGER NOT NULL REFERENCES analyses (id) ON DELETE CASCADE,
+        preset_id    TEXT NOT NULL,
+        verdict      TEXT NOT NULL,
+        PRIMARY KEY (analysis_id, preset_id)
+    );
+    CREATE INDEX IF NOT EXISTS analysis_presets_preset ON analysis_presets (preset_id, verdict);
+";
+
+// ── Structs ──
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetVerdict {
+    pub preset_id: String,
+    pub verdict: Verdict,
+}
+
+/// One row of the history list.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySummary {
+    pub id: i64,
+    pub path: String,
+    pub file_name: String,
+    pub file_size: u64,
+    pub content_hash: String,
+    /// Unix time in milliseconds.
+    pub analyzed_at: i64,
+    /// FLAWED when any preset was FLAWED.
+    pub verdict: Verdict,
+    pub presets: Vec<PresetVerdict>,
+}
+
+/// A stored analysis, in the shape `evaluate_presets` returned it.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    #[serde(flatten)]
+    pub summary: HistorySummary,
+    pub analysis: AnalyzeResult,
+    pub results: Vec<PresetResult>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQuery {
+    /// Matched against the path, file name and content hash.
+    pub search: Option<String>,
+    /// Entries with this verdict; for `preset_id` when that is set too.
+    pub verdict: Option<Verdict>,
+    /// Entries that were checked against this preset.
+    pub preset_id: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// Managed state: the database, opened on first use.
+#[derive(Default)]
+pub struct HistoryDb {
+    conn: Mutex<Option<Connection>>,
+}
+
+// ── Commands ──
+
+/// Newest first.
+#[tauri::command]
+pub async fn list_history(
+    app: tauri::AppHandle,
+    query: Option<HistoryQuery>,
+) -> Result<Vec<HistorySummary>, ExportDoctorError> {
+    let query = query.unwrap_or_default();
+    with_db_blocking(&app, move |conn| list(conn, &query)).await
+}
+
+#[tauri::command]
+pub async fn get_history_entry(
+    app: tauri::AppHandle,
+    id: i64,
+) -> Result<HistoryEntry, ExportDoctorError> {
+    load_entry(&app, id).await
+}
+
+#[tauri::command]
+pub async fn delete_history_entry(app: tauri::AppHandle, id: i64) -> Result<(), ExportDoctorError> {
+    with_db_blocking(&app, move |conn| {
+        conn.execute("DELETE FROM analyses WHERE id = ?1", params![id])?;
+        Ok(())
+    })
+    .await
+}
+
+/// A stored entry with its metadata and results parsed.
+pub async fn load_entry(
+    app: &tauri::AppHandle,
+    id: i64,
+) -> Result<HistoryEntry, ExportDoctorError> {
+    let (summary, metadata, results) = with_db_blocking(app, move |conn| load(conn, id))
+        .await?
+        .ok_or_else(|| ExportDoctorError::history(format!("no history entry {}", id)))?;
+    let metadata: ExtendedMetadata =
+        serde_json::from_str(&metadata).map_err(ExportDoctorError::history)?;
+    let results: Vec<PresetResult> =
+        serde_json::from_str(&results).map_err(ExportDoctorError::history)?;
+    Ok(HistoryEntry {
+        analysis: AnalyzeResult {
+            metadata,
+            file_name: summary.file_name.clone(),
+            file_size: summary.file_size,
+        },
+        summary,
+        results,
+    })
+}
+
+// ── Recording ──
+
+/// Add an analysis to the history in the background. Hashing a new file
+/// version reads the whole file, so the caller doesn't wait for it.
+/// Failures are logged, never returned: a broken history must not stop
+/// files from being checked.
+pub fn record(app: &tauri::AppHandle, path: &str, evaluation: &Evaluation) {
+    let row = match NewEntry::new(path, &evaluation.analysis, &evaluation.results) {
+        Ok(row) => row,
+        Err(e) => {
+            eprintln!("[export-doctor] failed to record history: {}", e);
+            return;
+        }
+    };
+    let app = app.clone();
+    let known = evaluation.content_hash.clone();
+    let fingerprint = evaluation.fingerprint.clone();
+    // Detached: the join handle is dropped and the task runs to completion.
+    tauri::async_runtime::spawn_blocking(move || {
+        let hash = match known {
+            Some(hash) => hash,
+            None => match content_hash(Path::new(&row.path)) {
+                Ok(hash) => {
+                    // The next analysis of this version takes it from the cache.
+                    if let Some(fingerprint) = &fingerprint {
+                        cache::remember_content_hash(
+                            Host::App(&app),
+                            &row.path,
+                            fingerprint,
+                            &hash,
+                        );
+                    }
+                    hash
+                }
+                Err(e) => {
+                    eprintln!("[export-doctor] failed to record history: {}", e);
+                    return;
+                }
+            },
+        };
+        if let Err(e) = with_db(&app, |conn| insert(conn, &row, &hash)) {
+            eprintln!("[export-doctor] failed to record history: {}", e);
+        }
+    });
+}
+
+/// SHA-256 of the whole file, so an entry can prove which bytes were
+/// delivered. Blocks for as long as reading the file takes.
+pub(crate) fn content_hash(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// ── Database ──
+
+/// Run `f` on the database, opening it (and creating the schema) first if
+/// this is the first use.
+fn with_db<T>(
+    app: &tauri::AppHandle,
+    f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>,
+) -> Result<T, ExportDoctorError> {
+    let state = app.state::<HistoryDb>();
+    let mut guard = state.conn.lock().map_err(ExportDoctorError::history)?;
+    if guard.is_none() {
+        let dir = app
+            .path()
+            .app_data_dir()
+            .map_err(ExportDoctorError::history)?;
+        std::fs::create_dir_all(&dir).map_err(ExportDoctorError::history)?;
+        *guard = Some(open(&dir.join(DB_FILE)).map_err(ExportDoctorError::history)?);
+    }
+    f(guard.as_mut().expect("history database opened above")).map_err(ExportDoctorError::history)
+}
+
+/// `with_db` on a blocking thread, for commands: SQLite calls block.
+async fn with_db_blocking<T: Send + 'static>(
+    app: &tauri::AppHandle,
+    f: impl FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+) -> Result<T, ExportDoctorError> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || with_db(&app, f))
+        .await
+        .map_err(ExportDoctorError::history)?
+}
+
+fn open(path: &Path) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+/// An analysis serialized for storage.
+struct NewEntry {
+    path: String,
+    file_name: String,
+    file_size: u64,
+    verdict: Verdict,
+    metadata: String,
+    results: Vec<(String, Verdict)>,
+    results_json: String,
+}
+
+impl NewEntry {
+    fn new(
+        path: &str,
+        analysis: &AnalyzeResult,
+        results: &[PresetResult],
+    ) -> Result<Self, serde_json::Error> {
+        let verdict = if results.iter().any(|r| r.result.verdict == Verdict::Flawed) {
+            Verdict::Flawed
+        } else {
+            Verdict::Perfect
+        };
+        Ok(NewEntry {
+            path: path.to_string(),
+            file_name: analysis.file_name.clone(),
+            file_size: analysis.file_size,
+            verdict,
+            metadata: serde_json::to_string(&analysis.metadata)?,
+            results: results
+                .iter()
+                .map(|r| (r.platform_id.clone(), r.result.verdict))
+                .collect(),
+            results_json: serde_json::to_string(results)?,
+        })
+    }
+}
+
+fn insert(conn: &mut Connection, row: &NewEntry, hash: &str) -> rusqlite::Result<i64> {
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT INTO analyses
+            (path, file_name, file_size, content_hash, analyzed_at, verdict, metadata, results)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            row.path,
+            row.file_name,
+            row.file_size as i64,
+            hash,
+            now_millis(),
+            verdict_text(row.verdict),
+            row.metadata,
+            row.results_json,
+        ],
+    )?;
+    let id = tx.last_insert_rowid();
+    for (preset_id, verdict) in &row.results {
+        tx.execute(
+            "INSERT OR REPLACE INTO analysis_presets (analysis_id, preset_id, verdict)
+             VALUES (?1, ?2, ?3)",
+            params![id, preset_id, verdict_text(*verdict)],
+        )?;
+    }
+    tx.commit()?;
+    Ok(id)
+}
+
+fn list(conn: &Connection, query: &HistoryQuery) -> rusqlite::Result<Vec<HistorySummary>> {
+    let mut sql = String::from(
+        "SELECT id, path, file_name, file_size, content_hash, analyzed_at, verdict
+         FROM analyses a WHERE 1 = 1",
+    );
+    let mut args: Vec<Value> = Vec::new();
+    if let Some(search) = query
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        sql.push_str(
+            " AND (a.path LIKE ?1 ESCAPE '\\' OR a.file_name LIKE ?1 ESCAPE '\\'
+                   OR a.content_hash LIKE ?1 ESCAPE '\\')",
+        );
+        args.push(Value::Text(format!("%{}%", escape_like(search))));
+    }
+    match (&query.preset_id, query.verdict) {
+        (Some(preset_id), verdict) => {
+            let n = args.len();
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM analysis_presets p
+                   WHERE p.analysis_id = a.id AND p.preset_id = ?{}",
+                n + 1
+            ));
+            args.push(Value::Text(preset_id.clone()));
+            if let Some(verdict) = verdict {
+                sql.push_str(&format!(" AND p.verdict = ?{}", n + 2));
+                args.push(Value::Text(verdict_text(verdict).to_string()));
+            }
+            sql.push(')');
+        }
+        (None, Some(verdict)) => {
+            sql.push_str(&format!(" AND a.verdict = ?{}", args.len() + 1));
+            args.push(Value::Text(verdict_text(verdict).to_string()));
+        }
+        (None, None) => {}
+    }
+    sql.push_str(&format!(
+        " ORDER BY a.analyzed_at DESC, a.id DESC LIMIT ?{} OFFSET ?{}",
+        args.len() + 1,
+        args.len() + 2
+    ));
+    args.push(Value::Integer(query.limit.unwrap_or(DEFAULT_LIMIT).into()));
+    args.push(Value::Integer(query.offset.unwrap_or(0).into()));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(args), summary_from_row)?;
+    let mut summaries = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+    for summary in &mut summaries {
+        summary.presets = preset_verdicts(conn, summary.id)?;
+    }
+    Ok(summaries)
+}
+
+/// Summary plus the stored metadata and results JSON.
+fn load(conn: &Connection, id: i64) -> rusqlite::Result<Option<(HistorySummary, String, String)>> {
+    let row = conn
+        .query_row(
+            "SELECT id, path, file_name, file_size, content_hash, analyzed_at, verdict,
+                    metadata, results
+             FROM analyses WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    summary_from_row(row)?,
+                    row.get::<_, String>(7)?,
+                    row.get::<_, String>(8)?,
+                ))
+            },
+        )
+        .optional()?;
+    let Some((mut summary, metadata, results)) = row else {
+        return Ok(None);
+    };
+    summary.presets = preset_verdicts(conn, id)?;
+    Ok(Some((summary, metadata, results)))
+}
+
+fn summary_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<HistorySummary> {
+    Ok(HistorySummary {
+        id: row.get(0)?,
+        path: row.get(1)?,
+        file_name: row.get(2)?,
+        file_size: row.get::<_, i64>(3)?.max(0) as u64,
+        content_hash: row.get(4)?,
+        analyzed_at: row.get(5)?,
+        verdict: parse_verdict(&row.get::<_, String>(6)?),
+        presets: Vec::new(),
+    })
+}
+
+fn preset_verdicts(conn: &Connection, id: i64) -> rusqlite::Result<Vec<PresetVerdict>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT preset_id, verdict FROM analysis_presets WHERE analysis_id = ?1 ORDER BY preset_id",
+    )?;
+    let rows = stmt.query_map(params![id], |row| {
+        Ok(PresetVerdict {
+            preset_id: row.get(0)?,
+            verdict: parse_verdict(&row.get::<_, String>(1)?),
+        })
+    })?;
+    rows.collect()
+}
+
+// ── Helpers ──
+
+fn verdict_text(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Perfect => "PERFECT",
+        Verdict::Flawed => "FLAWED",
+    }
+}
+
+fn parse_verdict(text: &str) -> Verdict {
+    if text == "PERFECT" {
+        Verdict::Perfect
+    } else {
+        Verdict::Flawed
+    }
+}
+
+/// Make `%`, `_` and the escape character itself match literally.
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        conn
+    }
+
+    fn add(conn: &mut Connection, path: &str, hash: &str, results: &[(&str, Verdict)]) -> i64 {
+        let verdict = if results.iter().any(|(_, v)| *v == Verdict::Flawed) {
+            Verdict::Flawed
+        } else {
+            Verdict::Perfect
+        };
+        let row = NewEntry {
+            path: path.to_string(),
+            file_name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            file_size: 1,
+            verdict,
+            metadata: "{}".to_string(),
+            results: results.iter().map(|(id, v)| (id.to_string(), *v)).collect(),
+            results_json: "[]".to_string(),
+        };
+        insert(conn, &row, hash).unwrap()
+    }
+
+    fn ids(conn: &Connection, query: HistoryQuery) -> Vec<i64> {
+        list(conn, &query).unwrap().iter().map(|s| s.id).collect()
+    }
+
+    /// Three entries, newest last: a passes everything, b fails YouTube,
+    /// c fails TikTok but passes YouTube.
+    fn seeded() -> (Connection, [i64; 3]) {
+        let mut conn = db();
+        let a = add(
+            &mut conn,
+            "/renders/final_v1.mov",
+            "aaa111",
+            &[("youtube", Verdict::Perfect)],
+        );
+        let b = add(
+            &mut conn,
+            "/renders/final_v2.mov",
+            "bbb222",
+            &[("youtube", Verdict::Flawed), ("tiktok", Verdict::Perfect)],
+        );
+        let c = add(
+            &mut conn,
+            "/other/100%_promo.mp4",
+            "ccc333",
+            &[("youtube", Verdict::Perfect), ("tiktok", Verdict::Flawed)],
+        );
+        (conn, [a, b, c])
+    }
+
+    #[test]
+    fn lists_newest_first_with_preset_verdicts() {
+        let (conn, [a, b, c]) = seeded();
+        let rows = list(&conn, &HistoryQuery::default()).unwrap();
+        assert_eq!(rows.iter().map(|r| r.id).collect::<Vec<_>>(), [c, b, a]);
+        assert_eq!(rows[1].verdict, Verdict::Flawed);
+        assert_eq!(
+            rows[1]
+                .presets
+                .iter()
+                .map(|p| (p.preset_id.as_str(), p.verdict))
+                .collect::<Vec<_>>(),
+            [("tiktok", Verdict::Perfect), ("youtube", Verdict::Flawed)]
+        );
+    }
+
+    #[test]
+    fn search_matches_path_name_and_hash_literally() {
+        let (conn, [a, b, c]) = seeded();
+        let search = |text: &str| HistoryQuery {
+            search: Some(text.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&conn, search("final")), [b, a]);
+        assert_eq!(ids(&conn, search("  /renders/  ")), [b, a]);
+        assert_eq!(ids(&conn, search("bbb2")), [b]);
+        // `%` and `_` are not wildcards.
+        assert_eq!(ids(&conn, search("100%_")), [c]);
+        assert!(ids(&conn, search("final%v")).is_empty());
+        assert!(ids(&conn, search("final_v_")).is_empty());
+        // Blank searches are ignored.
+        assert_eq!(ids(&conn, search("   ")), [c, b, a]);
+    }
+
+    #[test]
+    fn filters_by_overall_verdict() {
+        let (conn, [a, b, c]) = seeded();
+        let verdict = |v| HistoryQuery {
+            verdict: Some(v),
+            ..Default::default()
+        };
+        assert_eq!(ids(&conn, verdict(Verdict::Perfect)), [a]);
+        assert_eq!(ids(&conn, verdict(Verdict::Flawed)), [c, b]);
+    }
+
+    #[test]
+    fn filters_by_preset_and_its_verdict() {
+        let (conn, [a, b, c]) = seeded();
+        let preset = |id: &str, v| HistoryQuery {
+            preset_id: Some(id.to_string()),
+            verdict: v,
+            ..Default::default()
+        };
+        assert_eq!(ids(&conn, preset("tiktok", None)), [c, b]);
+        assert_eq!(
+            ids(&conn, preset("youtube", Some(Verdict::Perfect))),
+            [c, a]
+        );
+        assert_eq!(ids(&conn, preset("tiktok", Some(Verdict::Perfect))), [b]);
+        assert!(ids(&conn, preset("instagram", None)).is_empty());
+    }
+
+    #[test]
+    fn combines_search_with_filters_and_numbers_args_in_order() {
+        let (conn, [_, b, _]) = seeded();
+        let query = HistoryQuery {
+            search: Some("final".to_string()),
+            preset_id: Some("youtube".to_string()),
+            verdict: Some(Verdict::Flawed),
+            limit: Some(10),
+            offset: Some(0),
+        };
+        assert_eq!(ids(&conn, query), [b]);
+    }
+
+    #[test]
+    fn pages_with_limit_and_offset() {
+        let (conn, [a, b, c]) = seeded();
+        let page = |limit, offset| HistoryQuery {
+            limit: Some(limit),
+            offset: Some(offset),
+            ..Default::default()
+        };
+        assert_eq!(ids(&conn, page(2, 0)), [c, b]);
+        assert_eq!(ids(&conn, page(2, 2)), [a]);
+        assert!(ids(&conn, page(2, 4)).is_empty());
+        let filtered = HistoryQuery {
+            verdict: Some(Verdict::Flawed),
+            limit: Some(1),
+            offset: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(ids(&conn, filtered), [b]);
+    }
+}
@@ -0,0 +1,156 @@
+//! Interlacing judged from the picture itself with ffmpeg's `idet` filter.
+//! ffprobe's `field_order` is only a header flag, and re-wrapped files often
+//! carry the wrong one (interlaced masters flagged progressive, or the field
+//! dominance swapped), so this counts what the frames actually look like.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ExportDoctorError;
+use crate::run_ffmpeg;
+
+/// Points sampled across the duration; each decodes `SAMPLE_FRAMES` frames.
+const SAMPLES: u32 = 3;
+const SAMPLE_FRAMES: &str = "200";
+
+/// `idet` multi-frame classification, summed over the samples.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterlaceStats {
+    pub tff: u32,
+    pub bff: u32,
+    pub progressive: u32,
+    pub undetermined: u32,
+}
+
+impl InterlaceStats {
+    pub fn interlaced(&self) -> u32 {
+        self.tff + self.bff
+    }
+
+    /// "tff" or "bff" when interlaced frames outnumber progressive ones,
+    /// "progressive" when they don't, "undetermined" when `idet` could not
+    /// classify anything (static or black picture).
+    pub fn scan(&self) -> &'static str {
+        if self.interlaced() + self.progressive == 0 {
+            "undetermined"
+        } else if self.interlaced() <= self.progressive {
+            "progressive"
+        } else if self.tff >= self.bff {
+            "tff"
+        } else {
+            "bff"
+        }
+    }
+}
+
+/// Classify frames of video stream `stream` (its container index) at a few
+/// points through the file. `None` when no sample could be decoded.
+pub fn detect(
+    path: &str,
+    stream: u32,
+    duration: f64,
+) -> Result<Option<InterlaceStats>, ExportDoctorError> {
+    let points: Vec<f64> = if duration > 0.0 {
+        (0..SAMPLES)
+            .map(|i| duration * f64::from(2 * i + 1) / f64::from(2 * SAMPLES))
+            .collect()
+    } else {
+        vec![0.0]
+    };
+
+    let map = format!("0:{}", stream);
+    let mut total: Option<InterlaceStats> = None;
+    for point in points {
+        let start = format!("{:.3}", point);
+        let stderr = run_ffmpeg(&[
+            "-hide_banner",
+            "-nostats",
+            "-ss",
+            &start,
+            "-i",
+            path,
+            "-map",
+            &map,
+            "-vf",
+            "idet",
+            "-frames:v",
+            SAMPLE_FRAMES,
+            "-an",
+            "-f",
+            "null",
+            "-",
+        ])?;
+        // idet prints its totals when the graph closes; a sample that
+        // failed to decode has none and is skipped.
+        let Some(stats) = stderr.lines().rev().find_map(parse_multi_frame) else {
+            continue;
+        };
+        let sum = total.get_or_insert(InterlaceStats {
+            tff: 0,
+            bff: 0,
+            progressive: 0,
+            undetermined: 0,
+        });
+        sum.tff += stats.tff;
+        sum.bff += stats.bff;
+        sum.progressive += stats.progressive;
+        sum.undetermined += stats.undetermined;
+    }
+    Ok(total)
+}
+
+/// Counts from "Multi frame detection: TFF: 0 BFF: 0 Progressive: 198
+/// Undetermined: 2". The multi-frame figures smooth over single frames that
+/// are ambiguous on their own.
+fn parse_multi_frame(line: &str) -> Option<InterlaceStats> {
+    if !line.contains("[Parsed_idet") {
+        return None;
+    }
+    let (_, counts) = line.split_once("Multi frame detection:")?;
+    let count = |key: &str| -> Option<u32> {
+        let (_, rest) = counts.split_once(key)?;
+        rest.split_whitespace().next()?.parse().ok()
+    };
+    Some(InterlaceStats {
+        tff: count("TFF:")?,
+        bff: count("BFF:")?,
+        progressive: count("Progressive:")?,
+        undetermined: count("Undetermined:")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_multi_frame_counts() {
+        let line = "[Parsed_idet_0 @ 0x55] Multi frame detection: TFF:          180 BFF:            0 Progressive:           12 Undetermined:            8";
+        let stats = parse_multi_frame(line).unwrap();
+        assert_eq!(
+            (stats.tff, stats.bff, stats.progressive, stats.undetermined),
+            (180, 0, 12, 8)
+        );
+        assert_eq!(stats.scan(), "tff");
+
+        let single = "[Parsed_idet_0 @ 0x55] Single frame detection: TFF: 1 BFF: 0 Progressive: 2 Undetermined: 0";
+        assert!(parse_multi_frame(single).is_none());
+        assert!(parse_multi_frame("Multi frame detection: TFF: 1").is_none());
+    }
+
+    #[test]
+    fn scan_type() {
+        let stats = |tff, bff, progressive| InterlaceStats {
+            tff,
+            bff,
+            progressive,
+            undetermined: 0,
+        };
+        assert_eq!(stats(0, 0, 0).scan(), "undetermined");
+        assert_eq!(stats(10, 0, 190).scan(), "progressive");
+        assert_eq!(stats(50, 50, 100).scan(), "progressive");
+        assert_eq!(stats(10, 150, 40).scan(), "bff");
+        assert_eq!(stats(80, 80, 40).scan(), "tff");
+        assert_eq!(stats(80, 80, 40).interlaced(), 160);
+    }
+}
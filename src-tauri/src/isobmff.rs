@@ -0,0 +1,382 @@
+//! Minimal ISO-BMFF (MP4/MOV) box walker. Reads box headers only, plus the
+//! few payloads it reports on (`ftyp`, `elst`), so it costs a handful of
+//! seeks regardless of file size.
+
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Box types that can open an ISO-BMFF file. Anything else means the file
+/// isn't one and the walker reports nothing.
+const LEADING_BOXES: &[&[u8; 4]] = &[
+    b"ftyp", b"styp", b"moov", b"mdat", b"free", b"skip", b"wide", b"pdin", b"uuid",
+];
+
+/// Stop walking after this many top-level boxes; fragmented files have two
+/// per fragment, which is plenty to characterize them.
+const MAX_TOP_LEVEL: usize = 20_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mp4Layout {
+    /// Top-level box types in order of first appearance.
+    pub atoms: Vec<String>,
+    /// `moov` precedes the first `mdat`, so playback can start while
+    /// downloading.
+    pub faststart: bool,
+    /// Movie fragments (`moof`) or a fragment declaration (`mvex`).
+    pub fragmented: bool,
+    pub fragments: u32,
+    pub major_brand: Option<String>,
+    pub minor_version: Option<u32>,
+    pub compatible_brands: Vec<String>,
+    /// One entry per track that carries an edit list.
+    pub edit_lists: Vec<EditList>,
+    /// A box runs past the end of the file (incomplete render or copy).
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditList {
+    /// Zero-based `trak` index within `moov`.
+    pub track: u32,
+    pub entries: u32,
+    /// Entries with media time -1, which delay the track's start.
+    pub empty_edits: u32,
+}
+
+impl EditList {
+    /// Anything beyond a single plain entry shifts or trims the track.
+    pub fn is_complex(&self) -> bool {
+        self.entries > 1 || self.empty_edits > 0
+    }
+}
+
+struct BoxHeader {
+    kind: [u8; 4],
+    start: u64,
+    header_len: u64,
+    size: u64,
+}
+
+impl BoxHeader {
+    fn payload(&self) -> (u64, u64) {
+        (self.start + self.header_len, self.start + self.size)
+    }
+}
+
+/// Walk `path` if it is an ISO-BMFF file. Returns `None` for other formats
+/// and for files that can't be read.
+pub fn inspect(path: &Path) -> Option<Mp4Layout> {
+    let mut file = File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    walk(&mut file, len).ok().flatten()
+}
+
+fn walk<R: Read + Seek>(file: &mut R, len: u64) -> io::Result<Option<Mp4Layout>> {
+    let mut layout = Mp4Layout {
+        atoms: Vec::new(),
+        faststart: false,
+        fragmented: false,
+        fragments: 0,
+        major_brand: None,
+        minor_version: None,
+        compatible_brands: Vec::new(),
+        edit_lists: Vec::new(),
+        truncated: false,
+    };
+    let mut seen_mdat = false;
+    let mut pos = 0;
+    let mut count = 0;
+
+    while pos < len && count < MAX_TOP_LEVEL {
+        let Some(header) = read_header(file, pos, len)? else {
+            if count == 0 {
+                return Ok(None);
+            }
+            layout.truncated = true;
+            break;
+        };
+        if count == 0 && !LEADING_BOXES.contains(&&header.kind) {
+            return Ok(None);
+        }
+        let name = box_name(&header.kind);
+        if !layout.atoms.contains(&name) {
+            layout.atoms.push(name);
+        }
+        match &header.kind {
+            b"ftyp" => read_ftyp(file, &header, &mut layout)?,
+            b"moov" => {
+                layout.faststart = !seen_mdat;
+                read_moov(file, &header, &mut layout)?;
+            }
+            b"moof" => {
+                layout.fragmented = true;
+                layout.fragments += 1;
+            }
+            b"mdat" => seen_mdat = true,
+            _ => {}
+        }
+        pos = header.start + header.size;
+        count += 1;
+    }
+    Ok(Some(layout))
+}
+
+/// Read the header at `start`. `None` if it doesn't fit before `end`.
+fn read_header<R: Read + Seek>(
+    file: &mut R,
+    start: u64,
+    end: u64,
+) -> io::Result<Option<BoxHeader>> {
+    if start + 8 > end {
+        return Ok(None);
+    }
+    let mut buf = [0u8; 8];
+    file.seek(SeekFrom::Start(start))?;
+    file.read_exact(&mut buf)?;
+    let size32 = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]);
+    let kind = [buf[4], buf[5], buf[6], buf[7]];
+    let (size, header_len) = match size32 {
+        // Box extends to the end of its parent.
+        0 => (end - start, 8),
+        1 => {
+            if start + 16 > end {
+                return Ok(None);
+            }
+            let mut large = [0u8; 8];
+            file.read_exact(&mut large)?;
+            (u64::from_be_bytes(large), 16)
+        }
+        n => (n as u64, 8),
+    };
+    // A corrupt 64-bit size can overflow; treat it like any box that runs
+    // past its parent.
+    let fits = start
+        .checked_add(size)
+        .is_some_and(|box_end| box_end <= end);
+    if size < header_len || !fits {
+        return Ok(None);
+    }
+    Ok(Some(BoxHeader {
+        kind,
+        start,
+        header_len,
+        size,
+    }))
+}
+
+fn children<R: Read + Seek>(file: &mut R, parent: &BoxHeader) -> io::Result<Vec<BoxHeader>> {
+    let (mut pos, end) = parent.payload();
+    let mut out = Vec::new();
+    while let Some(header) = read_header(file, pos, end)? {
+        pos = header.start + header.size;
+        out.push(header);
+    }
+    Ok(out)
+}
+
+fn read_payload<R: Read + Seek>(file: &mut R, header: &BoxHeader, max: u64) -> io::Result<Vec<u8>> {
+    let (start, end) = header.payload();
+    let mut buf = vec![0u8; (end - start).min(max) as usize];
+    file.seek(SeekFrom::Start(start))?;
+    file.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_ftyp<R: Read + Seek>(
+    file: &mut R,
+    header: &BoxHeader,
+    layout: &mut Mp4Layout,
+) -> io::Result<()> {
+    let data = read_payload(file, header, 4096)?;
+    if data.len() < 8 {
+        return Ok(());
+    }
+    layout.major_brand = Some(brand(&data[0..4]));
+    layout.minor_version = Some(u32::from_be_bytes([data[4], data[5], data[6], data[7]]));
+    layout.compatible_brands = data[8..]
+        .chunks_exact(4)
+        .map(brand)
+        .filter(|b| !b.is_empty())
+        .collect();
+    Ok(())
+}
+
+fn read_moov<R: Read + Seek>(
+    file: &mut R,
+    moov: &BoxHeader,
+    layout: &mut Mp4Layout,
+) -> io::Result<()> {
+    let mut track = 0;
+    for child in children(file, moov)? {
+        match &child.kind {
+            b"mvex" => layout.fragmented = true,
+            b"trak" => {
+                for edts in children(file, &child)?
+                    .iter()
+                    .filter(|b| &b.kind == b"edts")
+                {
+                    for elst in children(file, edts)?.iter().filter(|b| &b.kind == b"elst") {
+                        if let Some(list) = read_elst(file, elst, track)? {
+                            layout.edit_lists.push(list);
+                        }
+                    }
+                }
+                track += 1;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn read_elst<R: Read + Seek>(
+    file: &mut R,
+    header: &BoxHeader,
+    track: u32,
+) -> io::Result<Option<EditList>> {
+    // Edit lists are tiny; cap the read in case of a corrupt size.
+    let data = read_payload(file, header, 64 * 1024)?;
+    if data.len() < 8 {
+        return Ok(None);
+    }
+    let version = data[0];
+    let count = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+    let entry_len = if version == 1 { 20 } else { 12 };
+    let empty_edits = data[8..]
+        .chunks_exact(entry_len)
+        .take(count as usize)
+        .filter(|entry| {
+            if version == 1 {
+                i64::from_be_bytes(entry[8..16].try_into().unwrap_or_default()) == -1
+            } else {
+                i32::from_be_bytes(entry[4..8].try_into().unwrap_or_default()) == -1
+            }
+        })
+        .count() as u32;
+    Ok(Some(EditList {
+        track,
+        entries: count,
+        empty_edits,
+    }))
+}
+
+fn box_name(kind: &[u8; 4]) -> String {
+    String::from_utf8_lossy(kind).to_string()
+}
+
+fn brand(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn atom(kind: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut out = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn ftyp() -> Vec<u8> {
+        atom(b"ftyp", b"isom\x00\x00\x02\x00isomiso2avc1mp41")
+    }
+
+    /// Version 0 `elst` with the given media times.
+    fn elst(media_times: &[i32]) -> Vec<u8> {
+        let mut payload = vec![0, 0, 0, 0];
+        payload.extend_from_slice(&(media_times.len() as u32).to_be_bytes());
+        for time in media_times {
+            payload.extend_from_slice(&1000u32.to_be_bytes());
+            payload.extend_from_slice(&time.to_be_bytes());
+            payload.extend_from_slice(&[0, 1, 0, 0]);
+        }
+        atom(b"elst", &payload)
+    }
+
+    fn trak(media_times: &[i32]) -> Vec<u8> {
+        atom(b"trak", &atom(b"edts", &elst(media_times)))
+    }
+
+    fn walk_bytes(bytes: &[u8]) -> Option<Mp4Layout> {
+        walk(&mut Cursor::new(bytes), bytes.len() as u64).unwrap()
+    }
+
+    #[test]
+    fn faststart_file() {
+        let moov = atom(b"moov", &[trak(&[0]), trak(&[-1, 0])].concat());
+        let bytes = [ftyp(), moov, atom(b"mdat", &[0; 64])].concat();
+        let layout = walk_bytes(&bytes).unwrap();
+        assert_eq!(layout.atoms, ["ftyp", "moov", "mdat"]);
+        assert!(layout.faststart);
+        assert!(!layout.fragmented);
+        assert!(!layout.truncated);
+        assert_eq!(layout.major_brand.as_deref(), Some("isom"));
+        assert_eq!(layout.minor_version, Some(512));
+        assert_eq!(layout.compatible_brands, ["isom", "iso2", "avc1", "mp41"]);
+        assert_eq!(layout.edit_lists.len(), 2);
+        assert_eq!(layout.edit_lists[0].track, 0);
+        assert!(!layout.edit_lists[0].is_complex());
+        assert_eq!(layout.edit_lists[1].track, 1);
+        assert_eq!(layout.edit_lists[1].empty_edits, 1);
+        assert!(layout.edit_lists[1].is_complex());
+    }
+
+    #[test]
+    fn moov_at_the_end() {
+        let bytes = [ftyp(), atom(b"mdat", &[0; 64]), atom(b"moov", &[])].concat();
+        let layout = walk_bytes(&bytes).unwrap();
+        assert!(!layout.faststart);
+        assert_eq!(layout.atoms, ["ftyp", "mdat", "moov"]);
+    }
+
+    #[test]
+    fn fragmented_file() {
+        let moov = atom(b"moov", &atom(b"mvex", &[]));
+        let fragment = [atom(b"moof", &[0; 16]), atom(b"mdat", &[0; 32])].concat();
+        let bytes = [ftyp(), moov, fragment.clone(), fragment].concat();
+        let layout = walk_bytes(&bytes).unwrap();
+        assert!(layout.fragmented);
+        assert_eq!(layout.fragments, 2);
+        assert_eq!(layout.atoms, ["ftyp", "moov", "moof", "mdat"]);
+    }
+
+    #[test]
+    fn truncated_file() {
+        let mut bytes = [ftyp(), atom(b"mdat", &[0; 64])].concat();
+        bytes.truncate(bytes.len() - 10);
+        assert!(walk_bytes(&bytes).unwrap().truncated);
+    }
+
+    #[test]
+    fn oversized_64_bit_box_is_truncated() {
+        let mut large = 1u32.to_be_bytes().to_vec();
+        large.extend_from_slice(b"mdat");
+        large.extend_from_slice(&u64::MAX.to_be_bytes());
+        let bytes = [ftyp(), large, vec![0; 16]].concat();
+        assert!(walk_bytes(&bytes).unwrap().truncated);
+    }
+
+    #[test]
+    fn size_zero_runs_to_the_end() {
+        let mut open_ended = 0u32.to_be_bytes().to_vec();
+        open_ended.extend_from_slice(b"mdat");
+        open_ended.extend_from_slice(&[0; 100]);
+        let layout = walk_bytes(&[ftyp(), open_ended].concat()).unwrap();
+        assert!(!layout.truncated);
+        assert_eq!(layout.atoms, ["ftyp", "mdat"]);
+    }
+
+    #[test]
+    fn other_formats_are_ignored() {
+        // Matroska starts with an EBML header.
+        assert!(walk_bytes(b"\x1a\x45\xdf\xa3\x9f\x42\x86\x81\x01\x42\xf7\x81").is_none());
+        assert!(walk_bytes(b"abc").is_none());
+    }
+}
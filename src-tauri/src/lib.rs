@@ -1,16 +1,59 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tauri_plugin_shell::ShellExt;
 use std::process::Command as StdCommand;
 
+use error::ExportDoctorError;
+
+mod batch;
+mod cache;
+pub mod cli;
+mod compare;
+mod crop;
+mod error;
+mod fix;
+mod gop;
+mod hdr;
+mod history;
+mod interlace;
+mod isobmff;
 mod license;
+mod loudness;
+mod packets;
+mod presets;
+mod qc;
+mod remedy;
+mod rules;
+mod timecode;
+mod timing;
+mod watcher;
 
 // ── Structs ──
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ExtendedMetadata {
+    /// Container index of the video stream judged as the picture (the first
+    /// one that isn't cover art). Passes that decode video map this stream.
+    #[serde(default)]
+    pub video_stream_index: u32,
+    /// Coded frame size, as stored in the stream.
     pub width: u32,
     pub height: u32,
+    /// Frame size as players show it: scaled by the sample aspect ratio and
+    /// swapped for 90°/270° rotation.
+    #[serde(default)]
+    pub display_width: u32,
+    #[serde(default)]
+    pub display_height: u32,
+    /// Clockwise display rotation in degrees (0, 90, 180, 270), from the
+    /// display matrix or the legacy `rotate` tag.
+    #[serde(default)]
+    pub rotation: u32,
+    #[serde(default)]
+    pub sample_aspect_ratio: Option<String>,
+    #[serde(default)]
+    pub display_aspect_ratio: Option<String>,
     pub fps: f64,
     pub fps_avg: f64,
     pub fps_r: f64,
@@ -37,9 +80,104 @@ pub struct ExtendedMetadata {
     pub audio_channels: Option<u32>,
     pub audio_channel_layout: Option<String>,
     pub bits_per_raw_sample: Option<String>,
+    /// Container-level tags (title, encoder, creation_time, ...).
+    #[serde(default)]
+    pub format_tags: BTreeMap<String, String>,
+    /// Writing application, from the container or else the video stream.
+    #[serde(default)]
+    pub encoder: Option<String>,
+    #[serde(default)]
+    pub creation_time: Option<String>,
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+    /// Start timecode, when the file carries one.
+    #[serde(default)]
+    pub timecode: Option<timecode::Timecode>,
+    /// HDR signalling; `None` for SDR video.
+    #[serde(default)]
+    pub hdr: Option<hdr::HdrInfo>,
+    /// Every stream in the file, in container order.
+    #[serde(default)]
+    pub streams: Vec<StreamInfo>,
+    /// Only measured when a selected preset checks loudness.
+    #[serde(default)]
+    pub loudness: Option<loudness::Loudness>,
+    /// Only measured when a selected preset checks frame timing or GOPs.
+    #[serde(default)]
+    pub frame_timing: Option<timing::FrameTiming>,
+    #[serde(default)]
+    pub gop: Option<gop::GopInfo>,
+    /// Box layout for MP4/MOV files, read natively rather than by ffprobe.
+    #[serde(default)]
+    pub mp4: Option<isobmff::Mp4Layout>,
+    /// Black, freeze and silence events; only scanned in deep-analysis mode
+    /// or when a selected preset checks them.
+    #[serde(default)]
+    pub qc: Option<qc::QcReport>,
+    /// Active picture area from sampled `cropdetect`; measured in
+    /// deep-analysis mode or when a selected preset checks it.
+    #[serde(default)]
+    pub crop: Option<crop::CropInfo>,
+    /// `idet` frame counts; measured in deep-analysis mode or when a
+    /// selected preset checks the detected scan type.
+    #[serde(default)]
+    pub interlace: Option<interlace::InterlaceStats>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamInfo {
+    pub index: u32,
+    /// video | audio | subtitle | data | attachment
+    pub codec_type: String,
+    pub codec_name: Option<String>,
+    pub language: Option<String>,
+    pub title: Option<String>,
+    /// Disposition flags that are set, e.g. "default", "forced", "attached_pic".
+    pub disposition: Vec<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub channels: Option<u32>,
+    pub channel_layout: Option<String>,
+    pub duration_sec: Option<f64>,
+    /// CEA-608/708 captions embedded in the video (A/53 side data).
+    #[serde(default)]
+    pub closed_captions: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Chapter {
+    pub id: i64,
+    pub start_sec: f64,
+    pub end_sec: f64,
+    pub title: Option<String>,
+}
+
+/// Subtitle codecs that carry broadcast closed captions as their own track.
+pub(crate) const CAPTION_CODECS: &[&str] = &["eia_608", "eia_708"];
+
+impl StreamInfo {
+    pub fn has_disposition(&self, flag: &str) -> bool {
+        self.disposition.iter().any(|d| d == flag)
+    }
+
+    /// Cover art and thumbnails are stored as single-frame video streams.
+    pub fn is_attached_pic(&self) -> bool {
+        self.codec_type == "video" && self.has_disposition("attached_pic")
+    }
+
+    /// Closed captions, either embedded in the video or as a 608/708 track.
+    pub fn has_captions(&self) -> bool {
+        self.closed_captions
+            || self
+                .codec_name
+                .as_deref()
+                .is_some_and(|c| CAPTION_CODECS.contains(&c))
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AnalyzeResult {
     pub metadata: ExtendedMetadata,
@@ -47,6 +185,13 @@ pub struct AnalyzeResult {
     pub file_size: u64,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluateResult {
+    pub analysis: AnalyzeResult,
+    pub results: Vec<rules::PresetResult>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FfprobeStatus {
@@ -61,36 +206,28 @@ pub struct FfprobeStatus {
 async fn analyze_video(
     app: tauri::AppHandle,
     path: String,
-) -> Result<AnalyzeResult, String> {
+) -> Result<AnalyzeResult, ExportDoctorError> {
     // Check license before analyzing
     license::check_license_valid(&app)?;
+    analyze_path(&app, &path).await
+}
 
-    // Get file info
-    let file_meta = std::fs::metadata(&path)
-        .map_err(|e| format!("Cannot access file: {}", e))?;
-    let file_name = std::path::Path::new(&path)
-        .file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "unknown".to_string());
-
-    // Run ffprobe
-    let output = run_ffprobe(&app, &path).await?;
-
-    // Parse JSON
-    let probe_data: serde_json::Value = serde_json::from_str(&output)
-        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
-
-    let metadata = extract_metadata(&probe_data)?;
-
-    Ok(AnalyzeResult {
-        metadata,
-        file_name,
-        file_size: file_meta.len(),
-    })
+#[tauri::command]
+async fn evaluate_presets(
+    app: tauri::AppHandle,
+    path: String,
+    preset_ids: Vec<String>,
+    deep: Option<bool>,
+) -> Result<EvaluateResult, ExportDoctorError> {
+    license::check_license_valid(&app)?;
+    let catalog = presets::catalog(&app);
+    let deep = deep.unwrap_or(false);
+    let (analysis, results) = evaluate_and_record(&app, &path, &catalog, &preset_ids, deep).await?;
+    Ok(EvaluateResult { analysis, results })
 }
 
 #[tauri::command]
-async fn check_ffprobe(app: tauri::AppHandle) -> Result<FfprobeStatus, String> {
+async fn check_ffprobe(app: tauri::AppHandle) -> Result<FfprobeStatus, ExportDoctorError> {
     // Try sidecar first (Tauri plugin-shell)
     match app.shell().sidecar("ffprobe") {
         Ok(cmd) => {
@@ -152,13 +289,298 @@ async fn check_ffprobe(app: tauri::AppHandle) -> Result<FfprobeStatus, String> {
 
 // ── Helpers ──
 
+const FFPROBE_ARGS: [&str; 7] = [
+    "-v", "quiet",
+    "-print_format", "json",
+    "-show_format",
+    "-show_streams",
+    "-show_chapters",
+];
+
+fn metadata_args(path: &str) -> Vec<&str> {
+    FFPROBE_ARGS.iter().copied().chain([path]).collect()
+}
+
+/// File name and size, or an error if the file can't be accessed.
+fn file_info(path: &str) -> Result<(String, u64), ExportDoctorError> {
+    let file_meta = std::fs::metadata(path).map_err(|e| ExportDoctorError::FileUnreadable {
+        path: path.to_string(),
+        detail: e.to_string(),
+    })?;
+    let file_name = std::path::Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    Ok((file_name, file_meta.len()))
+}
+
+fn parse_probe_output(output: &str, path: &str) -> Result<ExtendedMetadata, ExportDoctorError> {
+    let probe_data: serde_json::Value = serde_json::from_str(output)
+        .map_err(|e| ExportDoctorError::NotMediaFile {
+            detail: format!("Failed to parse ffprobe output: {}.", e),
+        })?;
+    let mut metadata = extract_metadata(&probe_data)?;
+    metadata.mp4 = isobmff::inspect(std::path::Path::new(path));
+    Ok(metadata)
+}
+
+/// Where an analysis runs. `Headless` is CLI mode: no Tauri runtime, so no
+/// sidecar; ffprobe comes from next to the executable or from PATH.
+#[derive(Clone, Copy)]
+pub(crate) enum Host<'a> {
+    App(&'a tauri::AppHandle),
+    Headless,
+}
+
+impl Host<'_> {
+    async fn ffprobe(self, args: &[&str]) -> Result<String, ExportDoctorError> {
+        match self {
+            Host::App(app) => run_ffprobe(app, args).await,
+            Host::Headless => run_local_ffprobe(args).await,
+        }
+    }
+}
+
+/// A probe, fresh or from the cache, with what the cache needs to store
+/// passes added to it.
+struct Probe {
+    analysis: AnalyzeResult,
+    /// Passes whose results the metadata already has.
+    passes: cache::Passes,
+    fingerprint: Option<cache::Fingerprint>,
+    content_hash: Option<String>,
+    cached: bool,
+}
+
+/// An evaluated file, with what identifies the version that was analyzed
+/// so the history can record it without reading the file again.
+pub(crate) struct Evaluation {
+    pub analysis: AnalyzeResult,
+    pub results: Vec<rules::PresetResult>,
+    /// `None` when the file couldn't be fingerprinted.
+    pub fingerprint: Option<cache::Fingerprint>,
+    /// Whole-file SHA-256, if an earlier analysis of this version had it.
+    pub content_hash: Option<String>,
+}
+
+/// Probe one file. Callers are responsible for the license check.
+pub(crate) async fn analyze_path(app: &tauri::AppHandle, path: &str) -> Result<AnalyzeResult, ExportDoctorError> {
+    let host = Host::App(app);
+    let probe = probe_cached(host, path).await?;
+    if let (Some(fingerprint), false) = (&probe.fingerprint, probe.cached) {
+        cache::store(host, path, fingerprint, probe.passes, &probe.analysis.metadata, None).await;
+    }
+    Ok(probe.analysis)
+}
+
+/// Run ffprobe on `path`, unless the cache has a probe of the file as it
+/// is now.
+async fn probe_cached(host: Host<'_>, path: &str) -> Result<Probe, ExportDoctorError> {
+    let (file_name, file_size) = file_info(path)?;
+    let lookup = cache::lookup(host, path).await;
+    let (metadata, passes, content_hash, cached) = match lookup.hit {
+        Some(hit) => (hit.metadata, hit.passes, hit.content_hash, true),
+        None => {
+            let output = host.ffprobe(&metadata_args(path)).await?;
+            (parse_probe_output(&output, path)?, cache::Passes::default(), None, false)
+        }
+    };
+    Ok(Probe {
+        analysis: AnalyzeResult {
+            metadata,
+            file_name,
+            file_size,
+        },
+        passes,
+        fingerprint: lookup.fingerprint,
+        content_hash,
+        cached,
+    })
+}
+
+/// Probe one file and evaluate it against `preset_ids`. Frame timing, GOP
+/// structure, first-frame HDR metadata and loudness are measured only when
+/// one of those presets checks them; the QC scan, crop detection and
+/// interlace detection also run when `deep` is set. Passes already cached
+/// for the unchanged file are not run again.
+/// Callers do the license check.
+pub(crate) async fn analyze_and_evaluate(
+    app: &tauri::AppHandle,
+    path: &str,
+    catalog: &[presets::Preset],
+    preset_ids: &[String],
+    deep: bool,
+) -> Result<(AnalyzeResult, Vec<rules::PresetResult>), ExportDoctorError> {
+    let evaluation = evaluate_on(Host::App(app), path, catalog, preset_ids, deep).await?;
+    Ok((evaluation.analysis, evaluation.results))
+}
+
+/// `analyze_and_evaluate`, then add the result to the history. The history
+/// write runs in the background and doesn't delay the result.
+pub(crate) async fn evaluate_and_record(
+    app: &tauri::AppHandle,
+    path: &str,
+    catalog: &[presets::Preset],
+    preset_ids: &[String],
+    deep: bool,
+) -> Result<(AnalyzeResult, Vec<rules::PresetResult>), ExportDoctorError> {
+    let evaluation = evaluate_on(Host::App(app), path, catalog, preset_ids, deep).await?;
+    history::record(app, path, &evaluation);
+    Ok((evaluation.analysis, evaluation.results))
+}
+
+/// `analyze_and_evaluate` for CLI mode, which has no async caller and
+/// shares the app's probe cache.
+pub(crate) fn evaluate_headless(
+    path: &str,
+    catalog: &[presets::Preset],
+    preset_ids: &[String],
+    deep: bool,
+) -> Result<(AnalyzeResult, Vec<rules::PresetResult>), ExportDoctorError> {
+    let evaluation =
+        tauri::async_runtime::block_on(evaluate_on(Host::Headless, path, catalog, preset_ids, deep))?;
+    Ok((evaluation.analysis, evaluation.results))
+}
+
+async fn evaluate_on(
+    host: Host<'_>,
+    path: &str,
+    catalog: &[presets::Preset],
+    preset_ids: &[String],
+    deep: bool,
+) -> Result<Evaluation, ExportDoctorError> {
+    let Probe {
+        mut analysis,
+        mut passes,
+        fingerprint,
+        content_hash,
+        cached,
+    } = probe_cached(host, path).await?;
+    let cached_passes = passes;
+    if !passes.packets && presets_use(catalog, preset_ids, presets::Metric::needs_packets) {
+        let stream = analysis.metadata.video_stream_index.to_string();
+        let output = host.ffprobe(&packets::probe_args(path, &stream)).await?;
+        packets::apply(&mut analysis.metadata, &output);
+        passes.packets = true;
+    }
+    if !passes.hdr_frame
+        && hdr::needs_frame_probe(&analysis.metadata)
+        && presets_use(catalog, preset_ids, presets::Metric::is_hdr)
+    {
+        let stream = analysis.metadata.video_stream_index.to_string();
+        let output = host.ffprobe(&hdr::frame_probe_args(path, &stream)).await?;
+        hdr::apply_frame(&mut analysis.metadata, &output);
+        passes.hdr_frame = true;
+    }
+    if !passes.loudness && has_audio(&analysis) && presets_use(catalog, preset_ids, presets::Metric::is_loudness) {
+        let path = path.to_string();
+        let measured = tauri::async_runtime::spawn_blocking(move || loudness::measure(&path))
+            .await
+            .map_err(|e| ExportDoctorError::LoudnessFailed { detail: e.to_string() })??;
+        analysis.metadata.loudness = Some(measured);
+        passes.loudness = true;
+    }
+    if !passes.qc && wants_qc(&analysis, catalog, preset_ids, deep) {
+        let path = path.to_string();
+        let audio = has_audio(&analysis);
+        let duration = analysis.metadata.duration_sec;
+        let stream = analysis.metadata.video_stream_index;
+        let report = tauri::async_runtime::spawn_blocking(move || qc::scan(&path, stream, audio, duration))
+            .await
+            .map_err(|e| ExportDoctorError::QcFailed { detail: e.to_string() })??;
+        analysis.metadata.qc = Some(report);
+        passes.qc = true;
+    }
+    if !passes.crop && wants_crop(&analysis, catalog, preset_ids, deep) {
+        let path = path.to_string();
+        let duration = analysis.metadata.duration_sec;
+        let stream = analysis.metadata.video_stream_index;
+        let frame = decoded_size(&analysis.metadata);
+        analysis.metadata.crop = tauri::async_runtime::spawn_blocking(move || crop::detect(&path, stream, duration, frame))
+            .await
+            .map_err(|e| ExportDoctorError::CropFailed { detail: e.to_string() })??;
+        passes.crop = true;
+    }
+    if !passes.interlace && wants_interlace(&analysis, catalog, preset_ids, deep) {
+        let path = path.to_string();
+        let duration = analysis.metadata.duration_sec;
+        let stream = analysis.metadata.video_stream_index;
+        analysis.metadata.interlace = tauri::async_runtime::spawn_blocking(move || interlace::detect(&path, stream, duration))
+            .await
+            .map_err(|e| ExportDoctorError::InterlaceFailed { detail: e.to_string() })??;
+        passes.interlace = true;
+    }
+    if let Some(fingerprint) = fingerprint.as_ref().filter(|_| !cached || passes != cached_passes) {
+        let hash = content_hash.as_deref();
+        cache::store(host, path, fingerprint, passes, &analysis.metadata, hash).await;
+    }
+    let results = rules::evaluate_presets(&analysis.metadata, catalog, preset_ids)?;
+    Ok(Evaluation {
+        analysis,
+        results,
+        fingerprint,
+        content_hash,
+    })
+}
+
+/// Whether any selected preset reads a metric matching `pred`. Used to skip
+/// the slower analysis passes nobody asked for.
+fn presets_use(catalog: &[presets::Preset], preset_ids: &[String], pred: fn(presets::Metric) -> bool) -> bool {
+    catalog
+        .iter()
+        .filter(|p| preset_ids.contains(&p.id))
+        .any(|p| p.uses_metric(pred))
+}
+
+/// Stills have nothing to scan.
+fn wants_qc(analysis: &AnalyzeResult, catalog: &[presets::Preset], preset_ids: &[String], deep: bool) -> bool {
+    analysis.metadata.duration_sec > 0.0 && (deep || presets_use(catalog, preset_ids, presets::Metric::is_qc))
+}
+
+/// Unlike the QC scan, crop detection also works on stills.
+fn wants_crop(analysis: &AnalyzeResult, catalog: &[presets::Preset], preset_ids: &[String], deep: bool) -> bool {
+    analysis.metadata.width > 0 && (deep || presets_use(catalog, preset_ids, presets::Metric::is_crop))
+}
+
+/// Stills have no fields to compare.
+fn wants_interlace(analysis: &AnalyzeResult, catalog: &[presets::Preset], preset_ids: &[String], deep: bool) -> bool {
+    analysis.metadata.duration_sec > 0.0
+        && analysis.metadata.width > 0
+        && (deep || presets_use(catalog, preset_ids, presets::Metric::is_interlace))
+}
+
+/// Frame size as ffmpeg decodes it: coded size, turned for rotation.
+fn decoded_size(meta: &ExtendedMetadata) -> (u32, u32) {
+    if meta.rotation % 180 == 90 {
+        (meta.height, meta.width)
+    } else {
+        (meta.width, meta.height)
+    }
+}
+
+fn has_audio(analysis: &AnalyzeResult) -> bool {
+    analysis.metadata.streams.iter().any(|s| s.codec_type == "audio")
+}
+
+/// The app's bundle identifier, which names its data and cache directories.
+/// Headless runs use it to find the same directories Tauri resolves.
+pub(crate) fn bundle_identifier() -> Option<String> {
+    let conf: serde_json::Value = serde_json::from_str(include_str!("../tauri.conf.json")).ok()?;
+    Some(conf["identifier"].as_str()?.to_string())
+}
+
 /// Resolve the path to the ffprobe binary next to the current executable.
 /// In a bundled .app, both live under Contents/MacOS/.
 fn resolve_ffprobe_path() -> Option<std::path::PathBuf> {
+    resolve_bundled_binary("ffprobe")
+}
+
+/// Find the tool `name` (ffprobe, ffmpeg) next to the current executable.
+pub(crate) fn resolve_bundled_binary(name: &str) -> Option<std::path::PathBuf> {
     if let Ok(exe) = std::env::current_exe() {
         if let Some(dir) = exe.parent() {
             // Try plain "ffprobe" (bundled app) or "ffprobe.exe" (Windows)
-            let plain_name = if cfg!(windows) { "ffprobe.exe" } else { "ffprobe" };
+            let plain_name = if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() };
             let candidate = dir.join(plain_name);
             eprintln!("[export-doctor] checking direct path: {:?} exists={}", candidate, candidate.exists());
             if candidate.exists() {
@@ -173,7 +595,7 @@ fn resolve_ffprobe_path() -> Option<std::path::PathBuf> {
             } else {
                 "x86_64-unknown-linux-gnu"
             };
-            let candidate_triple = dir.join(format!("ffprobe-{}", triple));
+            let candidate_triple = dir.join(format!("{}-{}", name, triple));
             eprintln!("[export-doctor] checking triple path: {:?} exists={}", candidate_triple, candidate_triple.exists());
             if candidate_triple.exists() {
                 return Some(candidate_triple);
@@ -183,6 +605,37 @@ fn resolve_ffprobe_path() -> Option<std::path::PathBuf> {
     None
 }
 
+/// Run ffmpeg (bundled, then PATH) and return its stderr, where the
+/// analysis filters log. Blocks until ffmpeg exits.
+pub(crate) fn run_ffmpeg(args: &[&str]) -> Result<String, ExportDoctorError> {
+    let mut last_error = None;
+    let candidates = resolve_bundled_binary("ffmpeg")
+        .into_iter()
+        .chain(std::iter::once("ffmpeg".into()));
+    for ffmpeg in candidates {
+        match StdCommand::new(&ffmpeg).args(args).output() {
+            Ok(output) => return Ok(String::from_utf8_lossy(&output.stderr).to_string()),
+            Err(e) => {
+                eprintln!("[export-doctor] ffmpeg at {:?} failed: {}", ffmpeg, e);
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(ExportDoctorError::FfmpegNotFound {
+        detail: last_error.map(|e| e.to_string()).unwrap_or_default(),
+    })
+}
+
+/// Last non-empty line of ffmpeg's stderr, which is where it reports why it
+/// stopped.
+pub(crate) fn last_line(stderr: &str) -> Option<&str> {
+    stderr
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+}
+
 /// Try running ffprobe directly via std::process::Command (bypasses Tauri shell plugin).
 fn try_direct_ffprobe() -> Option<FfprobeStatus> {
     if let Some(ffprobe_path) = resolve_ffprobe_path() {
@@ -211,10 +664,10 @@ fn try_direct_ffprobe() -> Option<FfprobeStatus> {
 }
 
 /// Run ffprobe directly with args and return stdout.
-fn run_ffprobe_direct(path: &str) -> Option<String> {
+fn run_ffprobe_direct(args: &[&str]) -> Option<String> {
     if let Some(ffprobe_path) = resolve_ffprobe_path() {
         let result = StdCommand::new(&ffprobe_path)
-            .args(["-v", "quiet", "-print_format", "json", "-show_format", "-show_streams", path])
+            .args(args)
             .output();
         match result {
             Ok(output) => {
@@ -233,18 +686,10 @@ fn run_ffprobe_direct(path: &str) -> Option<String> {
     None
 }
 
-async fn run_ffprobe(app: &tauri::AppHandle, path: &str) -> Result<String, String> {
-    let args = [
-        "-v", "quiet",
-        "-print_format", "json",
-        "-show_format",
-        "-show_streams",
-        path,
-    ];
-
+async fn run_ffprobe(app: &tauri::AppHandle, args: &[&str]) -> Result<String, ExportDoctorError> {
     // Try sidecar first (Tauri plugin-shell)
     if let Ok(cmd) = app.shell().sidecar("ffprobe") {
-        if let Ok(output) = cmd.args(&args).output().await {
+        if let Ok(output) = cmd.args(args).output().await {
             if output.status.success() {
                 let stdout = String::from_utf8_lossy(&output.stdout).to_string();
                 if !stdout.trim().is_empty() {
@@ -254,48 +699,62 @@ async fn run_ffprobe(app: &tauri::AppHandle, path: &str) -> Result<String, Strin
         }
     }
 
-    // Fallback: direct binary execution (bypasses Tauri shell plugin)
-    if let Some(stdout) = run_ffprobe_direct(path) {
-        return Ok(stdout);
-    }
+    // Fallback: direct binary, then system ffprobe from PATH
+    run_local_ffprobe(args).await
+}
+
+/// `run_ffprobe` without the sidecar: the bundled binary, then the one on
+/// PATH. Both block until ffprobe exits, so they run on a blocking thread.
+async fn run_local_ffprobe(args: &[&str]) -> Result<String, ExportDoctorError> {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    tauri::async_runtime::spawn_blocking(move || {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        match run_ffprobe_direct(&args) {
+            Some(stdout) => Ok(stdout),
+            None => run_system_ffprobe(&args),
+        }
+    })
+    .await
+    .map_err(|e| ExportDoctorError::FfprobeFailed { stderr: e.to_string() })?
+}
 
-    // Fallback: system ffprobe from PATH
-    let output = app
-        .shell()
-        .command("ffprobe")
-        .args(&args)
+/// Run the ffprobe found on PATH.
+fn run_system_ffprobe(args: &[&str]) -> Result<String, ExportDoctorError> {
+    let output = StdCommand::new("ffprobe")
+        .args(args)
         .output()
-        .await
-        .map_err(|e| {
-            format!(
-                "ffprobe not found. Please reinstall the application. Error: {}",
-                e
-            )
+        .map_err(|e| ExportDoctorError::FfprobeNotFound {
+            detail: e.to_string(),
         })?;
 
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("ffprobe failed: {}", stderr));
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        return Err(ExportDoctorError::FfprobeFailed { stderr });
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     if stdout.trim().is_empty() {
-        return Err("ffprobe returned empty output. The file may not be a valid media file.".to_string());
+        return Err(ExportDoctorError::NotMediaFile {
+            detail: "ffprobe returned empty output.".to_string(),
+        });
     }
 
     Ok(stdout)
 }
 
-fn extract_metadata(probe_data: &serde_json::Value) -> Result<ExtendedMetadata, String> {
+fn extract_metadata(probe_data: &serde_json::Value) -> Result<ExtendedMetadata, ExportDoctorError> {
     let streams = probe_data["streams"]
         .as_array()
-        .ok_or("No streams found in ffprobe output")?;
+        .ok_or_else(|| ExportDoctorError::NotMediaFile {
+            detail: "No streams found in ffprobe output.".to_string(),
+        })?;
     let format = &probe_data["format"];
 
+    // Cover art is a video stream too; never judge it as the picture.
     let video_stream = streams
         .iter()
-        .find(|s| s["codec_type"].as_str() == Some("video"))
-        .ok_or("No video stream found")?;
+        .find(|s| s["codec_type"].as_str() == Some("video") && s["disposition"]["attached_pic"] != 1)
+        .ok_or(ExportDoctorError::NoVideoStream)?;
     let audio_stream = streams
         .iter()
         .find(|s| s["codec_type"].as_str() == Some("audio"));
@@ -314,6 +773,57 @@ fn extract_metadata(probe_data: &serde_json::Value) -> Result<ExtendedMetadata,
         .unwrap_or("unknown")
         .to_string();
 
+    // Display geometry
+    let width = video_stream["width"].as_u64().unwrap_or(0) as u32;
+    let height = video_stream["height"].as_u64().unwrap_or(0) as u32;
+    let sample_aspect_ratio = video_stream["sample_aspect_ratio"]
+        .as_str()
+        .filter(|s| parse_ratio(s).is_some())
+        .map(|s| s.to_string());
+    let rotation = parse_rotation(video_stream);
+    let sar = sample_aspect_ratio.as_deref().and_then(parse_ratio);
+    let display_width = match sar {
+        Some((num, den)) => (width as f64 * num as f64 / den as f64).round() as u32,
+        None => width,
+    };
+    // Unrotated, like ffprobe's own; square pixels when the SAR is unknown.
+    let display_aspect_ratio = video_stream["display_aspect_ratio"]
+        .as_str()
+        .filter(|s| parse_ratio(s).is_some())
+        .map(|s| s.to_string())
+        .or_else(|| {
+            let (num, den) = sar.unwrap_or((1, 1));
+            reduced_ratio(width as u64 * num as u64, height as u64 * den as u64)
+        });
+    let (display_width, display_height) = if rotation % 180 == 90 {
+        (height, display_width)
+    } else {
+        (display_width, height)
+    };
+
+    // Container tags
+    let format_tags: BTreeMap<String, String> = format["tags"]
+        .as_object()
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    let tag = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| format_tags.get(*k))
+            .filter(|v| !v.trim().is_empty())
+            .cloned()
+    };
+    let encoder = tag(&["encoder", "com.apple.quicktime.software", "encoding_tool"]).or_else(|| {
+        video_stream["tags"]["encoder"]
+            .as_str()
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| s.to_string())
+    });
+    let creation_time = tag(&["creation_time", "com.apple.quicktime.creationdate", "date"]);
+
     // Video codec
     let raw_codec = video_stream["codec_name"].as_str().unwrap_or("unknown");
     let codec_tag = video_stream["codec_tag_string"].as_str().unwrap_or("");
@@ -324,8 +834,14 @@ fn extract_metadata(probe_data: &serde_json::Value) -> Result<ExtendedMetadata,
     };
 
     Ok(ExtendedMetadata {
-        width: video_stream["width"].as_u64().unwrap_or(0) as u32,
-        height: video_stream["height"].as_u64().unwrap_or(0) as u32,
+        video_stream_index: video_stream["index"].as_u64().unwrap_or(0) as u32,
+        width,
+        height,
+        display_width,
+        display_height,
+        rotation,
+        sample_aspect_ratio,
+        display_aspect_ratio,
         fps,
         fps_avg,
         fps_r,
@@ -377,15 +893,87 @@ fn extract_metadata(probe_data: &serde_json::Value) -> Result<ExtendedMetadata,
             .get("bits_per_raw_sample")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
+        format_tags,
+        encoder,
+        creation_time,
+        chapters: probe_data["chapters"]
+            .as_array()
+            .map(|chapters| chapters.iter().map(extract_chapter).collect())
+            .unwrap_or_default(),
+        timecode: timecode::extract(probe_data, fps),
+        hdr: hdr::from_stream(video_stream),
+        streams: streams.iter().map(extract_stream).collect(),
+        loudness: None,
+        frame_timing: None,
+        gop: None,
+        mp4: None,
+        qc: None,
+        crop: None,
+        interlace: None,
     })
 }
 
+fn extract_stream(stream: &serde_json::Value) -> StreamInfo {
+    let text = |val: &serde_json::Value| {
+        val.as_str()
+            .filter(|s| !s.is_empty() && *s != "und")
+            .map(|s| s.to_string())
+    };
+    let disposition = stream["disposition"]
+        .as_object()
+        .map(|flags| {
+            flags
+                .iter()
+                .filter(|(_, set)| set.as_i64() == Some(1))
+                .map(|(name, _)| name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    StreamInfo {
+        index: stream["index"].as_u64().unwrap_or(0) as u32,
+        codec_type: stream["codec_type"].as_str().unwrap_or("unknown").to_string(),
+        codec_name: text(&stream["codec_name"]),
+        language: text(&stream["tags"]["language"]),
+        title: text(&stream["tags"]["title"]),
+        disposition,
+        width: stream["width"].as_u64().map(|v| v as u32),
+        height: stream["height"].as_u64().map(|v| v as u32),
+        channels: stream["channels"].as_u64().map(|v| v as u32),
+        channel_layout: text(&stream["channel_layout"]),
+        duration_sec: stream["duration"].as_str().and_then(|s| s.parse::<f64>().ok()),
+        closed_captions: stream["closed_captions"].as_i64() == Some(1),
+    }
+}
+
+fn extract_chapter(chapter: &serde_json::Value) -> Chapter {
+    let seconds = |val: &serde_json::Value| {
+        val.as_str()
+            .and_then(|s| s.parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+    Chapter {
+        id: chapter["id"].as_i64().unwrap_or(0),
+        start_sec: seconds(&chapter["start_time"]),
+        end_sec: seconds(&chapter["end_time"]),
+        title: chapter["tags"]["title"]
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
+    }
+}
+
+/// Anything faster is a timebase ffprobe reported in place of a frame rate
+/// (1000/1 from Matroska, 90000/1 from MPEG-TS), not a real one.
+const MAX_FPS: f64 = 500.0;
+
+/// Parse an "N/D" frame rate. `0` for "0/0" (unknown), malformed values and
+/// rates above `MAX_FPS`.
 fn parse_frame_rate(rate_str: &str) -> (f64, f64) {
     let parts: Vec<&str> = rate_str.split('/').collect();
     if parts.len() == 2 {
         let num: f64 = parts[0].parse().unwrap_or(0.0);
         let den: f64 = parts[1].parse().unwrap_or(1.0);
-        if den > 0.0 {
+        if den > 0.0 && num / den <= MAX_FPS {
             let val = (num / den * 1000.0).round() / 1000.0;
             return (val, val);
         }
@@ -393,6 +981,46 @@ fn parse_frame_rate(rate_str: &str) -> (f64, f64) {
     (0.0, 0.0)
 }
 
+/// Parse an "N:D" aspect ratio. `None` for "0:1" (unknown) and malformed
+/// values.
+fn parse_ratio(ratio: &str) -> Option<(u32, u32)> {
+    let (num, den) = ratio.split_once(':')?;
+    let num: u32 = num.trim().parse().ok()?;
+    let den: u32 = den.trim().parse().ok()?;
+    (num > 0 && den > 0).then_some((num, den))
+}
+
+/// `width:height` in lowest terms, as ffprobe writes aspect ratios. `None`
+/// when either side is zero.
+fn reduced_ratio(width: u64, height: u64) -> Option<String> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let (mut a, mut b) = (width, height);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    Some(format!("{}:{}", width / a, height / a))
+}
+
+/// Clockwise rotation in degrees, snapped to a quarter turn. The display
+/// matrix side data stores the counter-clockwise angle; older files use a
+/// `rotate` tag with the clockwise one.
+fn parse_rotation(stream: &serde_json::Value) -> u32 {
+    let matrix = stream["side_data_list"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|d| d["side_data_type"].as_str() == Some("Display Matrix"))
+        .and_then(|d| d["rotation"].as_f64())
+        .map(|ccw| -ccw);
+    let tag = stream["tags"]["rotate"]
+        .as_str()
+        .and_then(|s| s.trim().parse::<f64>().ok());
+    let degrees = matrix.or(tag).unwrap_or(0.0);
+    ((degrees / 90.0).round() as i64 * 90).rem_euclid(360) as u32
+}
+
 fn parse_optional_u64(val: &serde_json::Value) -> Option<u64> {
     val.as_str()
         .filter(|s| *s != "N/A" && !s.is_empty())
@@ -413,9 +1041,34 @@ pub fn run() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(watcher::WatchState::default())
+        .manage(fix::FixState::default())
+        .manage(history::HistoryDb::default())
+        .setup(|app| {
+            watcher::restore(app.handle());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             analyze_video,
+            evaluate_presets,
+            batch::analyze_batch,
             check_ffprobe,
+            presets::list_presets,
+            presets::create_custom_preset,
+            presets::update_custom_preset,
+            presets::delete_custom_preset,
+            presets::import_custom_presets,
+            presets::export_preset,
+            watcher::get_watch_config,
+            watcher::set_watch_config,
+            fix::fix_for_preset,
+            fix::cancel_fix,
+            history::list_history,
+            history::get_history_entry,
+            history::delete_history_entry,
+            cache::clear_probe_cache,
+            compare::compare_files,
             license::get_license_status,
             license::validate_license_online,
             license::start_checkout,
@@ -426,3 +1079,316 @@ pub fn run() {
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    /// ffprobe leaves unknown fields out, so a `null` override removes one.
+    fn merge(stream: &mut Value, overrides: Value) {
+        let stream = stream.as_object_mut().unwrap();
+        for (key, value) in overrides.as_object().unwrap() {
+            if value.is_null() {
+                stream.remove(key);
+            } else {
+                stream.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    /// ffprobe output for a 10 s 1920x1080 ProRes/PCM master; `video` and
+    /// `audio` are merged into the respective streams.
+    fn probe(video: Value, audio: Value) -> Value {
+        let mut v = json!({
+            "index": 0, "codec_type": "video", "codec_name": "prores",
+            "width": 1920, "height": 1080,
+            "r_frame_rate": "25/1", "avg_frame_rate": "25/1",
+            "pix_fmt": "yuv422p10le", "bits_per_raw_sample": "10",
+            "disposition": { "default": 1, "attached_pic": 0 },
+        });
+        merge(&mut v, video);
+        let mut a = json!({
+            "index": 1, "codec_type": "audio", "codec_name": "pcm_s24le",
+            "sample_rate": "48000", "channels": 2, "channel_layout": "stereo",
+        });
+        merge(&mut a, audio);
+        json!({
+            "streams": [v, a],
+            "format": { "format_name": "mov,mp4,m4a,3gp,3g2,mj2", "duration": "10.000000" },
+        })
+    }
+
+    fn extract(video: Value, audio: Value) -> ExtendedMetadata {
+        extract_metadata(&probe(video, audio)).unwrap()
+    }
+
+    #[test]
+    fn pixel_format_and_bit_depth() {
+        let meta = extract(json!({}), json!({}));
+        assert_eq!(meta.container, "mov");
+        assert_eq!(meta.video_codec, "prores");
+        assert_eq!(meta.pix_fmt.as_deref(), Some("yuv422p10le"));
+        assert_eq!(meta.bits_per_raw_sample.as_deref(), Some("10"));
+        assert_eq!(meta.duration_sec, 10.0);
+
+        let meta = extract(
+            json!({ "codec_name": "h264", "pix_fmt": "yuv420p", "bits_per_raw_sample": null }),
+            json!({}),
+        );
+        assert_eq!(meta.pix_fmt.as_deref(), Some("yuv420p"));
+        assert_eq!(meta.bits_per_raw_sample, None);
+    }
+
+    #[test]
+    fn color_fields() {
+        let meta = extract(
+            json!({
+                "color_range": "tv", "color_space": "bt2020nc",
+                "color_transfer": "smpte2084", "color_primaries": "bt2020",
+            }),
+            json!({}),
+        );
+        assert_eq!(meta.color_range.as_deref(), Some("tv"));
+        assert_eq!(meta.color_space.as_deref(), Some("bt2020nc"));
+        assert_eq!(meta.color_transfer.as_deref(), Some("smpte2084"));
+        assert_eq!(meta.color_primaries.as_deref(), Some("bt2020"));
+
+        // Untagged files leave them unknown rather than assuming BT.709.
+        let meta = extract(json!({}), json!({}));
+        assert_eq!(meta.color_space, None);
+        assert_eq!(meta.color_primaries, None);
+    }
+
+    #[test]
+    fn field_order() {
+        let meta = extract(json!({ "field_order": "tt" }), json!({}));
+        assert_eq!(meta.field_order.as_deref(), Some("tt"));
+        let meta = extract(json!({ "field_order": "progressive" }), json!({}));
+        assert_eq!(meta.field_order.as_deref(), Some("progressive"));
+        assert_eq!(extract(json!({}), json!({})).field_order, None);
+    }
+
+    #[test]
+    fn audio_layout() {
+        let meta = extract(json!({}), json!({}));
+        assert_eq!(meta.audio_codec.as_deref(), Some("pcm_s24le"));
+        assert_eq!(meta.audio_sample_rate, Some(48000));
+        assert_eq!(meta.audio_channels, Some(2));
+        assert_eq!(meta.audio_channel_layout.as_deref(), Some("stereo"));
+
+        let meta = extract(
+            json!({}),
+            json!({ "channels": 6, "channel_layout": null, "channel_layout_name": "5.1(side)" }),
+        );
+        assert_eq!(meta.audio_channels, Some(6));
+        assert_eq!(meta.audio_channel_layout.as_deref(), Some("5.1(side)"));
+
+        let mut silent = probe(json!({}), json!({}));
+        silent["streams"].as_array_mut().unwrap().pop();
+        let meta = extract_metadata(&silent).unwrap();
+        assert_eq!(meta.audio_codec, None);
+        assert_eq!(meta.audio_channels, None);
+        assert_eq!(meta.audio_channel_layout, None);
+    }
+
+    #[test]
+    fn cover_art_is_not_the_picture() {
+        let mut probe = probe(json!({ "index": 1 }), json!({ "index": 2 }));
+        let cover = json!({
+            "index": 0, "codec_type": "video", "codec_name": "mjpeg",
+            "width": 600, "height": 600, "pix_fmt": "yuvj420p",
+            "disposition": { "default": 0, "attached_pic": 1 },
+        });
+        probe["streams"].as_array_mut().unwrap().insert(0, cover);
+        let meta = extract_metadata(&probe).unwrap();
+        assert_eq!(meta.video_stream_index, 1);
+        assert_eq!(meta.video_codec, "prores");
+        assert_eq!((meta.width, meta.height), (1920, 1080));
+        // The cover is still listed among the streams.
+        assert_eq!(meta.streams.len(), 3);
+        assert!(meta.streams[0].is_attached_pic());
+
+        let only_cover = json!({
+            "streams": [probe["streams"][0].clone(), probe["streams"][2].clone()],
+            "format": probe["format"].clone(),
+        });
+        assert_eq!(
+            extract_metadata(&only_cover).unwrap_err(),
+            ExportDoctorError::NoVideoStream
+        );
+    }
+
+    fn matrix(rotation: f64) -> Value {
+        json!({ "side_data_list": [
+            { "side_data_type": "Display Matrix", "displaymatrix": "...", "rotation": rotation },
+        ] })
+    }
+
+    #[test]
+    fn rotation() {
+        let tag = |rotate: &str| json!({ "tags": { "rotate": rotate } });
+        let cases = [
+            (json!({}), 0),
+            (tag("90"), 90),
+            (tag(" 180 "), 180),
+            (tag("270"), 270),
+            (tag("-90"), 270),
+            (tag("sideways"), 0),
+            // The matrix angle is counter-clockwise.
+            (matrix(-90.0), 90),
+            (matrix(90.0), 270),
+            (matrix(-270.0), 270),
+            (matrix(270.0), 90),
+            (matrix(180.0), 180),
+            (matrix(-180.0), 180),
+            (matrix(-89.98), 90),
+            (matrix(0.0), 0),
+        ];
+        for (stream, expected) in cases {
+            assert_eq!(parse_rotation(&stream), expected, "{}", stream);
+        }
+
+        // The matrix wins over a stale tag.
+        let mut both = matrix(-90.0);
+        both["tags"] = json!({ "rotate": "180" });
+        assert_eq!(parse_rotation(&both), 90);
+    }
+
+    #[test]
+    fn rotation_swaps_display_size() {
+        for (rotation, display) in [
+            (-90.0, (1080, 1920)),
+            (90.0, (1080, 1920)),
+            (180.0, (1920, 1080)),
+            (0.0, (1920, 1080)),
+        ] {
+            let meta = extract(matrix(rotation), json!({}));
+            assert_eq!((meta.width, meta.height), (1920, 1080));
+            assert_eq!((meta.display_width, meta.display_height), display);
+        }
+
+        // Anamorphic width is corrected before the swap.
+        let mut video = matrix(-90.0);
+        video["width"] = json!(1440);
+        video["sample_aspect_ratio"] = json!("4:3");
+        let meta = extract(video, json!({}));
+        assert_eq!(meta.rotation, 90);
+        assert_eq!((meta.display_width, meta.display_height), (1080, 1920));
+    }
+
+    #[test]
+    fn ratios() {
+        assert_eq!(parse_ratio("16:9"), Some((16, 9)));
+        assert_eq!(parse_ratio(" 4 : 3 "), Some((4, 3)));
+        assert_eq!(parse_ratio("0:1"), None);
+        assert_eq!(parse_ratio("1:0"), None);
+        assert_eq!(parse_ratio("N/A"), None);
+        assert_eq!(parse_ratio("16/9"), None);
+        assert_eq!(parse_ratio(""), None);
+
+        assert_eq!(reduced_ratio(1920, 1080).as_deref(), Some("16:9"));
+        assert_eq!(reduced_ratio(1440 * 4, 1080 * 3).as_deref(), Some("16:9"));
+        assert_eq!(reduced_ratio(720 * 64, 576 * 45).as_deref(), Some("16:9"));
+        assert_eq!(reduced_ratio(4096, 2160).as_deref(), Some("256:135"));
+        assert_eq!(reduced_ratio(0, 1080), None);
+    }
+
+    #[test]
+    fn display_aspect_ratio() {
+        // As reported.
+        let meta = extract(
+            json!({ "sample_aspect_ratio": "1:1", "display_aspect_ratio": "16:9" }),
+            json!({}),
+        );
+        assert_eq!(meta.display_aspect_ratio.as_deref(), Some("16:9"));
+
+        // Anamorphic HDV with no DAR: 1440x1080 at 4:3 pixels is 16:9.
+        let meta = extract(
+            json!({ "width": 1440, "sample_aspect_ratio": "4:3" }),
+            json!({}),
+        );
+        assert_eq!(meta.sample_aspect_ratio.as_deref(), Some("4:3"));
+        assert_eq!(meta.display_aspect_ratio.as_deref(), Some("16:9"));
+        assert_eq!((meta.display_width, meta.display_height), (1920, 1080));
+
+        // Unknown values are recomputed too; unknown pixels are square.
+        let meta = extract(
+            json!({
+                "width": 720, "height": 576,
+                "sample_aspect_ratio": "64:45", "display_aspect_ratio": "0:1",
+            }),
+            json!({}),
+        );
+        assert_eq!(meta.display_aspect_ratio.as_deref(), Some("16:9"));
+        let meta = extract(
+            json!({ "sample_aspect_ratio": "0:1", "display_aspect_ratio": "N/A" }),
+            json!({}),
+        );
+        assert_eq!(meta.sample_aspect_ratio, None);
+        assert_eq!(meta.display_aspect_ratio.as_deref(), Some("16:9"));
+
+        let meta = extract(json!({ "width": 0, "height": 0 }), json!({}));
+        assert_eq!(meta.display_aspect_ratio, None);
+    }
+
+    #[test]
+    fn frame_rates() {
+        assert_eq!(parse_frame_rate("30000/1001"), (29.97, 29.97));
+        assert_eq!(parse_frame_rate("25/1"), (25.0, 25.0));
+        assert_eq!(parse_frame_rate("240/1"), (240.0, 240.0));
+        assert_eq!(parse_frame_rate("0/0"), (0.0, 0.0));
+        assert_eq!(parse_frame_rate("0/1"), (0.0, 0.0));
+        assert_eq!(parse_frame_rate("1000/1"), (0.0, 0.0));
+        assert_eq!(parse_frame_rate("90000/1"), (0.0, 0.0));
+        assert_eq!(parse_frame_rate("N/A"), (0.0, 0.0));
+        assert_eq!(parse_frame_rate("30"), (0.0, 0.0));
+    }
+
+    #[test]
+    fn timebase_rates_fall_back() {
+        // A WebM stream whose average rate is unknown.
+        let meta = extract(
+            json!({ "r_frame_rate": "30/1", "avg_frame_rate": "0/0" }),
+            json!({}),
+        );
+        assert_eq!((meta.fps, meta.fps_avg, meta.fps_r), (30.0, 0.0, 30.0));
+
+        // A Matroska timebase in r_frame_rate.
+        let meta = extract(
+            json!({ "r_frame_rate": "1000/1", "avg_frame_rate": "24000/1001" }),
+            json!({}),
+        );
+        assert_eq!((meta.fps, meta.fps_avg, meta.fps_r), (23.976, 23.976, 0.0));
+
+        let meta = extract(
+            json!({ "r_frame_rate": "1000/1", "avg_frame_rate": "0/0" }),
+            json!({}),
+        );
+        assert_eq!(meta.fps, 0.0);
+    }
+
+    /// `pts,dts,flags` lines, as `packets::probe_args` lists them.
+    fn packet_csv(times: impl Iterator<Item = f64>) -> String {
+        times.map(|t| format!("{:.6},{:.6},___\n", t, t)).collect()
+    }
+
+    #[test]
+    fn variable_frame_rate_comes_from_packets() {
+        // Phone footage: the header rates both claim ~30 fps.
+        let header = json!({ "r_frame_rate": "30/1", "avg_frame_rate": "2997/100" });
+
+        let mut vfr = extract(header.clone(), json!({}));
+        assert_eq!((vfr.fps_r, vfr.fps_avg), (30.0, 29.97));
+        assert!(vfr.frame_timing.is_none());
+        let jittered = (0..90).map(|i| i as f64 / 30.0 + (i % 3) as f64 * 0.004);
+        packets::apply(&mut vfr, &packet_csv(jittered));
+        assert!(vfr.frame_timing.unwrap().vfr);
+
+        let mut cfr = extract(header, json!({}));
+        packets::apply(&mut cfr, &packet_csv((0..90).map(|i| i as f64 / 30.0)));
+        let timing = cfr.frame_timing.unwrap();
+        assert!(!timing.vfr);
+        assert_eq!(timing.measured_fps, 30.0);
+    }
+}
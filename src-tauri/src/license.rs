@@ -4,12 +4,14 @@ use tauri_plugin_store::StoreExt;
 use tauri_plugin_opener::OpenerExt;
 use sha2::{Sha256, Digest};
 
+use crate::error::ExportDoctorError;
+
 // ── IMPORTANT: Replace with your actual Cloudflare Worker URL ──
 const API_BASE: &str = "https://export-doctor-license.mertulgut-556.workers.dev";
 const TRIAL_DAYS: u64 = 7;
 const OFFLINE_MAX_DAYS: u64 = 30;
-const STORE_FILE: &str = "license.json";
-const STORE_KEY: &str = "license";
+pub(crate) const STORE_FILE: &str = "license.json";
+pub(crate) const STORE_KEY: &str = "license";
 
 // ── Types ──
 
@@ -134,21 +136,42 @@ fn get_machine_id() -> String {
 }
 
 /// Check if the current license allows analysis. Called from analyze_video.
-pub fn check_license_valid(app: &tauri::AppHandle) -> Result<(), String> {
-    let (mut data, _) = read_license(app);
+pub fn check_license_valid(app: &tauri::AppHandle) -> Result<(), ExportDoctorError> {
+    let (data, _) = read_license(app);
+    license_allows_analysis(data)
+}
+
+/// Same check for headless runs that read the store file directly.
+/// `stored` is the value under the license key. A missing or unreadable
+/// value is refused rather than treated as a new trial, or every run would
+/// get one; the CLI saves the trial start itself on first use.
+pub fn check_stored_license(stored: Option<serde_json::Value>) -> Result<(), ExportDoctorError> {
+    let data = stored
+        .and_then(|val| serde_json::from_value(val).ok())
+        .ok_or(ExportDoctorError::LicenseInvalid)?;
+    license_allows_analysis(data)
+}
+
+/// Store value for a trial starting now, for headless runs on a machine
+/// where the app has never been opened.
+pub fn new_trial_value() -> Option<serde_json::Value> {
+    serde_json::to_value(default_license()).ok()
+}
+
+fn license_allows_analysis(mut data: LicenseData) -> Result<(), ExportDoctorError> {
     data.status = compute_effective_status(&data);
     let now = now_unix();
     match data.status.as_str() {
         "trial" | "active" if data.expires_at > now => Ok(()),
-        "expired" => Err("Your license has expired. Please subscribe to continue using Export Doctor.".to_string()),
-        _ => Err("Invalid license. Please subscribe or enter a valid license key.".to_string()),
+        "expired" => Err(ExportDoctorError::LicenseExpired),
+        _ => Err(ExportDoctorError::LicenseInvalid),
     }
 }
 
 // ── Tauri Commands ──
 
 #[tauri::command]
-pub async fn get_license_status(app: tauri::AppHandle) -> Result<LicenseData, String> {
+pub async fn get_license_status(app: tauri::AppHandle) -> Result<LicenseData, ExportDoctorError> {
     let (mut data, found_in_store) = read_license(&app);
 
     // First launch: persist the initial trial so trial_started_at is stable
@@ -162,12 +185,12 @@ pub async fn get_license_status(app: tauri::AppHandle) -> Result<LicenseData, St
 }
 
 #[tauri::command]
-pub async fn validate_license_online(app: tauri::AppHandle) -> Result<LicenseData, String> {
+pub async fn validate_license_online(app: tauri::AppHandle) -> Result<LicenseData, ExportDoctorError> {
     let (mut data, _) = read_license(&app);
 
     let key = match &data.license_key {
         Some(k) => k.clone(),
-        None => return Err("No license key stored".to_string()),
+        None => return Err(ExportDoctorError::NoLicenseKey),
     };
 
     let machine_id = get_machine_id();
@@ -177,16 +200,16 @@ pub async fn validate_license_online(app: tauri::AppHandle) -> Result<LicenseDat
         .json(&serde_json::json!({ "licenseKey": key, "machineId": machine_id }))
         .send()
         .await
-        .map_err(|e| format!("Network error: {}", e))?;
+        .map_err(|e| ExportDoctorError::Network { detail: e.to_string() })?;
 
     if !resp.status().is_success() {
-        return Err(format!("Server error: {}", resp.status()));
+        return Err(ExportDoctorError::Server { detail: resp.status().to_string() });
     }
 
     let result: ValidateApiResponse = resp
         .json()
         .await
-        .map_err(|e| format!("Parse error: {}", e))?;
+        .map_err(|e| ExportDoctorError::Server { detail: format!("unexpected response: {}", e) })?;
 
     data.last_online_check = now_unix();
     data.expires_at = result.expires_at;
@@ -203,7 +226,7 @@ pub async fn validate_license_online(app: tauri::AppHandle) -> Result<LicenseDat
 }
 
 #[tauri::command]
-pub async fn start_checkout(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn start_checkout(app: tauri::AppHandle) -> Result<String, ExportDoctorError> {
     let machine_id = get_machine_id();
     let client = reqwest::Client::new();
     let resp = client
@@ -211,16 +234,16 @@ pub async fn start_checkout(app: tauri::AppHandle) -> Result<String, String> {
         .json(&serde_json::json!({ "machineId": machine_id }))
         .send()
         .await
-        .map_err(|e| format!("Network error: {}", e))?;
+        .map_err(|e| ExportDoctorError::Network { detail: e.to_string() })?;
 
     if !resp.status().is_success() {
-        return Err(format!("Server error: {}", resp.status()));
+        return Err(ExportDoctorError::Server { detail: resp.status().to_string() });
     }
 
     let result: CheckoutApiResponse = resp
         .json()
         .await
-        .map_err(|e| format!("Parse error: {}", e))?;
+        .map_err(|e| ExportDoctorError::Server { detail: format!("unexpected response: {}", e) })?;
 
     // Save the license key locally (status pending until webhook fires)
     let (mut data, _) = read_license(&app);
@@ -229,7 +252,7 @@ pub async fn start_checkout(app: tauri::AppHandle) -> Result<String, String> {
 
     // Open checkout URL in default browser
     app.opener().open_url(&result.url, None::<&str>)
-        .map_err(|e| format!("Failed to open browser: {}", e))?;
+        .map_err(|e| ExportDoctorError::OpenUrlFailed { detail: e.to_string() })?;
 
     Ok(result.license_key)
 }
@@ -238,7 +261,7 @@ pub async fn start_checkout(app: tauri::AppHandle) -> Result<String, String> {
 pub async fn activate_license(
     app: tauri::AppHandle,
     license_key: String,
-) -> Result<LicenseData, String> {
+) -> Result<LicenseData, ExportDoctorError> {
     let (mut data, _) = read_license(&app);
     data.license_key = Some(license_key);
     save_license(&app, &data);
@@ -248,12 +271,12 @@ pub async fn activate_license(
 }
 
 #[tauri::command]
-pub async fn open_manage_portal(app: tauri::AppHandle) -> Result<(), String> {
+pub async fn open_manage_portal(app: tauri::AppHandle) -> Result<(), ExportDoctorError> {
     let (data, _) = read_license(&app);
 
     let key = match &data.license_key {
         Some(k) => k.clone(),
-        None => return Err("No license key".to_string()),
+        None => return Err(ExportDoctorError::NoLicenseKey),
     };
 
     let client = reqwest::Client::new();
@@ -261,25 +284,25 @@ pub async fn open_manage_portal(app: tauri::AppHandle) -> Result<(), String> {
         .get(format!("{}/manage?licenseKey={}", API_BASE, key))
         .send()
         .await
-        .map_err(|e| format!("Network error: {}", e))?;
+        .map_err(|e| ExportDoctorError::Network { detail: e.to_string() })?;
 
     if !resp.status().is_success() {
-        return Err(format!("Server error: {}", resp.status()));
+        return Err(ExportDoctorError::Server { detail: resp.status().to_string() });
     }
 
     let result: ManageApiResponse = resp
         .json()
         .await
-        .map_err(|e| format!("Parse error: {}", e))?;
+        .map_err(|e| ExportDoctorError::Server { detail: format!("unexpected response: {}", e) })?;
 
     app.opener().open_url(&result.url, None::<&str>)
-        .map_err(|e| format!("Failed to open browser: {}", e))?;
+        .map_err(|e| ExportDoctorError::OpenUrlFailed { detail: e.to_string() })?;
 
     Ok(())
 }
 
 #[tauri::command]
-pub async fn deactivate_license(app: tauri::AppHandle) -> Result<(), String> {
+pub async fn deactivate_license(app: tauri::AppHandle) -> Result<(), ExportDoctorError> {
     let (mut data, _) = read_license(&app);
     data.license_key = None;
     data.status = "expired".to_string();
@@ -0,0 +1,120 @@
+//! Loudness measurement (EBU R128 / ITU-R BS.1770) using ffmpeg's `ebur128`
+//! filter. ffprobe can't decode audio, so this needs an ffmpeg binary next to
+//! the app or on PATH. Decoding the whole track is slow, so callers only
+//! measure when a selected preset checks loudness.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ExportDoctorError;
+use crate::{last_line, run_ffmpeg};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Loudness {
+    /// Integrated loudness in LUFS (= LKFS).
+    pub integrated_lufs: Option<f64>,
+    /// Loudness range in LU.
+    pub loudness_range_lu: Option<f64>,
+    /// Maximum true peak across channels in dBTP. `None` for digital silence.
+    pub true_peak_dbtp: Option<f64>,
+}
+
+/// Measure the first audio stream of `path`. Blocks until ffmpeg has decoded
+/// the whole track.
+pub fn measure(path: &str) -> Result<Loudness, ExportDoctorError> {
+    let args = [
+        "-hide_banner",
+        "-nostats",
+        "-i",
+        path,
+        "-map",
+        "0:a:0",
+        "-af",
+        "ebur128=peak=true",
+        "-f",
+        "null",
+        "-",
+    ];
+
+    let stderr = run_ffmpeg(&args)?;
+    parse_summary(&stderr).ok_or_else(|| ExportDoctorError::LoudnessFailed {
+        detail: last_line(&stderr)
+            .unwrap_or("no summary in ffmpeg output")
+            .to_string(),
+    })
+}
+
+/// Read the filter's final "Summary:" block from ffmpeg's stderr:
+///
+/// ```text
+///   Integrated loudness:
+///     I:         -23.0 LUFS
+///   Loudness range:
+///     LRA:         5.2 LU
+///   True peak:
+///     Peak:       -1.5 dBFS
+/// ```
+fn parse_summary(stderr: &str) -> Option<Loudness> {
+    let (_, summary) = stderr.rsplit_once("Summary:")?;
+    let value = |label: &str| {
+        summary
+            .lines()
+            .map(str::trim)
+            .find_map(|line| line.strip_prefix(label))
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|v| v.is_finite())
+    };
+    let loudness = Loudness {
+        integrated_lufs: value("I:"),
+        loudness_range_lu: value("LRA:"),
+        true_peak_dbtp: value("Peak:"),
+    };
+    loudness.integrated_lufs.map(|_| loudness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STDERR: &str = "\
+[Parsed_ebur128_0 @ 0x7f8] t: 59.9  TARGET:-23 LUFS  M: -22.1 S: -23.4  I: -23.0 LUFS  LRA:  5.2 LU  FTPK: -2.1 dBFS
+[Parsed_ebur128_0 @ 0x7f8] Summary:
+
+  Integrated loudness:
+    I:         -23.1 LUFS
+    Threshold: -33.2 LUFS
+
+  Loudness range:
+    LRA:         5.4 LU
+    Threshold: -43.2 LUFS
+    LRA low:   -27.1 LUFS
+    LRA high:  -21.7 LUFS
+
+  True peak:
+    Peak:       -1.6 dBFS
+";
+
+    #[test]
+    fn reads_the_summary_block() {
+        let loudness = parse_summary(STDERR).unwrap();
+        assert_eq!(loudness.integrated_lufs, Some(-23.1));
+        assert_eq!(loudness.loudness_range_lu, Some(5.4));
+        assert_eq!(loudness.true_peak_dbtp, Some(-1.6));
+    }
+
+    #[test]
+    fn silence_has_no_true_peak() {
+        let stderr = STDERR.replace("-1.6 dBFS", "-inf dBFS");
+        let loudness = parse_summary(&stderr).unwrap();
+        assert_eq!(loudness.integrated_lufs, Some(-23.1));
+        assert_eq!(loudness.true_peak_dbtp, None);
+    }
+
+    #[test]
+    fn needs_an_integrated_value() {
+        assert!(parse_summary("no summary here").is_none());
+        let stderr = STDERR.replace("-23.1 LUFS", "nan LUFS");
+        assert!(parse_summary(&stderr).is_none());
+    }
+}
@@ -1,5 +1,32 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // `export-doctor check ...` runs headless and never opens a window.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if export_doctor_lib::cli::is_cli_invocation(&args) {
+        attach_parent_console();
+        std::process::exit(export_doctor_lib::cli::run(&args));
+    }
+
     export_doctor_lib::run()
 }
+
+/// Release builds use the GUI subsystem, so Windows starts them without a
+/// console and CLI output would go nowhere. Borrow the console of the shell
+/// that launched us; without one (e.g. started from Explorer) this is a no-op.
+#[cfg(windows)]
+fn attach_parent_console() {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    // SAFETY: AttachConsole takes a plain process id and has no other
+    // preconditions; failure is reported through the return value.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
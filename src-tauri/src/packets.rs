@@ -0,0 +1,84 @@
+//! One ffprobe pass over the packets of the analyzed video stream
+//! (`video_stream_index`, so never cover art), shared by the frame timing
+//! and GOP analyses. Reading packets doesn't decode anything,
+//! so this is far cheaper than `-show_frames`.
+
+use crate::{gop, timing, ExtendedMetadata};
+
+/// A video packet in decode order.
+#[derive(Debug, Clone, Copy)]
+pub struct Packet {
+    pub pts: Option<f64>,
+    pub dts: Option<f64>,
+    pub key: bool,
+}
+
+impl Packet {
+    /// Presentation time, falling back to the decode time.
+    pub fn time(&self) -> Option<f64> {
+        self.pts.or(self.dts)
+    }
+}
+
+/// Arguments for an ffprobe run that lists the packets of the stream at
+/// container index `stream`, one `pts,dts,flags` line each.
+pub fn probe_args<'a>(path: &'a str, stream: &'a str) -> [&'a str; 9] {
+    [
+        "-v",
+        "quiet",
+        "-select_streams",
+        stream,
+        "-show_entries",
+        "packet=pts_time,dts_time,flags",
+        "-of",
+        "csv=p=0",
+        path,
+    ]
+}
+
+pub fn parse(csv: &str) -> Vec<Packet> {
+    csv.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut fields = line.split(',').map(str::trim);
+            let mut time = || fields.next().and_then(|f| f.parse::<f64>().ok());
+            let pts = time();
+            let dts = time();
+            let key = fields.next().is_some_and(|flags| flags.starts_with('K'));
+            Packet { pts, dts, key }
+        })
+        .collect()
+}
+
+/// Fill in the packet-derived fields of `meta` from `probe_args` output.
+pub fn apply(meta: &mut ExtendedMetadata, csv: &str) {
+    let packets = parse(csv);
+    meta.frame_timing = timing::analyze(&packets);
+    meta.gop = gop::analyze(&packets);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csv_lines() {
+        let csv = "0.000000,-0.066667,K__\n0.133333,-0.033333,___\nN/A,0.000000,__D\n\n";
+        let packets = parse(csv);
+        assert_eq!(packets.len(), 3);
+        assert_eq!(packets[0].pts, Some(0.0));
+        assert_eq!(packets[0].dts, Some(-0.066667));
+        assert!(packets[0].key);
+        assert!(!packets[1].key);
+        assert_eq!(packets[2].pts, None);
+        assert_eq!(packets[2].time(), Some(0.0));
+    }
+
+    #[test]
+    fn tolerates_short_lines() {
+        let packets = parse("1.5\n");
+        assert_eq!(packets[0].pts, Some(1.5));
+        assert_eq!(packets[0].dts, None);
+        assert!(!packets[0].key);
+    }
+}
@@ -0,0 +1,803 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+use tauri_plugin_store::StoreExt;
+
+use crate::error::ExportDoctorError;
+
+pub(crate) const STORE_FILE: &str = "presets.json";
+pub(crate) const STORE_KEY: &str = "custom";
+const CATEGORIES: &[&str] = &["social", "images", "broadcast", "cinema"];
+
+// ── Schema ──
+//
+// A preset is a list of checks. Each check reads one metric from the analyzed
+// file and walks its rules top to bottom; the first rule whose `when`
+// condition holds decides the check. The last rule of every check must be
+// unconditional so a check always produces a verdict, and it must be the only
+// unconditional one since nothing after it could fire.
+//
+// Keys are camelCase throughout; metric names (values, and `{metric}`
+// placeholders in text) are snake_case.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Preset {
+    pub id: String,
+    pub name: String,
+    pub title: String,
+    pub category: String, // social | images | broadcast | cinema
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub recommended: bool,
+    /// Set on user-defined presets; never written to exported files.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub custom: bool,
+    #[serde(default)]
+    pub specs: Vec<SpecItem>,
+    pub checks: Vec<CheckDef>,
+}
+
+impl Preset {
+    /// True if any check reads a metric matching `pred`, directly, from a
+    /// condition, or through a `{metric}` display placeholder.
+    pub fn uses_metric(&self, pred: impl Fn(Metric) -> bool) -> bool {
+        fn in_condition(cond: &Condition, pred: &dyn Fn(Metric) -> bool) -> bool {
+            cond.metric.is_some_and(pred)
+                || cond.all.iter().flatten().any(|c| in_condition(c, pred))
+                || cond.any.iter().flatten().any(|c| in_condition(c, pred))
+        }
+        let in_template = |text: &str| {
+            Metric::ALL
+                .iter()
+                .any(|m| pred(*m) && text.contains(&format!("{{{}}}", m.name())))
+        };
+        self.checks.iter().any(|check| {
+            pred(check.metric)
+                || check.display.as_deref().is_some_and(in_template)
+                || check.rules.iter().any(|rule| {
+                    rule.when.as_ref().is_some_and(|c| in_condition(c, &pred))
+                        || [Some(&rule.reason), rule.note.as_ref(), rule.value.as_ref()]
+                            .into_iter()
+                            .flatten()
+                            .any(|t| in_template(t))
+                })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpecItem {
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CheckDef {
+    /// Label shown in the compliance table.
+    pub field: String,
+    pub metric: Metric,
+    /// Decimal places used when displaying a numeric metric.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<usize>,
+    /// Display template for the value column, e.g. "{color_primaries} / {color_transfer}".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+    pub rules: Vec<RuleDef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RuleDef {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+    #[serde(default = "default_true")]
+    pub ok: bool,
+    pub expected: String,
+    pub reason: String,
+    /// Extra recommendation added to the result's `reasons` when this rule fires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Overrides the displayed value for this outcome.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// All present constraints must hold. Text comparisons use the metric's
+/// normalized lowercase form; numeric ones fail when the metric is unknown.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Condition {
+    /// Test this metric instead of the check's own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric: Option<Metric>,
+    #[serde(default, rename = "in", skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eq: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub above: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub below: Option<f64>,
+    /// Matches when the value is within `tolerance` of any listed value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub near: Option<Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<f64>,
+    /// Exact width x height pairs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Vec<[u32; 2]>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size: Option<[u32; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub missing: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all: Option<Vec<Condition>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub any: Option<Vec<Condition>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    Container,
+    VideoCodec,
+    ImageFormat,
+    Profile,
+    Level,
+    PixFmt,
+    Resolution,
+    CodedResolution,
+    Aspect,
+    Rotation,
+    SampleAspectRatio,
+    FieldOrder,
+    ColorPrimaries,
+    ColorTransfer,
+    ColorSpace,
+    Fps,
+    Duration,
+    Bitrate,
+    AudioCodec,
+    AudioSampleRate,
+    AudioChannels,
+    BitDepth,
+    VideoStreams,
+    AudioStreams,
+    SubtitleStreams,
+    DataStreams,
+    AttachedPics,
+    IntegratedLoudness,
+    LoudnessRange,
+    TruePeak,
+    FrameRateMode,
+    MeasuredFps,
+    DroppedFrames,
+    DuplicatedFrames,
+    GopLength,
+    GopDuration,
+    GopStructure,
+    ConsecutiveBFrames,
+    Faststart,
+    Fragmented,
+    MajorBrand,
+    ComplexEditLists,
+    HdrFormat,
+    MasteringDisplay,
+    MasteringPeak,
+    MaxCll,
+    MaxFall,
+    DolbyVisionProfile,
+    StartTimecode,
+    DropFrame,
+    TimecodeRate,
+    ClosedCaptions,
+    SubtitleCodecs,
+    SubtitleLanguages,
+    Title,
+    Encoder,
+    CreationTime,
+    FormatTags,
+    Chapters,
+    BlackSegments,
+    FreezeSegments,
+    SilenceSegments,
+    ActivePicture,
+    ActiveAspect,
+    Letterbox,
+    Pillarbox,
+    DetectedScan,
+    InterlacedFrames,
+}
+
+impl Metric {
+    pub const ALL: &'static [Metric] = &[
+        Metric::Container,
+        Metric::VideoCodec,
+        Metric::ImageFormat,
+        Metric::Profile,
+        Metric::Level,
+        Metric::PixFmt,
+        Metric::Resolution,
+        Metric::CodedResolution,
+        Metric::Aspect,
+        Metric::Rotation,
+        Metric::SampleAspectRatio,
+        Metric::FieldOrder,
+        Metric::ColorPrimaries,
+        Metric::ColorTransfer,
+        Metric::ColorSpace,
+        Metric::Fps,
+        Metric::Duration,
+        Metric::Bitrate,
+        Metric::AudioCodec,
+        Metric::AudioSampleRate,
+        Metric::AudioChannels,
+        Metric::BitDepth,
+        Metric::VideoStreams,
+        Metric::AudioStreams,
+        Metric::SubtitleStreams,
+        Metric::DataStreams,
+        Metric::AttachedPics,
+        Metric::IntegratedLoudness,
+        Metric::LoudnessRange,
+        Metric::TruePeak,
+        Metric::FrameRateMode,
+        Metric::MeasuredFps,
+        Metric::DroppedFrames,
+        Metric::DuplicatedFrames,
+        Metric::GopLength,
+        Metric::GopDuration,
+        Metric::GopStructure,
+        Metric::ConsecutiveBFrames,
+        Metric::Faststart,
+        Metric::Fragmented,
+        Metric::MajorBrand,
+        Metric::ComplexEditLists,
+        Metric::HdrFormat,
+        Metric::MasteringDisplay,
+        Metric::MasteringPeak,
+        Metric::MaxCll,
+        Metric::MaxFall,
+        Metric::DolbyVisionProfile,
+        Metric::StartTimecode,
+        Metric::DropFrame,
+        Metric::TimecodeRate,
+        Metric::ClosedCaptions,
+        Metric::SubtitleCodecs,
+        Metric::SubtitleLanguages,
+        Metric::Title,
+        Metric::Encoder,
+        Metric::CreationTime,
+        Metric::FormatTags,
+        Metric::Chapters,
+        Metric::BlackSegments,
+        Metric::FreezeSegments,
+        Metric::SilenceSegments,
+        Metric::ActivePicture,
+        Metric::ActiveAspect,
+        Metric::Letterbox,
+        Metric::Pillarbox,
+        Metric::DetectedScan,
+        Metric::InterlacedFrames,
+    ];
+
+    /// Metrics that need the audio decoded rather than just probed.
+    pub fn is_loudness(self) -> bool {
+        matches!(
+            self,
+            Metric::IntegratedLoudness | Metric::LoudnessRange | Metric::TruePeak
+        )
+    }
+
+    /// Metrics that need the packet timestamps rather than the stream header.
+    pub fn is_frame_timing(self) -> bool {
+        matches!(
+            self,
+            Metric::FrameRateMode
+                | Metric::MeasuredFps
+                | Metric::DroppedFrames
+                | Metric::DuplicatedFrames
+        )
+    }
+
+    /// Metrics read from the video packets (frame timing and GOP structure).
+    pub fn needs_packets(self) -> bool {
+        self.is_frame_timing()
+            || matches!(
+                self,
+                Metric::GopLength
+                    | Metric::GopDuration
+                    | Metric::GopStructure
+                    | Metric::ConsecutiveBFrames
+            )
+    }
+
+    /// Metrics from the deep QC scan, which decodes the whole file.
+    pub fn is_qc(self) -> bool {
+        matches!(
+            self,
+            Metric::BlackSegments | Metric::FreezeSegments | Metric::SilenceSegments
+        )
+    }
+
+    /// Metrics from the sampled `cropdetect` pass.
+    pub fn is_crop(self) -> bool {
+        matches!(
+            self,
+            Metric::ActivePicture | Metric::ActiveAspect | Metric::Letterbox | Metric::Pillarbox
+        )
+    }
+
+    /// Metrics from the `idet` pass, which judges scan type from the picture.
+    pub fn is_interlace(self) -> bool {
+        matches!(self, Metric::DetectedScan | Metric::InterlacedFrames)
+    }
+
+    /// HDR metrics, which may need the first frame's side data.
+    pub fn is_hdr(self) -> bool {
+        matches!(
+            self,
+            Metric::HdrFormat
+                | Metric::MasteringDisplay
+                | Metric::MasteringPeak
+                | Metric::MaxCll
+                | Metric::MaxFall
+                | Metric::DolbyVisionProfile
+        )
+    }
+
+    /// The snake_case name used in preset files and display templates.
+    pub fn name(self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_default()
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// ── Built-ins ──
+
+const BUILTIN_SOURCES: &[(&str, &str)] = &[
+    ("ig_reels", include_str!("../presets/ig_reels.toml")),
+    ("ig_post", include_str!("../presets/ig_post.toml")),
+    ("ig_story", include_str!("../presets/ig_story.toml")),
+    ("tiktok", include_str!("../presets/tiktok.toml")),
+    ("yt_shorts", include_str!("../presets/yt_shorts.toml")),
+    ("youtube", include_str!("../presets/youtube.toml")),
+    ("mxf_pal", include_str!("../presets/mxf_pal.toml")),
+    ("mxf_ntsc", include_str!("../presets/mxf_ntsc.toml")),
+    ("cinema", include_str!("../presets/cinema.toml")),
+    ("dcp", include_str!("../presets/dcp.toml")),
+    (
+        "ig_post_image",
+        include_str!("../presets/ig_post_image.toml"),
+    ),
+    (
+        "ig_story_image",
+        include_str!("../presets/ig_story_image.toml"),
+    ),
+    (
+        "ig_reels_cover",
+        include_str!("../presets/ig_reels_cover.toml"),
+    ),
+    ("fb_cover", include_str!("../presets/fb_cover.toml")),
+    (
+        "linkedin_banner",
+        include_str!("../presets/linkedin_banner.toml"),
+    ),
+];
+
+/// Built-in presets in the order the UI lists them. The embedded files are
+/// part of the binary, so a malformed one is a build defect and panics.
+pub fn builtin_presets() -> &'static [Preset] {
+    static PRESETS: OnceLock<Vec<Preset>> = OnceLock::new();
+    PRESETS.get_or_init(|| {
+        BUILTIN_SOURCES
+            .iter()
+            .map(|(id, source)| {
+                let preset = parse_preset(source)
+                    .unwrap_or_else(|e| panic!("built-in preset {} is invalid: {}", id, e));
+                assert_eq!(&preset.id, id, "built-in preset id mismatch");
+                assert!(!preset.custom, "built-in preset {} is marked custom", id);
+                preset
+            })
+            .collect()
+    })
+}
+
+/// Parse and validate a preset from TOML text.
+pub fn parse_preset(source: &str) -> Result<Preset, ExportDoctorError> {
+    let preset: Preset = toml::from_str(source)
+        .map_err(|e| ExportDoctorError::invalid_preset(format!("Invalid preset file: {}", e)))?;
+    validate_preset(&preset)?;
+    Ok(preset)
+}
+
+/// Parse a preset file, choosing JSON or TOML by extension.
+/// Parse errors are prefixed with the file path.
+pub fn read_preset_file(path: &Path) -> Result<Preset, ExportDoctorError> {
+    let source = std::fs::read_to_string(path).map_err(|e| ExportDoctorError::FileUnreadable {
+        path: path.display().to_string(),
+        detail: e.to_string(),
+    })?;
+    let preset = if is_json(path) {
+        serde_json::from_str::<Preset>(&source)
+            .map_err(|e| ExportDoctorError::invalid_preset(format!("Invalid preset file: {}", e)))
+            .and_then(|preset| validate_preset(&preset).map(|_| preset))
+    } else {
+        parse_preset(&source)
+    };
+    preset.map_err(|e| ExportDoctorError::invalid_preset(format!("{}: {}", path.display(), e)))
+}
+
+pub fn validate_preset(preset: &Preset) -> Result<(), ExportDoctorError> {
+    let id_ok = !preset.id.is_empty()
+        && preset
+            .id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !id_ok {
+        return Err(ExportDoctorError::invalid_preset(format!(
+            "Preset id \"{}\" must be non-empty lowercase letters, digits or underscores",
+            preset.id
+        )));
+    }
+    if preset.name.trim().is_empty() {
+        return Err(ExportDoctorError::invalid_preset(format!(
+            "Preset {} has no name",
+            preset.id
+        )));
+    }
+    if !CATEGORIES.contains(&preset.category.as_str()) {
+        return Err(ExportDoctorError::invalid_preset(format!(
+            "Preset {} has unknown category \"{}\" (expected one of: {})",
+            preset.id,
+            preset.category,
+            CATEGORIES.join(", ")
+        )));
+    }
+    if preset.checks.is_empty() {
+        return Err(ExportDoctorError::invalid_preset(format!(
+            "Preset {} has no checks",
+            preset.id
+        )));
+    }
+    for check in &preset.checks {
+        match check.rules.last() {
+            None => {
+                return Err(ExportDoctorError::invalid_preset(format!(
+                    "Check \"{}\" has no rules",
+                    check.field
+                )))
+            }
+            Some(last) if last.when.is_some() => {
+                return Err(ExportDoctorError::invalid_preset(format!(
+                    "Check \"{}\" must end with a rule without a `when` condition",
+                    check.field
+                )))
+            }
+            _ => {}
+        }
+        if let Some(index) = check.rules[..check.rules.len() - 1]
+            .iter()
+            .position(|rule| rule.when.is_none())
+        {
+            return Err(ExportDoctorError::invalid_preset(format!(
+                "Check \"{}\": rule {} has no `when` condition, so the rules after it can never apply",
+                check.field,
+                index + 1
+            )));
+        }
+        let texts = check
+            .display
+            .iter()
+            .chain(check.rules.iter().flat_map(|rule| {
+                [Some(&rule.reason), rule.note.as_ref(), rule.value.as_ref()]
+                    .into_iter()
+                    .flatten()
+            }));
+        for text in texts {
+            if let Some(name) = unknown_placeholder(text) {
+                return Err(ExportDoctorError::invalid_preset(format!(
+                    "Check \"{}\" refers to unknown metric {{{}}}",
+                    check.field, name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The first `{name}` in `text` that is neither a metric nor one of the
+/// check's own placeholders (`{value}`, `{norm}`).
+fn unknown_placeholder(text: &str) -> Option<&str> {
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        let close = after.find('}')?;
+        let name = &after[..close];
+        let known =
+            name == "value" || name == "norm" || Metric::ALL.iter().any(|m| m.name() == name);
+        if !known {
+            return Some(name);
+        }
+        rest = &after[close + 1..];
+    }
+    None
+}
+
+// ── Custom presets ──
+
+/// User-defined presets, in creation order. Entries that no longer validate
+/// (e.g. after a schema change) are skipped rather than failing the list.
+pub fn read_custom_presets(app: &tauri::AppHandle) -> Vec<Preset> {
+    let store = app.store(STORE_FILE).unwrap_or_else(|_| {
+        app.store_builder(STORE_FILE)
+            .build()
+            .expect("failed to build store")
+    });
+    store
+        .get(STORE_KEY)
+        .map(custom_presets_from_value)
+        .unwrap_or_default()
+}
+
+/// Decode the stored custom preset list. Also used by the CLI, which reads
+/// the store file without a Tauri runtime.
+pub fn custom_presets_from_value(val: serde_json::Value) -> Vec<Preset> {
+    let entries: Vec<serde_json::Value> = serde_json::from_value(val).unwrap_or_default();
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let preset = serde_json::from_value::<Preset>(entry)
+                .map_err(ExportDoctorError::invalid_preset)
+                .and_then(|p| validate_preset(&p).map(|_| p));
+            match preset {
+                Ok(mut p) => {
+                    p.custom = true;
+                    Some(p)
+                }
+                Err(e) => {
+                    eprintln!("[export-doctor] Skipping invalid custom preset: {}", e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn save_custom_presets(
+    app: &tauri::AppHandle,
+    presets: &[Preset],
+) -> Result<(), ExportDoctorError> {
+    let store = app.store(STORE_FILE).unwrap_or_else(|_| {
+        app.store_builder(STORE_FILE)
+            .build()
+            .expect("failed to build store")
+    });
+    let val = serde_json::to_value(presets).map_err(ExportDoctorError::storage)?;
+    store.set(STORE_KEY, val);
+    store.save().map_err(ExportDoctorError::storage)
+}
+
+/// Built-ins followed by the user's custom presets.
+pub fn catalog(app: &tauri::AppHandle) -> Vec<Preset> {
+    let mut all = builtin_presets().to_vec();
+    all.extend(read_custom_presets(app));
+    all
+}
+
+/// Validate a custom preset and make sure it doesn't shadow a built-in.
+fn check_custom(mut preset: Preset) -> Result<Preset, ExportDoctorError> {
+    validate_preset(&preset)?;
+    if builtin_presets().iter().any(|p| p.id == preset.id) {
+        return Err(ExportDoctorError::PresetConflict { id: preset.id });
+    }
+    preset.custom = true;
+    Ok(preset)
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"))
+}
+
+// ── Tauri Commands ──
+
+#[tauri::command]
+pub async fn list_presets(app: tauri::AppHandle) -> Result<Vec<Preset>, ExportDoctorError> {
+    Ok(catalog(&app))
+}
+
+#[tauri::command]
+pub async fn create_custom_preset(
+    app: tauri::AppHandle,
+    preset: Preset,
+) -> Result<Preset, ExportDoctorError> {
+    let preset = check_custom(preset)?;
+    let mut presets = read_custom_presets(&app);
+    if presets.iter().any(|p| p.id == preset.id) {
+        return Err(ExportDoctorError::PresetConflict { id: preset.id });
+    }
+    presets.push(preset.clone());
+    save_custom_presets(&app, &presets)?;
+    Ok(preset)
+}
+
+/// Replace the custom preset `id`. The new definition may carry a new id as
+/// long as it doesn't collide with another preset.
+#[tauri::command]
+pub async fn update_custom_preset(
+    app: tauri::AppHandle,
+    id: String,
+    preset: Preset,
+) -> Result<Preset, ExportDoctorError> {
+    let preset = check_custom(preset)?;
+    let mut presets = read_custom_presets(&app);
+    let index = presets
+        .iter()
+        .position(|p| p.id == id)
+        .ok_or_else(|| ExportDoctorError::UnknownPreset { id: id.clone() })?;
+    if preset.id != id && presets.iter().any(|p| p.id == preset.id) {
+        return Err(ExportDoctorError::PresetConflict { id: preset.id });
+    }
+    presets[index] = preset.clone();
+    save_custom_presets(&app, &presets)?;
+    Ok(preset)
+}
+
+#[tauri::command]
+pub async fn delete_custom_preset(
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), ExportDoctorError> {
+    let mut presets = read_custom_presets(&app);
+    let before = presets.len();
+    presets.retain(|p| p.id != id);
+    if presets.len() == before {
+        return Err(ExportDoctorError::UnknownPreset { id });
+    }
+    save_custom_presets(&app, &presets)
+}
+
+/// Import preset files (TOML, or JSON by extension). A file whose id matches
+/// an existing custom preset replaces it. Nothing is saved if any file fails.
+#[tauri::command]
+pub async fn import_custom_presets(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+) -> Result<Vec<Preset>, ExportDoctorError> {
+    let imported = paths
+        .iter()
+        .map(|path| check_custom(read_preset_file(Path::new(path))?))
+        .collect::<Result<Vec<_>, ExportDoctorError>>()?;
+
+    let mut presets = read_custom_presets(&app);
+    for preset in &imported {
+        match presets.iter_mut().find(|p| p.id == preset.id) {
+            Some(existing) => *existing = preset.clone(),
+            None => presets.push(preset.clone()),
+        }
+    }
+    save_custom_presets(&app, &presets)?;
+    Ok(imported)
+}
+
+/// Write a preset (built-in or custom) to `path` as TOML, or JSON by extension.
+#[tauri::command]
+pub async fn export_preset(
+    app: tauri::AppHandle,
+    id: String,
+    path: String,
+) -> Result<(), ExportDoctorError> {
+    let mut preset = catalog(&app)
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| ExportDoctorError::UnknownPreset { id: id.clone() })?;
+    preset.custom = false;
+
+    let write_failed = |detail: String| ExportDoctorError::FileWriteFailed {
+        path: path.clone(),
+        detail,
+    };
+    let contents = if is_json(Path::new(&path)) {
+        serde_json::to_string_pretty(&preset).map_err(|e| write_failed(e.to_string()))?
+    } else {
+        toml::to_string_pretty(&preset).map_err(|e| write_failed(e.to_string()))?
+    };
+    std::fs::write(&path, contents).map_err(|e| write_failed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = r#"
+id = "test"
+name = "Test"
+title = "Test"
+category = "social"
+
+[[checks]]
+field = "Resolution"
+metric = "resolution"
+rules = [
+  { when = { minSize = [1080, 1920] }, expected = ">= 1080x1920", reason = "OK" },
+  { ok = false, expected = ">= 1080x1920", reason = "Too small: {resolution}" },
+]
+"#;
+
+    #[test]
+    fn builtins_parse_and_round_trip() {
+        for preset in builtin_presets() {
+            let toml = toml::to_string_pretty(preset).unwrap();
+            let back = parse_preset(&toml).unwrap_or_else(|e| panic!("{}: {}", preset.id, e));
+            assert_eq!(
+                serde_json::to_value(&back).unwrap(),
+                serde_json::to_value(preset).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn condition_keys_are_camel_case() {
+        let preset = parse_preset(MINIMAL).unwrap();
+        let when = preset.checks[0].rules[0].when.as_ref().unwrap();
+        assert_eq!(when.min_size, Some([1080, 1920]));
+
+        let snake = MINIMAL.replace("minSize", "min_size");
+        assert!(parse_preset(&snake).is_err());
+    }
+
+    #[test]
+    fn rejects_rules_after_an_unconditional_rule() {
+        let source = MINIMAL.replace(
+            r#"{ when = { minSize = [1080, 1920] }, "#,
+            r#"{ expected = "any", reason = "Always" },
+  { when = { minSize = [1080, 1920] }, "#,
+        );
+        let err = parse_preset(&source).unwrap_err().to_string();
+        assert!(err.contains("can never apply"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_a_conditional_last_rule() {
+        let source = MINIMAL.replace(
+            r#"{ ok = false, "#,
+            r#"{ when = { missing = true }, ok = false, "#,
+        );
+        assert!(parse_preset(&source).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_metrics() {
+        let source = MINIMAL.replace(r#"metric = "resolution""#, r#"metric = "resolutoin""#);
+        assert!(parse_preset(&source).is_err());
+
+        let source = MINIMAL.replace("{resolution}", "{resolutoin}");
+        let err = parse_preset(&source).unwrap_err().to_string();
+        assert!(err.contains("{resolutoin}"), "{}", err);
+    }
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(unknown_placeholder("{value} at {fps} fps ({norm})"), None);
+        assert_eq!(unknown_placeholder("plain text"), None);
+        assert_eq!(unknown_placeholder("{value} / {nope}"), Some("nope"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Deep QC pass: black gaps, frozen picture and silence, found with ffmpeg's
+//! `blackdetect`, `freezedetect` and `silencedetect` filters in a single
+//! decode. This reads every frame, so it only runs in deep-analysis mode or
+//! when a selected preset checks one of its metrics.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ExportDoctorError;
+use crate::{last_line, run_ffmpeg};
+
+/// Shortest black stretch worth reporting, in seconds.
+const BLACK_MIN_SEC: f64 = 0.5;
+/// A pixel counts as black below this luma share of the range.
+const BLACK_PIXEL_THRESHOLD: f64 = 0.10;
+/// Picture unchanged (within `FREEZE_NOISE`) for this long counts as frozen.
+const FREEZE_MIN_SEC: f64 = 2.0;
+const FREEZE_NOISE: &str = "-60dB";
+/// Audio below `SILENCE_NOISE` for this long counts as silent.
+const SILENCE_MIN_SEC: f64 = 2.0;
+const SILENCE_NOISE: &str = "-60dB";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QcKind {
+    Black,
+    Freeze,
+    Silence,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QcEvent {
+    pub kind: QcKind,
+    pub start_sec: f64,
+    pub end_sec: f64,
+    pub duration_sec: f64,
+}
+
+/// QC issues in timeline order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QcReport {
+    pub events: Vec<QcEvent>,
+}
+
+impl QcReport {
+    pub fn count(&self, kind: QcKind) -> usize {
+        self.events.iter().filter(|e| e.kind == kind).count()
+    }
+}
+
+/// Scan video stream `stream` (a container index, so cover art is never
+/// picked up) and, if `has_audio`, the first audio stream of `path`.
+/// `duration` closes events still open when the file ends.
+pub fn scan(
+    path: &str,
+    stream: u32,
+    has_audio: bool,
+    duration: f64,
+) -> Result<QcReport, ExportDoctorError> {
+    let mut graph = format!(
+        "[0:{}]blackdetect=d={}:pix_th={},freezedetect=n={}:d={}[v]",
+        stream, BLACK_MIN_SEC, BLACK_PIXEL_THRESHOLD, FREEZE_NOISE, FREEZE_MIN_SEC
+    );
+    if has_audio {
+        graph.push_str(&format!(
+            ";[0:a:0]silencedetect=n={}:d={}[a]",
+            SILENCE_NOISE, SILENCE_MIN_SEC
+        ));
+    }
+    let mut args = vec![
+        "-hide_banner",
+        "-nostats",
+        "-i",
+        path,
+        "-filter_complex",
+        &graph,
+        "-map",
+        "[v]",
+    ];
+    if has_audio {
+        args.extend(["-map", "[a]"]);
+    }
+    args.extend(["-f", "null", "-"]);
+
+    let stderr = run_ffmpeg(&args)?;
+    // ffmpeg prints its final "video:... audio:..." stats only after
+    // processing the whole input.
+    if !stderr.contains("video:") {
+        return Err(ExportDoctorError::QcFailed {
+            detail: last_line(&stderr)
+                .unwrap_or("no output from ffmpeg")
+                .to_string(),
+        });
+    }
+    Ok(QcReport {
+        events: parse_events(&stderr, duration),
+    })
+}
+
+fn parse_events(stderr: &str, duration: f64) -> Vec<QcEvent> {
+    let mut events = Vec::new();
+    let mut freeze_start = None;
+    let mut silence_start = None;
+
+    for line in stderr.lines() {
+        if line.contains("[blackdetect") {
+            if let (Some(start), Some(end)) =
+                (value(line, "black_start:"), value(line, "black_end:"))
+            {
+                events.push(event(QcKind::Black, start, end));
+            }
+        } else if line.contains("[freezedetect") {
+            if let Some(start) = value(line, "freeze_start:") {
+                freeze_start = Some(start);
+            } else if let Some(end) = value(line, "freeze_end:") {
+                if let Some(start) = freeze_start.take() {
+                    events.push(event(QcKind::Freeze, start, end));
+                }
+            }
+        } else if line.contains("[silencedetect") {
+            if let Some(start) = value(line, "silence_start:") {
+                silence_start = Some(start);
+            } else if let Some(end) = value(line, "silence_end:") {
+                if let Some(start) = silence_start.take() {
+                    events.push(event(QcKind::Silence, start, end));
+                }
+            }
+        }
+    }
+    // Freezes and silences that run to the end are never closed.
+    if let Some(start) = freeze_start {
+        events.push(event(QcKind::Freeze, start, duration.max(start)));
+    }
+    if let Some(start) = silence_start {
+        events.push(event(QcKind::Silence, start, duration.max(start)));
+    }
+
+    events.sort_by(|a, b| a.start_sec.total_cmp(&b.start_sec));
+    events
+}
+
+fn event(kind: QcKind, start_sec: f64, end_sec: f64) -> QcEvent {
+    QcEvent {
+        kind,
+        start_sec,
+        end_sec,
+        duration_sec: end_sec - start_sec,
+    }
+}
+
+/// The number following `key` on a filter log line.
+fn value(line: &str, key: &str) -> Option<f64> {
+    let (_, rest) = line.split_once(key)?;
+    rest.split_whitespace()
+        .next()?
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STDERR: &str = "\
+[blackdetect @ 0x6000] black_start:0 black_end:1.24 black_duration:1.24
+[freezedetect @ 0x6001] lavfi.freezedetect.freeze_start: 10.5
+[freezedetect @ 0x6001] lavfi.freezedetect.freeze_duration: 3
+[freezedetect @ 0x6001] lavfi.freezedetect.freeze_end: 13.5
+[silencedetect @ 0x6002] silence_start: 4.2
+[silencedetect @ 0x6002] silence_end: 7.7 | silence_duration: 3.5
+[freezedetect @ 0x6001] lavfi.freezedetect.freeze_start: 55
+[silencedetect @ 0x6002] silence_start: 58.25
+frame= 1500 fps=200 q=-0.0 Lsize=N/A time=00:01:00.00 bitrate=N/A speed=8x
+video:0kB audio:0kB subtitle:0kB other streams:0kB global headers:0kB
+";
+
+    #[test]
+    fn parses_events_in_timeline_order() {
+        let events = parse_events(STDERR, 60.0);
+        let summary: Vec<(QcKind, f64, f64)> = events
+            .iter()
+            .map(|e| (e.kind, e.start_sec, e.end_sec))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (QcKind::Black, 0.0, 1.24),
+                (QcKind::Silence, 4.2, 7.7),
+                (QcKind::Freeze, 10.5, 13.5),
+                (QcKind::Freeze, 55.0, 60.0),
+                (QcKind::Silence, 58.25, 60.0),
+            ]
+        );
+        assert!((events[1].duration_sec - 3.5).abs() < 1e-9);
+
+        let report = QcReport { events };
+        assert_eq!(report.count(QcKind::Freeze), 2);
+        assert_eq!(report.count(QcKind::Black), 1);
+    }
+
+    #[test]
+    fn open_events_close_no_earlier_than_they_start() {
+        let stderr = "[silencedetect @ 0x1] silence_start: 12";
+        let events = parse_events(stderr, 0.0);
+        assert_eq!(events[0].end_sec, 12.0);
+        assert_eq!(events[0].duration_sec, 0.0);
+    }
+
+    #[test]
+    fn ignores_unrelated_and_malformed_lines() {
+        let stderr = "\
+[blackdetect @ 0x1] black_start:nan black_end:2
+[freezedetect @ 0x1] lavfi.freezedetect.freeze_end: 4
+Stream #0:0: Video: h264, silence_start: 3
+";
+        assert!(parse_events(stderr, 10.0).is_empty());
+    }
+}
@@ -0,0 +1,1058 @@
+//! ffmpeg command lines that turn a FLAWED file into one a preset accepts.
+//!
+//! Targets come from the preset itself: for each check, the first passing
+//! rule whose conditions on *other* metrics hold for this file (so the Reels
+//! bitrate window follows the clip's duration) supplies the value to aim
+//! for. Only what failed is touched: a wrong container or missing fast start
+//! is a remux, and each stream is re-encoded only when one of its checks
+//! failed, or when its codec can't go into the target container (PCM audio
+//! moving from MOV to MP4, say).
+
+use serde::{Deserialize, Serialize};
+
+use crate::presets::{CheckDef, Condition, Metric, Preset};
+use crate::rules::holds;
+use crate::{ExtendedMetadata, CAPTION_CODECS};
+
+/// Sample rates tried, in order of preference, against a preset's bounds.
+const SAMPLE_RATES: [f64; 3] = [48000.0, 44100.0, 96000.0];
+const AUDIO_BITRATE: &str = "256k";
+/// Quality used when the preset sets no bitrate window.
+const DEFAULT_CRF: &str = "18";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Remediation {
+    /// ffmpeg arguments between the input and the output file.
+    pub args: Vec<String>,
+    /// Extension for the output file, e.g. "mp4".
+    pub extension: String,
+    /// Streams are copied; only the container is rewritten.
+    pub remux_only: bool,
+    /// Failed checks this command does not address (duration, QC issues,
+    /// HDR that needs tone mapping...), and tracks the output can't carry.
+    pub unresolved: Vec<String>,
+}
+
+impl Remediation {
+    /// Full ffmpeg argument list, without the binary.
+    pub fn command_line(&self, input: &str, output: &str) -> Vec<String> {
+        let mut args = vec![
+            "-hide_banner".to_string(),
+            "-i".to_string(),
+            input.to_string(),
+        ];
+        args.extend(self.args.iter().cloned());
+        args.push(output.to_string());
+        args
+    }
+
+    /// Command to paste into a shell.
+    pub fn shell_command(&self, input: &str, output: &str) -> String {
+        std::iter::once("ffmpeg".to_string())
+            .chain(self.command_line(input, output).iter().map(|a| quote(a)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// `<stem>_fixed.<extension>` next to `input`.
+    pub fn default_output(&self, input: &str) -> String {
+        let path = std::path::Path::new(input);
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("output");
+        path.with_file_name(format!("{}_fixed.{}", stem, self.extension))
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+/// What a failed check asks of the output.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Fix {
+    Remux,
+    Video,
+    Audio,
+    Unfixable,
+}
+
+fn fix_for(metric: Metric) -> Fix {
+    use Metric::*;
+    match metric {
+        Container | Faststart | Fragmented | MajorBrand | Chapters | StartTimecode => Fix::Remux,
+        VideoCodec | ImageFormat | Profile | Level | PixFmt | BitDepth | Resolution
+        | CodedResolution | Aspect | SampleAspectRatio | Rotation | Fps | Bitrate | FieldOrder
+        | DetectedScan | InterlacedFrames | FrameRateMode | MeasuredFps | DroppedFrames
+        | DuplicatedFrames | GopLength | GopDuration | GopStructure | ConsecutiveBFrames
+        | ComplexEditLists | ColorPrimaries | ColorTransfer | ColorSpace => Fix::Video,
+        AudioCodec | AudioSampleRate | AudioChannels | IntegratedLoudness | LoudnessRange
+        | TruePeak => Fix::Audio,
+        _ => Fix::Unfixable,
+    }
+}
+
+/// Build the command for a FLAWED result. `failed` are the checks whose
+/// deciding rule failed. `None` when none of them can be fixed by
+/// re-encoding or remuxing.
+pub fn plan(preset: &Preset, meta: &ExtendedMetadata, failed: &[&CheckDef]) -> Option<Remediation> {
+    let is_image = meta.duration_sec == 0.0 && meta.fps_avg == 0.0;
+    let has_audio = meta.audio_codec.is_some();
+    let failed_metric = |m: Metric| failed.iter().any(|c| c.metric == m);
+    let goal = |m: Metric| {
+        preset
+            .checks
+            .iter()
+            .filter(|c| c.metric == m)
+            .find_map(|c| target(meta, c))
+    };
+
+    let mut unresolved = Vec::new();
+    let mut video = false;
+    let mut audio = false;
+    for check in failed {
+        let fixable = match fix_for(check.metric) {
+            // Sizes can only be fixed towards an exact target; a minimum
+            // would mean upscaling, which adds no detail.
+            Fix::Video if is_size(check.metric) => frame_size(goal).is_some(),
+            Fix::Video => !is_hdr_conversion(meta, check.metric),
+            Fix::Audio => has_audio,
+            Fix::Remux => true,
+            Fix::Unfixable => false,
+        };
+        if !fixable {
+            unresolved.push(check.field.clone());
+            continue;
+        }
+        match fix_for(check.metric) {
+            Fix::Video => video = true,
+            Fix::Audio => audio = true,
+            _ => {}
+        }
+    }
+
+    let changes_container = failed_metric(Metric::Container);
+    let container = goal(Metric::Container)
+        .and_then(first_text)
+        .filter(|_| changes_container)
+        .unwrap_or_else(|| current_container(meta));
+    let drops_chapters = failed_metric(Metric::Chapters);
+    let timecode = failed_metric(Metric::StartTimecode)
+        .then(|| goal(Metric::StartTimecode).and_then(first_text))
+        .flatten();
+    // A new container may not take the streams as they are.
+    if changes_container {
+        video |= !container_accepts(&container, &current_codec(meta));
+        audio |= meta
+            .audio_codec
+            .as_deref()
+            .is_some_and(|codec| !container_accepts(&container, &codec.to_lowercase()));
+    }
+    let rewraps = changes_container
+        || drops_chapters
+        || timecode.is_some()
+        || [Metric::Faststart, Metric::Fragmented, Metric::MajorBrand]
+            .into_iter()
+            .any(failed_metric);
+    if !(video || audio || rewraps) {
+        return None;
+    }
+
+    let mut args: Vec<String> = Vec::new();
+    let mut push = |items: &[&str]| args.extend(items.iter().map(|s| s.to_string()));
+
+    if is_image {
+        let format = goal(Metric::ImageFormat)
+            .and_then(first_text)
+            .unwrap_or_else(|| meta.video_codec.to_lowercase());
+        let (encoder, extension) = image_encoder(&format);
+        push(&["-frames:v", "1", "-c:v", encoder]);
+        if encoder == "mjpeg" {
+            push(&["-q:v", "2"]);
+        }
+        let filters = video_filters(preset, meta, failed_metric, goal);
+        if !filters.is_empty() {
+            push(&["-vf", &filters.join(",")]);
+        }
+        return Some(Remediation {
+            args,
+            extension: extension.to_string(),
+            remux_only: false,
+            unresolved,
+        });
+    }
+
+    // Subtitle and data tracks (captions, timecode) are kept when the
+    // target container can carry them. A new start timecode replaces the
+    // tmcd track, so the old one isn't mapped.
+    let mut subtitles: Vec<(u32, &str)> = Vec::new();
+    let mut data: Vec<u32> = Vec::new();
+    let keeps_data = timecode.is_none()
+        && matches!(container.as_str(), "mp4" | "mov")
+        && matches!(current_container(meta).as_str(), "mp4" | "mov");
+    for stream in &meta.streams {
+        let codec = stream.codec_name.as_deref().unwrap_or("unknown");
+        match stream.codec_type.as_str() {
+            "subtitle" => match subtitle_codec(&container, codec) {
+                Some(target) => subtitles.push((stream.index, target)),
+                None => unresolved.push(format!("Subtitle track {} ({})", stream.index, codec)),
+            },
+            "data" if keeps_data => data.push(stream.index),
+            "data" if timecode.is_none() => {
+                unresolved.push(format!("Data track {} ({})", stream.index, codec))
+            }
+            _ => {}
+        }
+    }
+
+    push(&["-map", &format!("0:{}", meta.video_stream_index)]);
+    if has_audio {
+        push(&["-map", "0:a?"]);
+    }
+    for index in subtitles.iter().map(|(index, _)| index).chain(&data) {
+        push(&["-map", &format!("0:{}", index)]);
+    }
+    if drops_chapters {
+        push(&["-map_chapters", "-1"]);
+    }
+
+    if video {
+        let codec = goal(Metric::VideoCodec)
+            .and_then(first_text)
+            .or_else(|| {
+                Some(current_codec(meta)).filter(|codec| container_accepts(&container, codec))
+            })
+            .unwrap_or_else(|| default_video_codec(&container).to_string());
+        let encoder = video_encoder(&codec);
+        push(&["-c:v", encoder]);
+        if let Some(profile) = goal(Metric::Profile).and_then(first_name) {
+            push(&["-profile:v", &encoder_profile(&profile)]);
+        }
+        if let Some(level) = goal(Metric::Level).and_then(number) {
+            push(&["-level:v", &encoder_level(&codec, level)]);
+        }
+        if let Some(pix_fmt) = goal(Metric::PixFmt).and_then(first_text) {
+            push(&["-pix_fmt", &pix_fmt]);
+        }
+        match goal(Metric::Bitrate).map(bounds) {
+            Some((low, high)) if low.is_some() || high.is_some() => {
+                let (low, high) = (low.unwrap_or(0.0), high.unwrap_or(f64::INFINITY));
+                let rate = if high.is_finite() && low > 0.0 {
+                    (low + high) / 2.0
+                } else if high.is_finite() {
+                    high * 0.9
+                } else {
+                    low
+                };
+                push(&["-b:v", &kbps(rate)]);
+                if high.is_finite() {
+                    push(&["-maxrate", &kbps(high), "-bufsize", &kbps(high * 2.0)]);
+                }
+            }
+            _ if matches!(encoder, "libx264" | "libx265") => push(&["-crf", DEFAULT_CRF]),
+            _ => {}
+        }
+
+        let fps_failed = [
+            Metric::Fps,
+            Metric::FrameRateMode,
+            Metric::MeasuredFps,
+            Metric::DroppedFrames,
+            Metric::DuplicatedFrames,
+        ]
+        .into_iter()
+        .any(failed_metric);
+        let fps = goal(Metric::Fps).and_then(number).unwrap_or(meta.fps_avg);
+        if fps_failed && fps > 0.0 {
+            push(&["-fps_mode", "cfr", "-r", &frame_rate(fps)]);
+        }
+        if let Some(gop) = goal(Metric::GopLength).and_then(|t| bounds(t).1) {
+            push(&["-g", &format!("{}", gop.floor())]);
+        } else if let Some(seconds) = goal(Metric::GopDuration).and_then(|t| bounds(t).1) {
+            push(&["-g", &format!("{}", (seconds * fps).floor().max(1.0))]);
+        }
+        if failed_metric(Metric::GopStructure) {
+            push(&["-flags", "+cgop"]);
+        }
+        if let Some(bframes) = goal(Metric::ConsecutiveBFrames).and_then(|t| bounds(t).1) {
+            push(&["-bf", &format!("{}", bframes.floor())]);
+        }
+        let color_failed = [
+            Metric::ColorPrimaries,
+            Metric::ColorTransfer,
+            Metric::ColorSpace,
+        ]
+        .into_iter()
+        .any(failed_metric);
+        if color_failed && meta.hdr.is_none() {
+            push(&[
+                "-color_primaries",
+                "bt709",
+                "-color_trc",
+                "bt709",
+                "-colorspace",
+                "bt709",
+            ]);
+        }
+
+        let filters = video_filters(preset, meta, failed_metric, goal);
+        if !filters.is_empty() {
+            push(&["-vf", &filters.join(",")]);
+        }
+    } else {
+        push(&["-c:v", "copy"]);
+    }
+
+    if has_audio {
+        if audio {
+            let codec = goal(Metric::AudioCodec)
+                .and_then(first_text)
+                .unwrap_or_else(|| default_audio_codec(&container).to_string());
+            let encoder = audio_encoder(&codec);
+            push(&["-c:a", encoder]);
+            if matches!(encoder, "aac" | "libmp3lame" | "libopus") {
+                push(&["-b:a", AUDIO_BITRATE]);
+            }
+            let rate = goal(Metric::AudioSampleRate).and_then(|t| {
+                let (low, high) = bounds(t);
+                SAMPLE_RATES
+                    .into_iter()
+                    .find(|r| low.is_none_or(|l| *r >= l) && high.is_none_or(|h| *r <= h))
+            });
+            if let Some(rate) = rate {
+                push(&["-ar", &format!("{}", rate)]);
+            }
+            if failed_metric(Metric::AudioChannels) {
+                if let Some(channels) = goal(Metric::AudioChannels).and_then(number) {
+                    push(&["-ac", &format!("{}", channels.round())]);
+                }
+            }
+            if [
+                Metric::IntegratedLoudness,
+                Metric::TruePeak,
+                Metric::LoudnessRange,
+            ]
+            .into_iter()
+            .any(failed_metric)
+            {
+                let mut loudnorm = format!(
+                    "loudnorm=I={}",
+                    trim(
+                        goal(Metric::IntegratedLoudness)
+                            .and_then(number)
+                            .unwrap_or(-23.0)
+                    )
+                );
+                if let Some(peak) = goal(Metric::TruePeak).and_then(|t| bounds(t).1) {
+                    loudnorm.push_str(&format!(":TP={}", trim(peak)));
+                }
+                if let Some(range) = goal(Metric::LoudnessRange).and_then(|t| bounds(t).1) {
+                    loudnorm.push_str(&format!(":LRA={}", trim(range)));
+                }
+                push(&["-af", &loudnorm]);
+            }
+        } else {
+            push(&["-c:a", "copy"]);
+        }
+    }
+
+    for (n, (_, codec)) in subtitles.iter().enumerate() {
+        push(&[&format!("-c:s:{}", n), codec]);
+    }
+    if !data.is_empty() {
+        push(&["-c:d", "copy"]);
+    }
+    if let Some(timecode) = &timecode {
+        push(&["-timecode", timecode]);
+    }
+    if matches!(container.as_str(), "mp4" | "mov") {
+        push(&["-movflags", "+faststart"]);
+    }
+
+    Some(Remediation {
+        args,
+        extension: container_extension(&container).to_string(),
+        remux_only: !video && !audio,
+        unresolved,
+    })
+}
+
+/// Deinterlace, rotate-free scaling and bar handling, in filter order.
+fn video_filters<'a>(
+    preset: &Preset,
+    meta: &ExtendedMetadata,
+    failed_metric: impl Fn(Metric) -> bool,
+    goal: impl Fn(Metric) -> Option<&'a Condition>,
+) -> Vec<String> {
+    let mut filters = Vec::new();
+    if [
+        Metric::FieldOrder,
+        Metric::DetectedScan,
+        Metric::InterlacedFrames,
+    ]
+    .into_iter()
+    .any(&failed_metric)
+    {
+        filters.push("yadif".to_string());
+    }
+    let size_failed = [Metric::Resolution, Metric::CodedResolution, Metric::Aspect]
+        .into_iter()
+        .any(&failed_metric);
+    if let (true, Some([w, h])) = (size_failed, frame_size(goal)) {
+        let same_aspect = meta.display_height > 0
+            && (f64::from(meta.display_width) / f64::from(meta.display_height)
+                - f64::from(w) / f64::from(h))
+            .abs()
+                < 0.01;
+        if same_aspect {
+            filters.push(format!("scale={}:{}", w, h));
+        } else if preset.uses_metric(|m| matches!(m, Metric::Letterbox | Metric::Pillarbox)) {
+            // Padding would add the bars the preset rejects; fill and crop.
+            filters.push(format!(
+                "scale={}:{}:force_original_aspect_ratio=increase",
+                w, h
+            ));
+            filters.push(format!("crop={}:{}", w, h));
+        } else {
+            filters.push(format!(
+                "scale={}:{}:force_original_aspect_ratio=decrease",
+                w, h
+            ));
+            filters.push(format!("pad={}:{}:(ow-iw)/2:(oh-ih)/2", w, h));
+        }
+        filters.push("setsar=1".to_string());
+    } else if failed_metric(Metric::SampleAspectRatio) {
+        filters.push("scale=iw*sar:ih".to_string());
+        filters.push("setsar=1".to_string());
+    }
+    filters
+}
+
+fn is_size(metric: Metric) -> bool {
+    matches!(
+        metric,
+        Metric::Resolution | Metric::CodedResolution | Metric::Aspect
+    )
+}
+
+/// The exact frame size the preset asks for, if it names one.
+fn frame_size<'a>(goal: impl Fn(Metric) -> Option<&'a Condition>) -> Option<[u32; 2]> {
+    goal(Metric::Resolution)
+        .or_else(|| goal(Metric::CodedResolution))
+        .and_then(|t| t.size.as_ref()?.first().copied())
+}
+
+/// Condition of the first passing rule of `check` that sets a value for the
+/// check's own metric and whose conditions on other metrics hold for
+/// `meta`.
+fn target<'a>(meta: &ExtendedMetadata, check: &'a CheckDef) -> Option<&'a Condition> {
+    check.rules.iter().filter(|r| r.ok).find_map(|rule| {
+        let cond = rule.when.as_ref()?;
+        let own = cond.metric.is_none()
+            && (cond.one_of.is_some()
+                || cond.contains.is_some()
+                || cond.eq.is_some()
+                || cond.min.is_some()
+                || cond.max.is_some()
+                || cond.above.is_some()
+                || cond.below.is_some()
+                || cond.near.is_some()
+                || cond.size.is_some()
+                || cond.min_size.is_some());
+        let others_hold = cond
+            .all
+            .iter()
+            .flatten()
+            .filter(|c| c.metric.is_some())
+            .all(|c| holds(meta, c));
+        (own && others_hold).then_some(cond)
+    })
+}
+
+/// The first accepted value from an `in` list.
+fn first_text(cond: &Condition) -> Option<String> {
+    cond.one_of.as_ref()?.first().map(|s| s.to_lowercase())
+}
+
+/// Like `first_text`, but also takes a `contains` fragment, for names such
+/// as profiles where "high" is a complete value.
+fn first_name(cond: &Condition) -> Option<String> {
+    first_text(cond).or_else(|| cond.contains.as_ref()?.first().map(|s| s.to_lowercase()))
+}
+
+/// A single value to aim for: `eq`, the first `near`, or the middle of the
+/// bounds.
+fn number(cond: &Condition) -> Option<f64> {
+    if let Some(eq) = cond.eq {
+        return Some(eq);
+    }
+    if let Some(near) = cond.near.as_ref().and_then(|n| n.first()) {
+        return Some(*near);
+    }
+    match bounds(cond) {
+        (Some(low), Some(high)) => Some((low + high) / 2.0),
+        (low, high) => low.or(high),
+    }
+}
+
+/// Lower and upper bound from `eq`, `min`/`above` and `max`/`below`.
+fn bounds(cond: &Condition) -> (Option<f64>, Option<f64>) {
+    (
+        cond.eq.or(cond.min).or(cond.above),
+        cond.eq.or(cond.max).or(cond.below),
+    )
+}
+
+/// PQ/HLG to SDR needs tone mapping, which a plain re-encode can't do.
+fn is_hdr_conversion(meta: &ExtendedMetadata, metric: Metric) -> bool {
+    matches!(
+        metric,
+        Metric::ColorPrimaries | Metric::ColorTransfer | Metric::ColorSpace
+    ) && meta.hdr.is_some()
+}
+
+fn current_container(meta: &ExtendedMetadata) -> String {
+    let c = meta.container.to_lowercase();
+    ["mp4", "mov", "mxf", "webm", "mkv", "matroska"]
+        .into_iter()
+        .find(|name| c.contains(name))
+        .unwrap_or("mp4")
+        .to_string()
+}
+
+fn current_codec(meta: &ExtendedMetadata) -> String {
+    meta.video_codec.to_lowercase()
+}
+
+fn container_extension(container: &str) -> &str {
+    match container {
+        "matroska" => "mkv",
+        "quicktime" => "mov",
+        other => other,
+    }
+}
+
+/// Whether `container` can carry a stream in `codec` (ffprobe names).
+/// Matroska and containers not listed take anything.
+fn container_accepts(container: &str, codec: &str) -> bool {
+    let pcm = codec.starts_with("pcm_");
+    match container {
+        "mp4" => matches!(
+            codec,
+            "h264"
+                | "hevc"
+                | "av1"
+                | "vp9"
+                | "mpeg4"
+                | "mpeg2video"
+                | "aac"
+                | "mp3"
+                | "ac3"
+                | "eac3"
+                | "opus"
+                | "flac"
+                | "alac"
+        ),
+        "mov" => {
+            pcm || matches!(
+                codec,
+                "h264"
+                    | "hevc"
+                    | "prores"
+                    | "dnxhd"
+                    | "mpeg4"
+                    | "mpeg2video"
+                    | "mjpeg"
+                    | "aac"
+                    | "mp3"
+                    | "ac3"
+                    | "eac3"
+                    | "alac"
+            )
+        }
+        "webm" => matches!(codec, "vp8" | "vp9" | "av1" | "opus" | "vorbis"),
+        "mxf" => {
+            pcm || matches!(
+                codec,
+                "mpeg2video" | "dnxhd" | "h264" | "prores" | "jpeg2000" | "dvvideo"
+            )
+        }
+        _ => true,
+    }
+}
+
+/// `-c:s` for carrying a subtitle track in `codec` into `container`: text
+/// subtitles are converted to the format it stores, others copied. `None`
+/// when it can't go in (bitmap subtitles in MP4, say).
+fn subtitle_codec(container: &str, codec: &str) -> Option<&'static str> {
+    let text = matches!(
+        codec,
+        "subrip" | "srt" | "ass" | "ssa" | "webvtt" | "mov_text" | "text"
+    );
+    let captions = CAPTION_CODECS.contains(&codec);
+    match container {
+        "mp4" => text.then_some("mov_text"),
+        "mov" if captions => Some("copy"),
+        "mov" => text.then_some("mov_text"),
+        "webm" => text.then_some("webvtt"),
+        "mxf" => None,
+        // Matroska takes anything but MP4's own formats.
+        _ if codec == "mov_text" => Some("srt"),
+        _ => (!captions).then_some("copy"),
+    }
+}
+
+/// Codec to re-encode into when the preset names none and the current one
+/// doesn't fit the container.
+fn default_video_codec(container: &str) -> &'static str {
+    match container {
+        "webm" => "vp9",
+        "mxf" => "mpeg2video",
+        _ => "h264",
+    }
+}
+
+fn default_audio_codec(container: &str) -> &'static str {
+    match container {
+        "webm" => "opus",
+        "mxf" => "pcm_s24le",
+        _ => "aac",
+    }
+}
+
+fn video_encoder(codec: &str) -> &'static str {
+    match codec {
+        "hevc" | "h265" => "libx265",
+        "vp9" => "libvpx-vp9",
+        "av1" => "libsvtav1",
+        "prores" => "prores_ks",
+        "dnxhd" | "dnxhr" => "dnxhd",
+        "mpeg2" | "mpeg2video" => "mpeg2video",
+        "jpeg2000" => "jpeg2000",
+        _ => "libx264",
+    }
+}
+
+fn audio_encoder(codec: &str) -> &'static str {
+    match codec {
+        "pcm_s24le" => "pcm_s24le",
+        "pcm_s16le" | "pcm" => "pcm_s16le",
+        "mp3" => "libmp3lame",
+        "opus" => "libopus",
+        "ac3" => "ac3",
+        "eac3" => "eac3",
+        "flac" => "flac",
+        _ => "aac",
+    }
+}
+
+fn image_encoder(format: &str) -> (&'static str, &'static str) {
+    match format {
+        "png" => ("png", "png"),
+        "webp" => ("libwebp", "webp"),
+        _ => ("mjpeg", "jpg"),
+    }
+}
+
+/// ffprobe profile names ("High", "Main 10", "High 4:2:2") as encoders
+/// spell them.
+fn encoder_profile(profile: &str) -> String {
+    profile
+        .to_lowercase()
+        .replace("constrained", "")
+        .replace("4:2:2", "422")
+        .replace("4:4:4", "444")
+        .replace([' ', '-'], "")
+}
+
+/// ffprobe reports H.264 levels ×10 and HEVC levels ×30.
+fn encoder_level(codec: &str, level: f64) -> String {
+    match codec {
+        "h264" => trim(level / 10.0),
+        "hevc" => trim(level / 30.0),
+        _ => trim(level),
+    }
+}
+
+/// NTSC rates as exact fractions, others as plain numbers.
+fn frame_rate(fps: f64) -> String {
+    [24.0, 30.0, 60.0]
+        .into_iter()
+        .find(|base| (fps - base * 1000.0 / 1001.0).abs() < 0.01)
+        .map(|base| format!("{}/1001", base * 1000.0))
+        .unwrap_or_else(|| trim(fps))
+}
+
+fn kbps(mbps: f64) -> String {
+    format!("{}k", (mbps * 1000.0).round())
+}
+
+/// Up to three decimals, without trailing zeros.
+fn trim(value: f64) -> String {
+    format!("{}", (value * 1000.0).round() / 1000.0)
+}
+
+/// Quote for the shell the command will be pasted into: POSIX single quotes,
+/// or PowerShell single quotes on Windows. Both are literal, so `$var`,
+/// backticks and `%VAR%` in a path are never expanded.
+fn quote(arg: &str) -> String {
+    if cfg!(windows) {
+        quote_windows(arg)
+    } else {
+        quote_posix(arg)
+    }
+}
+
+fn quote_posix(arg: &str) -> String {
+    if is_plain(arg, "") {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// PowerShell doubles a quote inside single quotes. A bare comma would make
+/// an array argument, so it needs quoting too.
+fn quote_windows(arg: &str) -> String {
+    if is_plain(arg, "\\") && !arg.contains(',') {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "''"))
+    }
+}
+
+/// Needs no quoting in any shell; `extra` are characters that are also safe
+/// in the target one.
+fn is_plain(arg: &str, extra: &str) -> bool {
+    !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=+,".contains(c) || extra.contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::{builtin_presets, parse_preset};
+    use crate::rules::evaluate;
+    use serde_json::{json, Value};
+
+    /// An ig_reels-compliant clip in a MOV; `overrides` replaces top-level
+    /// fields.
+    fn meta(overrides: Value) -> ExtendedMetadata {
+        let mut base = json!({
+            "width": 1080, "height": 1920, "displayWidth": 1080, "displayHeight": 1920,
+            "fps": 30.0, "fpsAvg": 30.0, "fpsR": 30.0,
+            "videoCodec": "h264", "container": "mov", "durationSec": 20.0,
+            "audioCodec": "aac", "audioSampleRate": 48000, "audioChannels": 2,
+            "videoBitrate": 9_000_000, "profile": "High", "level": 42,
+            "pixFmt": "yuv420p", "fieldOrder": "progressive",
+            "colorPrimaries": "bt709", "colorTransfer": "bt709", "colorSpace": "bt709",
+            "mp4": {
+                "atoms": ["ftyp", "moov", "mdat"], "faststart": true, "fragmented": false,
+                "fragments": 0, "compatibleBrands": [], "editLists": [], "truncated": false,
+            },
+            "crop": {
+                "width": 1080, "height": 1920, "x": 0, "y": 0, "frameWidth": 1080,
+                "frameHeight": 1920, "letterbox": false, "pillarbox": false, "samples": 5,
+            },
+            "frameTiming": {
+                "frames": 600, "vfr": false, "measuredFps": 30.0, "minIntervalMs": 33.3,
+                "maxIntervalMs": 33.4, "stdevIntervalMs": 0.0, "droppedFrames": 0,
+                "duplicatedFrames": 0,
+            },
+        });
+        for (key, value) in overrides.as_object().unwrap() {
+            base[key] = value.clone();
+        }
+        serde_json::from_value(base).unwrap()
+    }
+
+    fn builtin(id: &str) -> &'static Preset {
+        builtin_presets().iter().find(|p| p.id == id).unwrap()
+    }
+
+    /// A preset that only asks for an MP4 container.
+    fn mp4_only() -> Preset {
+        parse_preset(
+            r#"
+id = "mp4_only"
+name = "MP4"
+title = "MP4"
+category = "social"
+
+[[checks]]
+field = "Container"
+metric = "container"
+rules = [
+  { when = { in = ["mp4"] }, expected = "MP4", reason = "OK" },
+  { ok = false, expected = "MP4", reason = "Not MP4" },
+]
+"#,
+        )
+        .unwrap()
+    }
+
+    fn plan_for(preset: &Preset, meta: &ExtendedMetadata) -> Option<Remediation> {
+        evaluate(preset, meta).remediation
+    }
+
+    /// The value following `flag`.
+    fn arg<'a>(remediation: &'a Remediation, flag: &str) -> Option<&'a str> {
+        let i = remediation.args.iter().position(|a| a == flag)?;
+        remediation.args.get(i + 1).map(String::as_str)
+    }
+
+    #[test]
+    fn passing_file_has_no_plan() {
+        let m = meta(json!({ "container": "mp4" }));
+        assert_eq!(
+            evaluate(builtin("ig_reels"), &m).verdict,
+            crate::rules::Verdict::Perfect
+        );
+        assert!(plan_for(builtin("ig_reels"), &m).is_none());
+    }
+
+    #[test]
+    fn wrong_container_is_a_remux() {
+        let fix = plan_for(builtin("ig_reels"), &meta(json!({}))).unwrap();
+        assert!(fix.remux_only);
+        assert_eq!(fix.extension, "mp4");
+        assert_eq!(arg(&fix, "-c:v"), Some("copy"));
+        assert_eq!(arg(&fix, "-c:a"), Some("copy"));
+        assert_eq!(arg(&fix, "-movflags"), Some("+faststart"));
+        assert!(fix.unresolved.is_empty());
+    }
+
+    #[test]
+    fn maps_the_analyzed_video_stream() {
+        // Cover art ahead of the picture in the container.
+        let fix = plan_for(builtin("ig_reels"), &meta(json!({ "videoStreamIndex": 2 }))).unwrap();
+        assert_eq!(arg(&fix, "-map"), Some("0:2"));
+    }
+
+    fn stream(index: u32, codec_type: &str, codec_name: &str) -> Value {
+        json!({
+            "index": index, "codecType": codec_type, "codecName": codec_name,
+            "disposition": [],
+        })
+    }
+
+    /// Every `-map` target, in order.
+    fn maps(remediation: &Remediation) -> Vec<&str> {
+        remediation
+            .args
+            .windows(2)
+            .filter(|pair| pair[0] == "-map")
+            .map(|pair| pair[1].as_str())
+            .collect()
+    }
+
+    #[test]
+    fn keeps_subtitle_caption_and_data_tracks() {
+        let streams = json!([
+            stream(0, "video", "h264"),
+            stream(1, "audio", "aac"),
+            stream(2, "subtitle", "subrip"),
+            stream(3, "subtitle", "eia_608"),
+            stream(4, "data", "tmcd"),
+        ]);
+
+        // MOV to MOV: captions copied, text converted, timecode kept.
+        let mut mov = mp4_only();
+        mov.checks[0].rules[0].when.as_mut().unwrap().one_of = Some(vec!["mov".into()]);
+        let m = meta(json!({ "container": "mp4", "streams": streams }));
+        let fix = plan_for(&mov, &m).unwrap();
+        assert_eq!(maps(&fix), ["0:0", "0:a?", "0:2", "0:3", "0:4"]);
+        assert_eq!(arg(&fix, "-c:s:0"), Some("mov_text"));
+        assert_eq!(arg(&fix, "-c:s:1"), Some("copy"));
+        assert_eq!(arg(&fix, "-c:d"), Some("copy"));
+        assert!(fix.unresolved.is_empty());
+
+        // MP4 has no caption track type; the rest still goes across.
+        let m = meta(json!({ "streams": streams }));
+        let fix = plan_for(&mp4_only(), &m).unwrap();
+        assert_eq!(maps(&fix), ["0:0", "0:a?", "0:2", "0:4"]);
+        assert_eq!(arg(&fix, "-c:s:0"), Some("mov_text"));
+        assert_eq!(arg(&fix, "-c:s:1"), None);
+        assert_eq!(fix.unresolved, ["Subtitle track 3 (eia_608)"]);
+    }
+
+    #[test]
+    fn subtitles_follow_the_target_container() {
+        let m = meta(json!({
+            "container": "mp4",
+            "streams": [
+                stream(0, "video", "h264"),
+                stream(1, "subtitle", "mov_text"),
+                stream(2, "subtitle", "hdmv_pgs_subtitle"),
+            ],
+        }));
+        let mut mkv = mp4_only();
+        mkv.checks[0].rules[0].when.as_mut().unwrap().one_of = Some(vec!["matroska".into()]);
+        let fix = plan_for(&mkv, &m).unwrap();
+        assert_eq!(maps(&fix), ["0:0", "0:a?", "0:1", "0:2"]);
+        assert_eq!(arg(&fix, "-c:s:0"), Some("srt"));
+        assert_eq!(arg(&fix, "-c:s:1"), Some("copy"));
+        assert!(fix.unresolved.is_empty());
+
+        assert_eq!(subtitle_codec("mp4", "hdmv_pgs_subtitle"), None);
+        assert_eq!(subtitle_codec("webm", "subrip"), Some("webvtt"));
+        assert_eq!(subtitle_codec("mxf", "subrip"), None);
+    }
+
+    #[test]
+    fn new_timecode_replaces_the_tmcd_track() {
+        let preset = parse_preset(
+            r#"
+id = "tc"
+name = "TC"
+title = "TC"
+category = "broadcast"
+
+[[checks]]
+field = "Start timecode"
+metric = "start_timecode"
+rules = [
+  { when = { in = ["10:00:00:00"] }, expected = "10:00:00:00", reason = "OK" },
+  { ok = false, expected = "10:00:00:00", reason = "Wrong start" },
+]
+"#,
+        )
+        .unwrap();
+        let m = meta(json!({
+            "timecode": {
+                "start": "01:00:00:00", "dropFrame": false, "rate": 30, "source": "tmcd",
+            },
+            "streams": [stream(0, "video", "h264"), stream(1, "data", "tmcd")],
+        }));
+        let fix = plan_for(&preset, &m).unwrap();
+        assert_eq!(maps(&fix), ["0:0", "0:a?"]);
+        assert_eq!(arg(&fix, "-timecode"), Some("10:00:00:00"));
+        assert!(fix.unresolved.is_empty());
+    }
+
+    #[test]
+    fn remux_reencodes_streams_the_container_cannot_hold() {
+        let preset = mp4_only();
+        let fix = plan_for(&preset, &meta(json!({ "audioCodec": "pcm_s16le" }))).unwrap();
+        assert!(!fix.remux_only);
+        assert_eq!(arg(&fix, "-c:v"), Some("copy"));
+        assert_eq!(arg(&fix, "-c:a"), Some("aac"));
+
+        let fix = plan_for(&preset, &meta(json!({ "videoCodec": "prores" }))).unwrap();
+        assert!(!fix.remux_only);
+        assert_eq!(arg(&fix, "-c:v"), Some("libx264"));
+        assert_eq!(arg(&fix, "-c:a"), Some("copy"));
+
+        // Matroska takes anything.
+        let mut mkv = preset.clone();
+        mkv.checks[0].rules[0].when.as_mut().unwrap().one_of = Some(vec!["matroska".into()]);
+        let fix = plan_for(&mkv, &meta(json!({ "audioCodec": "pcm_s16le" }))).unwrap();
+        assert!(fix.remux_only);
+        assert_eq!(fix.extension, "mkv");
+    }
+
+    #[test]
+    fn bitrate_window_follows_duration() {
+        let m =
+            meta(json!({ "container": "mp4", "durationSec": 45.0, "videoBitrate": 12_000_000 }));
+        let fix = plan_for(builtin("ig_reels"), &m).unwrap();
+        assert!(!fix.remux_only);
+        assert_eq!(arg(&fix, "-c:v"), Some("libx264"));
+        assert_eq!(arg(&fix, "-profile:v"), Some("high"));
+        assert_eq!(arg(&fix, "-level:v"), Some("4.2"));
+        assert_eq!(arg(&fix, "-b:v"), Some("8500k"));
+        assert_eq!(arg(&fix, "-maxrate"), Some("9000k"));
+        assert_eq!(arg(&fix, "-c:a"), Some("copy"));
+    }
+
+    #[test]
+    fn frame_rate_is_forced_constant() {
+        let m = meta(json!({ "container": "mp4", "fpsAvg": 25.0, "fps": 25.0 }));
+        let fix = plan_for(builtin("ig_reels"), &m).unwrap();
+        // The middle of ig_reels' 29.9-30.1 window.
+        assert_eq!(arg(&fix, "-fps_mode"), Some("cfr"));
+        assert_eq!(arg(&fix, "-r"), Some("30"));
+        assert_eq!(frame_rate(29.97), "30000/1001");
+        assert_eq!(frame_rate(23.976), "24000/1001");
+        assert_eq!(frame_rate(25.0), "25");
+    }
+
+    #[test]
+    fn wrong_aspect_is_cropped_when_bars_are_checked() {
+        let m = meta(json!({ "container": "mp4", "displayWidth": 1920, "displayHeight": 1080 }));
+        let fix = plan_for(builtin("ig_reels"), &m).unwrap();
+        assert_eq!(
+            arg(&fix, "-vf"),
+            Some("scale=1080:1920:force_original_aspect_ratio=increase,crop=1080:1920,setsar=1")
+        );
+    }
+
+    #[test]
+    fn audio_fixes() {
+        let m = meta(json!({ "container": "mp4", "audioCodec": "mp3", "audioSampleRate": 32000 }));
+        let fix = plan_for(builtin("ig_reels"), &m).unwrap();
+        assert_eq!(arg(&fix, "-c:v"), Some("copy"));
+        assert_eq!(arg(&fix, "-c:a"), Some("aac"));
+        assert_eq!(arg(&fix, "-b:a"), Some(AUDIO_BITRATE));
+        assert_eq!(arg(&fix, "-ar"), Some("48000"));
+    }
+
+    #[test]
+    fn unfixable_checks_are_listed() {
+        let m = meta(json!({ "durationSec": 200.0 }));
+        let fix = plan_for(builtin("ig_reels"), &m).unwrap();
+        assert!(fix.unresolved.contains(&"Duration".to_string()));
+
+        let m =
+            meta(json!({ "container": "mp4", "durationSec": 200.0, "videoBitrate": 7_000_000 }));
+        assert!(plan_for(builtin("ig_reels"), &m).is_none());
+    }
+
+    #[test]
+    fn quoting() {
+        assert_eq!(quote_posix("clip.mov"), "clip.mov");
+        assert_eq!(quote_posix("my clip.mov"), "'my clip.mov'");
+        assert_eq!(quote_posix("it's.mov"), r"'it'\''s.mov'");
+        assert_eq!(quote_posix(r"C:\clip.mov"), r"'C:\clip.mov'");
+        assert_eq!(quote_posix(""), "''");
+
+        assert_eq!(quote_windows(r"C:\Media\clip.mov"), r"C:\Media\clip.mov");
+        assert_eq!(
+            quote_windows(r"C:\My Media\clip.mov"),
+            r"'C:\My Media\clip.mov'"
+        );
+        assert_eq!(
+            quote_windows("pad=1080:1920:(ow-iw)/2:(oh-ih)/2"),
+            "'pad=1080:1920:(ow-iw)/2:(oh-ih)/2'"
+        );
+        assert_eq!(quote_windows("it's.mov"), "'it''s.mov'");
+        assert_eq!(quote_windows(r#"say "hi""#), r#"'say "hi"'"#);
+        assert_eq!(quote_windows("crop=1,scale=2"), "'crop=1,scale=2'");
+
+        // Nothing inside is expanded.
+        assert_eq!(quote_windows(r"C:\a $b %c%.mov"), r"'C:\a $b %c%.mov'");
+        assert_eq!(quote_windows("a`b.mov"), "'a`b.mov'");
+        assert_eq!(quote_posix("/a $b %c%.mov"), "'/a $b %c%.mov'");
+    }
+
+    #[test]
+    fn command_line_and_output() {
+        let fix = Remediation {
+            args: vec!["-c:v".into(), "copy".into()],
+            extension: "mp4".into(),
+            remux_only: true,
+            unresolved: Vec::new(),
+        };
+        assert_eq!(
+            fix.command_line("in.mov", "out.mp4"),
+            ["-hide_banner", "-i", "in.mov", "-c:v", "copy", "out.mp4"]
+        );
+        assert_eq!(
+            fix.shell_command("in.mov", "out.mp4"),
+            "ffmpeg -hide_banner -i in.mov -c:v copy out.mp4"
+        );
+        assert_eq!(fix.default_output("/a/b/clip.mov"), "/a/b/clip_fixed.mp4");
+    }
+}
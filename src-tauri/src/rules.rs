@@ -0,0 +1,1039 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::ExportDoctorError;
+use crate::presets::{CheckDef, Condition, Metric, Preset, RuleDef};
+use crate::qc::QcKind;
+use crate::remedy::{self, Remediation};
+use crate::{ExtendedMetadata, StreamInfo};
+
+// ── Types ──
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldCheck {
+    pub field: String,
+    pub value: String,
+    pub expected: String,
+    pub ok: bool,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Verdict {
+    Perfect,
+    Flawed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfectResult {
+    pub verdict: Verdict,
+    pub checks: Vec<FieldCheck>,
+    pub reasons: Vec<String>,
+    /// ffmpeg command that would fix the failed checks; FLAWED results only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<Remediation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetResult {
+    pub platform_id: String,
+    pub platform_name: String,
+    pub result: PerfectResult,
+}
+
+/// A metric resolved against one file. `text` is normalized for matching,
+/// `display` is what the user sees.
+struct MetricValue {
+    text: Option<String>,
+    number: Option<f64>,
+    size: Option<(u32, u32)>,
+    display: String,
+}
+
+impl MetricValue {
+    fn text(raw: Option<&str>, normalized: Option<String>, missing: &str) -> Self {
+        MetricValue {
+            text: normalized,
+            number: None,
+            size: None,
+            display: raw.filter(|s| !s.is_empty()).unwrap_or(missing).to_string(),
+        }
+    }
+
+    fn number(value: Option<f64>, precision: usize, suffix: &str) -> Self {
+        MetricValue {
+            text: value.map(|v| format!("{}", v)),
+            number: value,
+            size: None,
+            display: value
+                .map(|v| format!("{:.*}{}", precision, v, suffix))
+                .unwrap_or_else(|| "unknown".to_string()),
+        }
+    }
+
+    fn is_missing(&self) -> bool {
+        self.text.is_none() && self.number.is_none() && self.size.is_none()
+    }
+}
+
+// ── Evaluation ──
+
+/// Evaluate the given preset ids, in order, against `catalog`.
+/// Fails on the first id the catalog does not contain.
+pub fn evaluate_presets(
+    meta: &ExtendedMetadata,
+    catalog: &[Preset],
+    preset_ids: &[String],
+) -> Result<Vec<PresetResult>, ExportDoctorError> {
+    preset_ids
+        .iter()
+        .map(|id| {
+            let preset = catalog
+                .iter()
+                .find(|p| &p.id == id)
+                .ok_or_else(|| ExportDoctorError::UnknownPreset { id: id.clone() })?;
+            Ok(PresetResult {
+                platform_id: preset.id.clone(),
+                platform_name: preset.name.clone(),
+                result: evaluate(preset, meta),
+            })
+        })
+        .collect()
+}
+
+pub fn evaluate(preset: &Preset, meta: &ExtendedMetadata) -> PerfectResult {
+    let mut checks = Vec::with_capacity(preset.checks.len());
+    let mut reasons = Vec::new();
+    let mut failed = Vec::new();
+
+    for check in &preset.checks {
+        let value = resolve(meta, check.metric, check.precision);
+        let Some(rule) = check
+            .rules
+            .iter()
+            .find(|r| r.when.as_ref().is_none_or(|c| matches(meta, &value, c)))
+        else {
+            continue;
+        };
+        checks.push(field_check(meta, check, rule, &value));
+        if !rule.ok {
+            failed.push(check);
+        }
+        if let Some(note) = &rule.note {
+            reasons.push(expand(note, meta, &value));
+        }
+    }
+
+    let verdict = if checks.iter().any(|c| !c.ok) {
+        Verdict::Flawed
+    } else {
+        Verdict::Perfect
+    };
+    let remediation = match verdict {
+        Verdict::Flawed => remedy::plan(preset, meta, &failed),
+        Verdict::Perfect => None,
+    };
+    PerfectResult {
+        verdict,
+        checks,
+        reasons,
+        remediation,
+    }
+}
+
+fn field_check(
+    meta: &ExtendedMetadata,
+    check: &CheckDef,
+    rule: &RuleDef,
+    value: &MetricValue,
+) -> FieldCheck {
+    let shown = match (&rule.value, &check.display) {
+        (Some(template), _) | (None, Some(template)) => expand(template, meta, value),
+        (None, None) => value.display.clone(),
+    };
+    FieldCheck {
+        field: check.field.clone(),
+        value: shown,
+        expected: rule.expected.clone(),
+        ok: rule.ok,
+        reason: expand(&rule.reason, meta, value),
+    }
+}
+
+/// Whether `cond`, which names its own `metric`, holds for `meta`.
+pub(crate) fn holds(meta: &ExtendedMetadata, cond: &Condition) -> bool {
+    cond.metric
+        .is_some_and(|metric| matches(meta, &resolve(meta, metric, None), cond))
+}
+
+fn matches(meta: &ExtendedMetadata, own: &MetricValue, cond: &Condition) -> bool {
+    let other;
+    let v = match cond.metric {
+        Some(metric) => {
+            other = resolve(meta, metric, None);
+            &other
+        }
+        None => own,
+    };
+
+    let text = v.text.as_deref();
+    let number = v.number;
+
+    if let Some(missing) = cond.missing {
+        if v.is_missing() != missing {
+            return false;
+        }
+    }
+    if let Some(list) = &cond.one_of {
+        if !text.is_some_and(|t| list.iter().any(|item| item.eq_ignore_ascii_case(t))) {
+            return false;
+        }
+    }
+    if let Some(list) = &cond.contains {
+        if !text.is_some_and(|t| list.iter().any(|item| t.contains(&item.to_lowercase()))) {
+            return false;
+        }
+    }
+    let bound_holds = |bound: Option<f64>, cmp: fn(f64, f64) -> bool| {
+        bound.is_none_or(|b| number.is_some_and(|x| cmp(x, b)))
+    };
+    if !(bound_holds(cond.eq, |x, b| x == b)
+        && bound_holds(cond.min, |x, b| x >= b)
+        && bound_holds(cond.max, |x, b| x <= b)
+        && bound_holds(cond.above, |x, b| x > b)
+        && bound_holds(cond.below, |x, b| x < b))
+    {
+        return false;
+    }
+    if let Some(targets) = &cond.near {
+        let tolerance = cond.tolerance.unwrap_or(0.01);
+        if !number.is_some_and(|x| targets.iter().any(|t| (x - t).abs() < tolerance)) {
+            return false;
+        }
+    }
+    if let Some(sizes) = &cond.size {
+        if !v
+            .size
+            .is_some_and(|(w, h)| sizes.iter().any(|s| s[0] == w && s[1] == h))
+        {
+            return false;
+        }
+    }
+    if let Some([min_w, min_h]) = cond.min_size {
+        if !v.size.is_some_and(|(w, h)| w >= min_w && h >= min_h) {
+            return false;
+        }
+    }
+    if let Some(all) = &cond.all {
+        if !all.iter().all(|c| matches(meta, own, c)) {
+            return false;
+        }
+    }
+    if let Some(any) = &cond.any {
+        if !any.iter().any(|c| matches(meta, own, c)) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Expand `{value}`, `{norm}` and `{<metric>}` placeholders.
+fn expand(template: &str, meta: &ExtendedMetadata, value: &MetricValue) -> String {
+    if !template.contains('{') {
+        return template.to_string();
+    }
+    let mut out = template.replace("{value}", &value.display).replace(
+        "{norm}",
+        &value.text.clone().unwrap_or_default().to_uppercase(),
+    );
+    for metric in Metric::ALL {
+        let placeholder = format!("{{{}}}", metric.name());
+        if out.contains(&placeholder) {
+            out = out.replace(&placeholder, &resolve(meta, *metric, None).display);
+        }
+    }
+    out
+}
+
+// ── Metrics ──
+
+fn resolve(meta: &ExtendedMetadata, metric: Metric, precision: Option<usize>) -> MetricValue {
+    let p = |default: usize| precision.unwrap_or(default);
+    match metric {
+        Metric::Container => MetricValue::text(
+            Some(&meta.container),
+            Some(normalize_container(&lc(&meta.container))),
+            "",
+        ),
+        Metric::VideoCodec => MetricValue::text(
+            Some(&meta.video_codec),
+            Some(normalize_codec(&lc(&meta.video_codec))),
+            "",
+        ),
+        Metric::ImageFormat => MetricValue::text(
+            Some(&meta.video_codec),
+            Some(normalize_image_codec(&lc(&meta.video_codec))),
+            "",
+        ),
+        Metric::Profile => optional_text(meta.profile.as_deref(), ""),
+        Metric::Level => MetricValue {
+            text: meta.level.map(|l| l.to_string()),
+            number: meta.level.map(f64::from),
+            size: None,
+            display: meta.level.map(|l| l.to_string()).unwrap_or_default(),
+        },
+        Metric::PixFmt => optional_text(meta.pix_fmt.as_deref(), ""),
+        Metric::Resolution => MetricValue {
+            text: None,
+            number: None,
+            size: Some((meta.display_width, meta.display_height)),
+            display: format!("{}x{}", meta.display_width, meta.display_height),
+        },
+        Metric::CodedResolution => MetricValue {
+            text: None,
+            number: None,
+            size: Some((meta.width, meta.height)),
+            display: format!("{}x{}", meta.width, meta.height),
+        },
+        Metric::Aspect => {
+            let ratio = if meta.display_height > 0 {
+                meta.display_width as f64 / meta.display_height as f64
+            } else {
+                0.0
+            };
+            MetricValue::number(Some(ratio), p(4), "")
+        }
+        Metric::Rotation => MetricValue::number(Some(f64::from(meta.rotation)), p(0), "°"),
+        Metric::SampleAspectRatio => {
+            let sar = meta.sample_aspect_ratio.as_deref().unwrap_or("1:1");
+            let number = sar
+                .split_once(':')
+                .and_then(|(n, d)| Some(n.parse::<f64>().ok()? / d.parse::<f64>().ok()?));
+            MetricValue {
+                number,
+                ..MetricValue::text(Some(sar), Some(lc(sar)), "1:1")
+            }
+        }
+        Metric::FieldOrder => optional_text(meta.field_order.as_deref(), ""),
+        Metric::ColorPrimaries => optional_text(meta.color_primaries.as_deref(), "-"),
+        Metric::ColorTransfer => optional_text(meta.color_transfer.as_deref(), "-"),
+        Metric::ColorSpace => optional_text(meta.color_space.as_deref(), "-"),
+        Metric::Fps => MetricValue::number(Some(meta.fps_avg), p(2), ""),
+        Metric::Duration => MetricValue::number(Some(meta.duration_sec), p(2), "s"),
+        Metric::Bitrate => {
+            let bps = meta
+                .video_bitrate
+                .filter(|b| *b > 0)
+                .or(meta.format_bitrate)
+                .filter(|b| *b > 0);
+            MetricValue::number(bps.map(|b| b as f64 / 1_000_000.0), p(2), " Mbps")
+        }
+        Metric::AudioCodec => optional_text(meta.audio_codec.as_deref(), "none"),
+        Metric::AudioSampleRate => {
+            MetricValue::number(meta.audio_sample_rate.map(f64::from), p(0), "")
+        }
+        Metric::AudioChannels => MetricValue::number(meta.audio_channels.map(f64::from), p(0), ""),
+        Metric::BitDepth => {
+            let depth = meta
+                .bits_per_raw_sample
+                .as_deref()
+                .filter(|s| !s.is_empty());
+            MetricValue {
+                number: depth.and_then(|d| d.parse().ok()),
+                ..MetricValue::text(depth, depth.map(lc), "unknown")
+            }
+        }
+        Metric::VideoStreams => {
+            count_streams(meta, |s| s.codec_type == "video" && !s.is_attached_pic())
+        }
+        Metric::AudioStreams => count_streams(meta, |s| s.codec_type == "audio"),
+        Metric::SubtitleStreams => count_streams(meta, |s| s.codec_type == "subtitle"),
+        Metric::DataStreams => count_streams(meta, |s| s.codec_type == "data"),
+        Metric::AttachedPics => count_streams(meta, StreamInfo::is_attached_pic),
+        Metric::IntegratedLoudness => MetricValue::number(
+            meta.loudness.as_ref().and_then(|l| l.integrated_lufs),
+            p(1),
+            " LUFS",
+        ),
+        Metric::LoudnessRange => MetricValue::number(
+            meta.loudness.as_ref().and_then(|l| l.loudness_range_lu),
+            p(1),
+            " LU",
+        ),
+        Metric::TruePeak => MetricValue::number(
+            meta.loudness.as_ref().and_then(|l| l.true_peak_dbtp),
+            p(1),
+            " dBTP",
+        ),
+        Metric::FrameRateMode => {
+            let mode = meta
+                .frame_timing
+                .as_ref()
+                .map(|t| if t.vfr { "VFR" } else { "CFR" });
+            MetricValue::text(mode, mode.map(lc), "unknown")
+        }
+        Metric::MeasuredFps => {
+            MetricValue::number(meta.frame_timing.as_ref().map(|t| t.measured_fps), p(3), "")
+        }
+        Metric::DroppedFrames => MetricValue::number(
+            meta.frame_timing.as_ref().map(|t| t.dropped_frames as f64),
+            p(0),
+            "",
+        ),
+        Metric::DuplicatedFrames => MetricValue::number(
+            meta.frame_timing
+                .as_ref()
+                .map(|t| t.duplicated_frames as f64),
+            p(0),
+            "",
+        ),
+        Metric::GopLength => MetricValue::number(
+            meta.gop.as_ref().map(|g| g.max_gop_frames as f64),
+            p(0),
+            " frames",
+        ),
+        Metric::GopDuration => {
+            MetricValue::number(meta.gop.as_ref().map(|g| g.max_gop_seconds), p(2), "s")
+        }
+        Metric::GopStructure => {
+            let structure = meta
+                .gop
+                .as_ref()
+                .map(|g| if g.closed() { "closed" } else { "open" });
+            MetricValue::text(structure, structure.map(lc), "unknown")
+        }
+        Metric::ConsecutiveBFrames => MetricValue::number(
+            meta.gop.as_ref().map(|g| g.max_consecutive_b_frames as f64),
+            p(0),
+            "",
+        ),
+        Metric::Faststart => yes_no(meta.mp4.as_ref().map(|m| m.faststart)),
+        Metric::Fragmented => yes_no(meta.mp4.as_ref().map(|m| m.fragmented)),
+        Metric::MajorBrand => optional_text(
+            meta.mp4.as_ref().and_then(|m| m.major_brand.as_deref()),
+            "-",
+        ),
+        Metric::ComplexEditLists => MetricValue::number(
+            meta.mp4
+                .as_ref()
+                .map(|m| m.edit_lists.iter().filter(|e| e.is_complex()).count() as f64),
+            p(0),
+            "",
+        ),
+        Metric::HdrFormat => {
+            let format = meta.hdr.as_ref().map_or("SDR", |h| h.format.as_str());
+            MetricValue::text(Some(format), Some(lc(format)), "SDR")
+        }
+        Metric::MasteringDisplay => {
+            let display = meta.hdr.as_ref().and_then(|h| h.mastering_display.as_ref());
+            let primaries = display.map(|d| d.primaries.as_deref().unwrap_or("custom"));
+            MetricValue::text(primaries, primaries.map(lc), "none")
+        }
+        Metric::MasteringPeak => MetricValue::number(
+            meta.hdr
+                .as_ref()
+                .and_then(|h| h.mastering_display.as_ref())
+                .map(|d| d.max_luminance),
+            p(0),
+            " nits",
+        ),
+        Metric::MaxCll => MetricValue::number(
+            meta.hdr
+                .as_ref()
+                .and_then(|h| h.content_light.as_ref())
+                .map(|c| f64::from(c.max_cll)),
+            p(0),
+            " nits",
+        ),
+        Metric::MaxFall => MetricValue::number(
+            meta.hdr
+                .as_ref()
+                .and_then(|h| h.content_light.as_ref())
+                .map(|c| f64::from(c.max_fall)),
+            p(0),
+            " nits",
+        ),
+        Metric::DolbyVisionProfile => {
+            let profile = meta
+                .hdr
+                .as_ref()
+                .and_then(|h| h.dolby_vision.as_ref())
+                .map(|dv| dv.profile_name());
+            optional_text(profile.as_deref(), "none")
+        }
+        Metric::StartTimecode => {
+            let tc = meta.timecode.as_ref();
+            MetricValue::text(
+                tc.map(|t| t.start.as_str()),
+                tc.map(|t| t.normalized()),
+                "none",
+            )
+        }
+        Metric::DropFrame => yes_no(meta.timecode.as_ref().map(|t| t.drop_frame)),
+        Metric::TimecodeRate => {
+            MetricValue::number(meta.timecode.as_ref().map(|t| f64::from(t.rate)), p(0), "")
+        }
+        Metric::ClosedCaptions => yes_no(Some(meta.streams.iter().any(StreamInfo::has_captions))),
+        Metric::SubtitleCodecs => subtitle_list(meta, |s| s.codec_name.as_deref()),
+        Metric::SubtitleLanguages => subtitle_list(meta, |s| s.language.as_deref()),
+        Metric::Title => optional_text(meta.format_tags.get("title").map(String::as_str), "none"),
+        Metric::Encoder => optional_text(meta.encoder.as_deref(), "none"),
+        Metric::CreationTime => optional_text(meta.creation_time.as_deref(), "none"),
+        Metric::FormatTags => {
+            let tags = meta
+                .format_tags
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join("; ");
+            optional_text(Some(&tags), "none")
+        }
+        Metric::Chapters => count_items(meta.chapters.len()),
+        Metric::BlackSegments => qc_count(meta, QcKind::Black),
+        Metric::FreezeSegments => qc_count(meta, QcKind::Freeze),
+        Metric::SilenceSegments => qc_count(meta, QcKind::Silence),
+        Metric::ActivePicture => match &meta.crop {
+            Some(c) => MetricValue {
+                text: None,
+                number: None,
+                size: Some((c.width, c.height)),
+                display: format!("{}x{}", c.width, c.height),
+            },
+            None => MetricValue::text(None, None, "unknown"),
+        },
+        Metric::ActiveAspect => {
+            MetricValue::number(meta.crop.as_ref().map(|c| c.aspect()), p(4), "")
+        }
+        Metric::Letterbox => yes_no(meta.crop.as_ref().map(|c| c.letterbox)),
+        Metric::Pillarbox => yes_no(meta.crop.as_ref().map(|c| c.pillarbox)),
+        Metric::DetectedScan => optional_text(meta.interlace.as_ref().map(|i| i.scan()), "unknown"),
+        Metric::InterlacedFrames => MetricValue::number(
+            meta.interlace.as_ref().map(|i| f64::from(i.interlaced())),
+            0,
+            "",
+        ),
+    }
+}
+
+fn count_streams(meta: &ExtendedMetadata, pred: impl Fn(&StreamInfo) -> bool) -> MetricValue {
+    count_items(meta.streams.iter().filter(|s| pred(s)).count())
+}
+
+fn count_items(count: usize) -> MetricValue {
+    MetricValue::number(Some(count as f64), 0, "")
+}
+
+/// Missing unless the QC scan ran.
+fn qc_count(meta: &ExtendedMetadata, kind: QcKind) -> MetricValue {
+    MetricValue::number(meta.qc.as_ref().map(|q| q.count(kind) as f64), 0, "")
+}
+
+/// Distinct values across subtitle tracks, comma-separated in stream
+/// order, so presets can match them with `contains`.
+fn subtitle_list(
+    meta: &ExtendedMetadata,
+    field: impl Fn(&StreamInfo) -> Option<&str>,
+) -> MetricValue {
+    let mut values: Vec<&str> = Vec::new();
+    for value in meta
+        .streams
+        .iter()
+        .filter(|s| s.codec_type == "subtitle")
+        .filter_map(field)
+    {
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+    let joined = values.join(", ");
+    optional_text(Some(&joined), "none")
+}
+
+/// Flags match as "yes"/"no"; missing when the source wasn't measured.
+fn yes_no(flag: Option<bool>) -> MetricValue {
+    let raw = flag.map(|f| if f { "yes" } else { "no" });
+    MetricValue::text(raw, raw.map(lc), "-")
+}
+
+fn optional_text(raw: Option<&str>, missing: &str) -> MetricValue {
+    let raw = raw.filter(|s| !s.trim().is_empty());
+    MetricValue::text(raw, raw.map(lc), missing)
+}
+
+// ── Helpers ──
+
+fn lc(s: &str) -> String {
+    s.trim().to_lowercase()
+}
+
+fn normalize_container(c: &str) -> String {
+    let normalized = if c.contains("mp4") {
+        "mp4"
+    } else if c.contains("mov") || c.contains("quicktime") {
+        "mov"
+    } else if c.contains("mxf") {
+        "mxf"
+    } else if c.contains("mkv") || c.contains("matroska") {
+        "mkv"
+    } else if c.contains("webm") {
+        "webm"
+    } else if c.contains("avi") {
+        "avi"
+    } else if c.contains("wmv") {
+        "wmv"
+    } else if c.contains("flv") {
+        "flv"
+    } else {
+        c
+    };
+    normalized.to_string()
+}
+
+fn normalize_codec(c: &str) -> String {
+    let normalized = if c.contains("avc") || c.contains("h264") {
+        "h264"
+    } else if c.contains("hevc") || c.contains("h265") || c == "hvc1" || c == "hev1" {
+        "hevc"
+    } else if c.contains("vp9") {
+        "vp9"
+    } else if c.contains("av01") || c.contains("av1") {
+        "av1"
+    } else if c.contains("prores") {
+        "prores"
+    } else if c.contains("dnxh") {
+        "dnxhd"
+    } else if c.contains("jpeg2000") || c.contains("j2k") {
+        "jpeg2000"
+    } else if c.contains("mpeg2") {
+        "mpeg2"
+    } else {
+        c
+    };
+    normalized.to_string()
+}
+
+fn normalize_image_codec(c: &str) -> String {
+    match c {
+        "mjpeg" | "jpeg" | "jpg" => "jpeg".to_string(),
+        _ => c.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::builtin_presets;
+    use serde_json::{json, Value};
+
+    /// A 30 s 1080x1920 H.264/AAC clip; `overrides` replaces top-level
+    /// fields.
+    fn meta(overrides: Value) -> ExtendedMetadata {
+        let mut base = json!({
+            "width": 1080, "height": 1920, "displayWidth": 1080, "displayHeight": 1920,
+            "fps": 30.0, "fpsAvg": 30.0, "fpsR": 30.0,
+            "videoCodec": "h264", "container": "mov", "durationSec": 30.0,
+            "audioCodec": "aac", "audioSampleRate": 48000, "audioChannels": 2,
+            "videoBitrate": 9_000_000, "profile": "High", "level": 42,
+            "pixFmt": "yuv420p", "fieldOrder": "progressive",
+            "colorPrimaries": "bt709", "colorTransfer": "bt709", "colorSpace": "bt709",
+        });
+        for (key, value) in overrides.as_object().unwrap() {
+            base[key] = value.clone();
+        }
+        serde_json::from_value(base).unwrap()
+    }
+
+    fn preset(id: &str) -> &'static Preset {
+        builtin_presets().iter().find(|p| p.id == id).unwrap()
+    }
+
+    fn check(id: &str, field: &str, meta: &ExtendedMetadata) -> FieldCheck {
+        evaluate(preset(id), meta)
+            .checks
+            .into_iter()
+            .find(|c| c.field == field)
+            .unwrap_or_else(|| panic!("{} has no check {}", id, field))
+    }
+
+    fn cond(source: &str) -> Condition {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            when: Condition,
+        }
+        let wrapper: Wrapper = toml::from_str(&format!("when = {{ {} }}", source)).unwrap();
+        wrapper.when
+    }
+
+    #[test]
+    fn resolve_display_values() {
+        let m = meta(json!({}));
+        assert_eq!(resolve(&m, Metric::Aspect, None).display, "0.5625");
+        assert_eq!(resolve(&m, Metric::Aspect, Some(2)).display, "0.56");
+        assert_eq!(resolve(&m, Metric::Resolution, None).display, "1080x1920");
+        assert_eq!(resolve(&m, Metric::Bitrate, None).display, "9.00 Mbps");
+        assert_eq!(
+            resolve(&m, Metric::Container, None).text.as_deref(),
+            Some("mov")
+        );
+
+        let silent = meta(json!({ "audioCodec": null, "audioSampleRate": null }));
+        let codec = resolve(&silent, Metric::AudioCodec, None);
+        assert_eq!(codec.display, "none");
+        assert!(codec.is_missing());
+        let rate = resolve(&silent, Metric::AudioSampleRate, None);
+        assert_eq!(rate.display, "unknown");
+        assert!(rate.is_missing());
+        assert_eq!(
+            resolve(&silent, Metric::IntegratedLoudness, None).display,
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn matches_conditions() {
+        let m = meta(json!({}));
+        let holds =
+            |metric: Metric, source: &str| matches(&m, &resolve(&m, metric, None), &cond(source));
+
+        assert!(holds(Metric::VideoCodec, r#"in = ["H264", "hevc"]"#));
+        assert!(!holds(Metric::VideoCodec, r#"in = ["hevc"]"#));
+        assert!(holds(Metric::Profile, r#"contains = ["HIGH"]"#));
+        assert!(holds(Metric::Fps, "min = 30.0, max = 30.0"));
+        assert!(!holds(Metric::Fps, "above = 30.0"));
+        assert!(holds(Metric::Fps, "below = 30.01"));
+        assert!(holds(Metric::Fps, "near = [25, 29.995]"));
+        assert!(!holds(Metric::Fps, "near = [29.9], tolerance = 0.05"));
+        assert!(holds(
+            Metric::Resolution,
+            "size = [[1920, 1080], [1080, 1920]]"
+        ));
+        assert!(holds(Metric::Resolution, "minSize = [1080, 1920]"));
+        assert!(!holds(Metric::Resolution, "minSize = [1081, 0]"));
+        assert!(holds(Metric::Title, "missing = true"));
+        assert!(!holds(Metric::Fps, "missing = true"));
+        // A condition's own metric overrides the check's.
+        assert!(holds(
+            Metric::Fps,
+            r#"metric = "audio_codec", in = ["aac"]"#
+        ));
+        assert!(holds(
+            Metric::Fps,
+            r#"min = 29, all = [{ metric = "audio_channels", eq = 2 }], any = [{ max = 1 }, { max = 31 }]"#
+        ));
+        assert!(!holds(Metric::Fps, "any = [{ max = 1 }, { min = 31 }]"));
+    }
+
+    #[test]
+    fn numeric_bounds_fail_when_the_metric_is_unknown() {
+        let m = meta(json!({ "audioSampleRate": null }));
+        let rate = resolve(&m, Metric::AudioSampleRate, None);
+        assert!(!matches(&m, &rate, &cond("min = 0")));
+        assert!(!matches(&m, &rate, &cond("below = 1e9")));
+        assert!(matches(&m, &rate, &cond("missing = true")));
+    }
+
+    #[test]
+    fn expand_placeholders() {
+        let m = meta(json!({}));
+        let value = resolve(&m, Metric::Container, None);
+        assert_eq!(
+            expand("{value} ({norm}) at {fps} fps, {audio_codec}", &m, &value),
+            "mov (MOV) at 30.00 fps, aac"
+        );
+        assert_eq!(expand("no placeholders", &m, &value), "no placeholders");
+    }
+
+    #[test]
+    fn ig_reels_aspect_tolerance() {
+        // width / 10000 lands exactly on the bounds.
+        let aspect = |width: u32| {
+            check(
+                "ig_reels",
+                "Aspect",
+                &meta(json!({ "displayWidth": width, "displayHeight": 10000 })),
+            )
+            .ok
+        };
+        assert!(!aspect(5524));
+        assert!(aspect(5525));
+        assert!(aspect(5625));
+        assert!(aspect(5725));
+        assert!(!aspect(5726));
+    }
+
+    #[test]
+    fn missing_audio_is_shown_and_fails() {
+        let m = meta(json!({ "audioCodec": null, "audioSampleRate": null }));
+        let codec = check("ig_reels", "Audio Codec", &m);
+        assert_eq!(codec.value, "none");
+        assert!(!codec.ok);
+        let rate = check("ig_reels", "Audio Hz", &m);
+        assert_eq!(rate.value, "unknown");
+        assert!(!rate.ok);
+    }
+
+    #[test]
+    fn builtin_boundaries() {
+        let cases: &[(&str, &str, Value, bool)] = &[
+            (
+                "ig_reels",
+                "Duration",
+                json!({ "durationSec": 180.0 }),
+                true,
+            ),
+            (
+                "ig_reels",
+                "Duration",
+                json!({ "durationSec": 180.01 }),
+                false,
+            ),
+            ("ig_reels", "Duration", json!({ "durationSec": 0.0 }), false),
+            ("ig_reels", "FPS", json!({ "fpsAvg": 29.9 }), true),
+            ("ig_reels", "FPS", json!({ "fpsAvg": 29.89 }), false),
+            (
+                "ig_reels",
+                "Bitrate",
+                json!({ "videoBitrate": 8_500_000 }),
+                true,
+            ),
+            (
+                "ig_reels",
+                "Bitrate",
+                json!({ "videoBitrate": 10_000_001 }),
+                false,
+            ),
+            (
+                "ig_reels",
+                "Bitrate",
+                json!({ "durationSec": 45.0, "videoBitrate": 9_500_000 }),
+                false,
+            ),
+            (
+                "ig_reels",
+                "Audio Hz",
+                json!({ "audioSampleRate": 44100 }),
+                true,
+            ),
+            (
+                "ig_reels",
+                "Audio Hz",
+                json!({ "audioSampleRate": 32000 }),
+                false,
+            ),
+            ("ig_post", "Duration", json!({ "durationSec": 60.0 }), true),
+            ("ig_post", "Duration", json!({ "durationSec": 90.0 }), false),
+            ("ig_story", "Duration", json!({ "durationSec": 60.0 }), true),
+            (
+                "ig_story",
+                "Duration",
+                json!({ "durationSec": 61.0 }),
+                false,
+            ),
+            (
+                "tiktok",
+                "Resolution",
+                json!({ "displayWidth": 720, "displayHeight": 1280 }),
+                true,
+            ),
+            (
+                "tiktok",
+                "Resolution",
+                json!({ "displayWidth": 719, "displayHeight": 1280 }),
+                false,
+            ),
+            (
+                "yt_shorts",
+                "Resolution",
+                json!({ "displayWidth": 720, "displayHeight": 1280 }),
+                true,
+            ),
+            ("youtube", "FPS", json!({ "fpsAvg": 120.0 }), true),
+            ("youtube", "FPS", json!({ "fpsAvg": 121.0 }), false),
+            ("mxf_pal", "FPS", json!({ "fpsAvg": 25.0 }), true),
+            ("mxf_pal", "FPS", json!({ "fpsAvg": 29.97 }), false),
+            (
+                "mxf_pal",
+                "Loudness",
+                json!({ "loudness": { "integratedLufs": -23.5 } }),
+                true,
+            ),
+            (
+                "mxf_pal",
+                "Loudness",
+                json!({ "loudness": { "integratedLufs": -23.6 } }),
+                false,
+            ),
+            (
+                "mxf_pal",
+                "True Peak",
+                json!({ "loudness": { "truePeakDbtp": -1.0 } }),
+                true,
+            ),
+            (
+                "mxf_pal",
+                "True Peak",
+                json!({ "loudness": { "truePeakDbtp": -0.9 } }),
+                false,
+            ),
+            // Digital silence has no true peak.
+            ("mxf_pal", "True Peak", json!({ "loudness": {} }), true),
+            (
+                "mxf_ntsc",
+                "Loudness",
+                json!({ "loudness": { "integratedLufs": -22.0 } }),
+                true,
+            ),
+            (
+                "mxf_ntsc",
+                "Loudness",
+                json!({ "loudness": { "integratedLufs": -21.9 } }),
+                false,
+            ),
+            (
+                "cinema",
+                "Resolution",
+                json!({ "displayWidth": 1920, "displayHeight": 1080 }),
+                true,
+            ),
+            (
+                "cinema",
+                "Resolution",
+                json!({ "displayWidth": 1280, "displayHeight": 720 }),
+                false,
+            ),
+            (
+                "dcp",
+                "Resolution",
+                json!({ "width": 2048, "height": 858 }),
+                true,
+            ),
+            (
+                "dcp",
+                "Resolution",
+                json!({ "width": 1920, "height": 1080 }),
+                false,
+            ),
+            ("dcp", "Audio Channels", json!({ "audioChannels": 6 }), true),
+            (
+                "dcp",
+                "Audio Channels",
+                json!({ "audioChannels": 2 }),
+                false,
+            ),
+            (
+                "ig_post_image",
+                "Aspect",
+                json!({ "displayWidth": 1080, "displayHeight": 1350 }),
+                true,
+            ),
+            (
+                "ig_story_image",
+                "Resolution",
+                json!({ "displayWidth": 720, "displayHeight": 1280 }),
+                true,
+            ),
+            (
+                "ig_story_image",
+                "Resolution",
+                json!({ "displayWidth": 720, "displayHeight": 1279 }),
+                false,
+            ),
+            (
+                "ig_reels_cover",
+                "Resolution",
+                json!({ "displayWidth": 420, "displayHeight": 654 }),
+                true,
+            ),
+            (
+                "ig_reels_cover",
+                "Resolution",
+                json!({ "displayWidth": 419, "displayHeight": 654 }),
+                false,
+            ),
+            (
+                "fb_cover",
+                "Resolution",
+                json!({ "displayWidth": 820, "displayHeight": 312 }),
+                true,
+            ),
+            (
+                "fb_cover",
+                "Resolution",
+                json!({ "displayWidth": 819, "displayHeight": 312 }),
+                false,
+            ),
+            (
+                "linkedin_banner",
+                "Resolution",
+                json!({ "displayWidth": 1200, "displayHeight": 300 }),
+                true,
+            ),
+            (
+                "linkedin_banner",
+                "Resolution",
+                json!({ "displayWidth": 1199, "displayHeight": 300 }),
+                false,
+            ),
+        ];
+        for (id, field, overrides, ok) in cases {
+            let result = check(id, field, &meta(overrides.clone()));
+            assert_eq!(
+                result.ok, *ok,
+                "{} {} with {}: got {:?}",
+                id, field, overrides, result
+            );
+        }
+        let covered: Vec<&str> = cases.iter().map(|(id, ..)| *id).collect();
+        for preset in builtin_presets() {
+            assert!(
+                covered.contains(&preset.id.as_str()),
+                "{} untested",
+                preset.id
+            );
+        }
+    }
+
+    #[test]
+    fn every_check_decides_on_unknown_metadata() {
+        let empty = meta(json!({
+            "width": 0, "height": 0, "displayWidth": 0, "displayHeight": 0,
+            "fps": 0.0, "fpsAvg": 0.0, "fpsR": 0.0, "videoCodec": "unknown",
+            "container": "unknown", "durationSec": 0.0, "audioCodec": null,
+            "audioSampleRate": null, "audioChannels": null, "videoBitrate": null,
+            "profile": null, "level": null, "pixFmt": null, "fieldOrder": null,
+        }));
+        for preset in builtin_presets() {
+            let result = evaluate(preset, &empty);
+            assert_eq!(result.checks.len(), preset.checks.len(), "{}", preset.id);
+            assert_eq!(result.verdict, Verdict::Flawed, "{}", preset.id);
+        }
+    }
+
+    #[test]
+    fn frame_rate_mode_comes_from_packet_timing() {
+        let timing = |vfr: bool| {
+            json!({
+                "frames": 900, "vfr": vfr, "measuredFps": 29.97,
+                "minIntervalMs": 25.3, "maxIntervalMs": 41.3, "stdevIntervalMs": 5.6,
+                "droppedFrames": 0, "duplicatedFrames": 0,
+            })
+        };
+        let mode = |overrides: Value| {
+            let m = meta(overrides);
+            resolve(&m, Metric::FrameRateMode, None).display
+        };
+        // The header rates disagree either way; only the packets decide.
+        assert_eq!(
+            mode(json!({ "fpsR": 30.0, "fpsAvg": 29.1, "frameTiming": timing(true) })),
+            "VFR"
+        );
+        assert_eq!(
+            mode(json!({ "fpsR": 30.0, "fpsAvg": 29.1, "frameTiming": timing(false) })),
+            "CFR"
+        );
+        assert_eq!(mode(json!({ "fpsR": 30.0, "fpsAvg": 29.1 })), "unknown");
+    }
+}
@@ -0,0 +1,152 @@
+//! Start timecode from ffprobe tags. QuickTime/MP4 files carry it on a
+//! `tmcd` data stream, MXF on the material package (format tags), and
+//! MPEG-2 and some MOV writers on the video stream itself.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timecode {
+    /// Start timecode as written, e.g. "10:00:00:00" or "01:00:00;00".
+    pub start: String,
+    /// Drop-frame counting, written with ';' (or '.') before the frames.
+    pub drop_frame: bool,
+    /// Nominal frames per timecode second (30 for 29.97 fps).
+    pub rate: u32,
+    /// Where the timecode was found: "tmcd", "video" or "format".
+    pub source: String,
+}
+
+impl Timecode {
+    /// Start timecode with ':' separators throughout, for comparisons.
+    pub fn normalized(&self) -> String {
+        self.start.replace([';', '.'], ":")
+    }
+}
+
+/// Find the start timecode in ffprobe output. `fps` is the video frame
+/// rate, used for the timecode rate when the timecode track has none.
+pub fn extract(probe: &Value, fps: f64) -> Option<Timecode> {
+    let streams = probe["streams"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    let tmcd = streams
+        .iter()
+        .find(|s| s["codec_tag_string"].as_str() == Some("tmcd"));
+    let video = streams.iter().find(|s| {
+        s["codec_type"].as_str() == Some("video") && s["disposition"]["attached_pic"] != 1
+    });
+
+    let (tag, source, track) = [
+        (tmcd, "tmcd"),
+        (video, "video"),
+        (Some(&probe["format"]), "format"),
+    ]
+    .into_iter()
+    .find_map(|(holder, source)| {
+        let holder = holder?;
+        let tag = holder["tags"]["timecode"]
+            .as_str()
+            .filter(|t| is_timecode(t))?;
+        Some((tag, source, holder))
+    })?;
+
+    // A tmcd track states its own rate; otherwise round the video rate up
+    // to the nominal one (29.97 counts as 30).
+    let track_rate = track["avg_frame_rate"]
+        .as_str()
+        .or(track["r_frame_rate"].as_str())
+        .and_then(parse_rate)
+        .filter(|r| *r > 0.0);
+    let rate = track_rate.unwrap_or(fps).round() as u32;
+
+    Some(Timecode {
+        start: tag.trim().to_string(),
+        drop_frame: tag.contains([';', '.']),
+        rate,
+        source: source.to_string(),
+    })
+}
+
+/// HH:MM:SS:FF with ':' or, before the frames, ';' / '.'.
+fn is_timecode(tag: &str) -> bool {
+    let tag = tag.trim();
+    let parts: Vec<&str> = tag.split([':', ';', '.']).collect();
+    parts.len() == 4
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.len() <= 3 && p.bytes().all(|b| b.is_ascii_digit()))
+        && !tag[..tag.rfind([':', ';', '.']).unwrap_or(0)].contains([';', '.'])
+}
+
+fn parse_rate(rate: &str) -> Option<f64> {
+    let (num, den) = rate.split_once('/')?;
+    let (num, den) = (num.parse::<f64>().ok()?, den.parse::<f64>().ok()?);
+    (den > 0.0).then(|| num / den)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn prefers_the_tmcd_track() {
+        let probe = json!({
+            "streams": [
+                { "codec_type": "video", "r_frame_rate": "30000/1001",
+                  "tags": { "timecode": "00:59:50:00" } },
+                { "codec_type": "data", "codec_tag_string": "tmcd",
+                  "avg_frame_rate": "30000/1001", "tags": { "timecode": "01:00:00;00" } },
+            ],
+            "format": { "tags": { "timecode": "02:00:00:00" } },
+        });
+        let tc = extract(&probe, 29.97).unwrap();
+        assert_eq!(tc.start, "01:00:00;00");
+        assert_eq!(tc.normalized(), "01:00:00:00");
+        assert!(tc.drop_frame);
+        assert_eq!(tc.rate, 30);
+        assert_eq!(tc.source, "tmcd");
+    }
+
+    #[test]
+    fn falls_back_to_video_then_format() {
+        let probe = json!({
+            "streams": [
+                { "codec_type": "video", "disposition": { "attached_pic": 1 },
+                  "tags": { "timecode": "09:00:00:00" } },
+                { "codec_type": "video", "tags": { "timecode": "10:00:00:00" } },
+            ],
+        });
+        let tc = extract(&probe, 25.0).unwrap();
+        assert_eq!(tc.start, "10:00:00:00");
+        assert_eq!(tc.source, "video");
+        assert_eq!(tc.rate, 25);
+        assert!(!tc.drop_frame);
+
+        let probe = json!({
+            "streams": [{ "codec_type": "video" }],
+            "format": { "tags": { "timecode": "10:00:00.00" } },
+        });
+        let tc = extract(&probe, 59.94).unwrap();
+        assert_eq!(tc.source, "format");
+        assert_eq!(tc.rate, 60);
+        assert!(tc.drop_frame);
+    }
+
+    #[test]
+    fn ignores_malformed_tags() {
+        for tag in [
+            "",
+            "10:00:00",
+            "10;00:00:00",
+            "aa:00:00:00",
+            "10:00:00:00:00",
+        ] {
+            let probe = json!({ "streams": [], "format": { "tags": { "timecode": tag } } });
+            assert!(extract(&probe, 25.0).is_none(), "{:?}", tag);
+        }
+    }
+}
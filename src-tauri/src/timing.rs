@@ -0,0 +1,179 @@
+//! Frame timing from packet timestamps. `r_frame_rate` and `avg_frame_rate`
+//! are header values and often report phone footage as constant; the packet
+//! timestamps show what a player will actually do.
+
+use serde::{Deserialize, Serialize};
+
+use crate::packets::Packet;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameTiming {
+    pub frames: u64,
+    /// True when some frame intervals are neither the nominal interval nor a
+    /// whole multiple of it (a drop).
+    pub vfr: bool,
+    /// Average frame rate over the stream, counting dropped frames.
+    pub measured_fps: f64,
+    pub min_interval_ms: f64,
+    pub max_interval_ms: f64,
+    pub stdev_interval_ms: f64,
+    /// Frames missing from gaps that are a whole multiple of the interval.
+    pub dropped_frames: u64,
+    /// Frames sharing (almost) the same timestamp as the previous one.
+    pub duplicated_frames: u64,
+}
+
+/// Share of irregular intervals above which a stream counts as VFR. Leaves
+/// room for a stray glitch at an edit point.
+const VFR_SHARE: f64 = 0.001;
+
+/// Build the timing summary from the video packets. Returns `None` when
+/// there are too few timestamps to judge.
+pub fn analyze(packets: &[Packet]) -> Option<FrameTiming> {
+    let mut pts: Vec<f64> = packets.iter().filter_map(Packet::time).collect();
+    // Packets arrive in decode order; B-frames put presentation out of order.
+    pts.sort_by(f64::total_cmp);
+    if pts.len() < 3 {
+        return None;
+    }
+
+    let intervals: Vec<f64> = pts.windows(2).map(|w| w[1] - w[0]).collect();
+    let mut sorted = intervals.clone();
+    sorted.sort_by(f64::total_cmp);
+    let nominal = sorted[sorted.len() / 2];
+    if nominal <= 0.0 {
+        return None;
+    }
+    // Millisecond timebases (MKV, WebM) round 29.97 fps to 33/34 ms, so
+    // allow 1.5 ms or 1%, whichever is larger.
+    let tolerance = (0.0015f64).max(nominal * 0.01);
+
+    let mut dropped = 0;
+    let mut duplicated = 0;
+    let mut irregular = 0;
+    for &interval in &intervals {
+        let multiple = (interval / nominal).round();
+        if (interval - nominal).abs() <= tolerance {
+            continue;
+        } else if interval < nominal * 0.5 {
+            duplicated += 1;
+        } else if multiple >= 2.0 && (interval - multiple * nominal).abs() <= tolerance {
+            dropped += multiple as u64 - 1;
+        } else {
+            irregular += 1;
+        }
+    }
+
+    let count = intervals.len() as f64;
+    let span = pts[pts.len() - 1] - pts[0];
+    let slots = (intervals.len() as u64 + dropped - duplicated) as f64;
+    let mean = intervals.iter().sum::<f64>() / count;
+    let variance = intervals.iter().map(|i| (i - mean).powi(2)).sum::<f64>() / count;
+    Some(FrameTiming {
+        frames: pts.len() as u64,
+        vfr: irregular as f64 > (count * VFR_SHARE).floor(),
+        measured_fps: (slots / span * 1000.0).round() / 1000.0,
+        min_interval_ms: sorted[0] * 1000.0,
+        max_interval_ms: sorted[sorted.len() - 1] * 1000.0,
+        stdev_interval_ms: variance.sqrt() * 1000.0,
+        dropped_frames: dropped,
+        duplicated_frames: duplicated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packets(times: &[f64]) -> Vec<Packet> {
+        times
+            .iter()
+            .map(|&t| Packet {
+                pts: Some(t),
+                dts: None,
+                key: false,
+            })
+            .collect()
+    }
+
+    fn frames(count: usize, fps: f64) -> Vec<f64> {
+        (0..count).map(|i| i as f64 / fps).collect()
+    }
+
+    #[test]
+    fn constant_frame_rate() {
+        let timing = analyze(&packets(&frames(300, 30.0))).unwrap();
+        assert_eq!(timing.frames, 300);
+        assert!(!timing.vfr);
+        assert_eq!(timing.measured_fps, 30.0);
+        assert_eq!(timing.dropped_frames, 0);
+        assert_eq!(timing.duplicated_frames, 0);
+        assert!(timing.stdev_interval_ms < 1e-6);
+    }
+
+    #[test]
+    fn decode_order_is_sorted_by_presentation() {
+        let mut times = frames(300, 25.0);
+        times.swap(1, 3);
+        times.swap(4, 6);
+        let timing = analyze(&packets(&times)).unwrap();
+        assert!(!timing.vfr);
+        assert_eq!(timing.measured_fps, 25.0);
+    }
+
+    #[test]
+    fn gaps_are_dropped_frames() {
+        let mut times = frames(300, 30.0);
+        times.remove(100);
+        times.remove(200);
+        times.remove(200);
+        let timing = analyze(&packets(&times)).unwrap();
+        assert!(!timing.vfr);
+        assert_eq!(timing.dropped_frames, 3);
+        // Dropped slots still count towards the rate.
+        assert_eq!(timing.measured_fps, 30.0);
+    }
+
+    #[test]
+    fn repeated_timestamps_are_duplicates() {
+        let mut times = frames(300, 30.0);
+        times.insert(50, times[50] + 0.001);
+        let timing = analyze(&packets(&times)).unwrap();
+        assert_eq!(timing.duplicated_frames, 1);
+    }
+
+    #[test]
+    fn irregular_intervals_are_vfr() {
+        let mut t = 0.0;
+        let times: Vec<f64> = (0..300)
+            .map(|i| {
+                t += if i % 3 == 0 { 1.0 / 24.0 } else { 1.0 / 30.0 };
+                t
+            })
+            .collect();
+        assert!(analyze(&packets(&times)).unwrap().vfr);
+    }
+
+    #[test]
+    fn millisecond_timebases_are_cfr() {
+        // 29.97 fps in a 1 ms timebase alternates 33 and 34 ms intervals.
+        let times: Vec<f64> = (0..300)
+            .map(|i| (i as f64 * 1001.0 / 30.0).round() / 1000.0)
+            .collect();
+        let timing = analyze(&packets(&times)).unwrap();
+        assert!(!timing.vfr);
+        assert_eq!(timing.dropped_frames, 0);
+    }
+
+    #[test]
+    fn too_few_timestamps() {
+        assert!(analyze(&packets(&[0.0, 0.04])).is_none());
+        let untimed = [Packet {
+            pts: None,
+            dts: None,
+            key: true,
+        }; 10];
+        assert!(analyze(&untimed).is_none());
+    }
+}
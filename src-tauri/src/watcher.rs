@@ -0,0 +1,526 @@
+use notify::event::ModifyKind;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
+
+use crate::error::ExportDoctorError;
+use crate::rules::{PresetResult, Verdict};
+use crate::{batch, evaluate_and_record, license, presets, AnalyzeResult};
+
+const STORE_FILE: &str = "watch.json";
+const STORE_KEY: &str = "config";
+
+/// Emitted for every file the watcher analyzes.
+pub const RESULT_EVENT: &str = "watch://result";
+
+/// How often a growing file's size is sampled, and how many identical
+/// samples in a row mean the render has finished.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const STABLE_SAMPLES: u32 = 3;
+
+/// Upper bound on remembered renders. Past it, entries for files that are
+/// gone are dropped first, then the least recently modified.
+const MAX_ANALYZED: usize = 4096;
+
+// ── Structs ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchConfig {
+    pub enabled: bool,
+    pub folders: Vec<String>,
+    pub preset_ids: Vec<String>,
+    #[serde(default = "default_true")]
+    pub recursive: bool,
+    /// Show a desktop notification in addition to the event.
+    #[serde(default = "default_true")]
+    pub notify: bool,
+    /// Run the black/freeze/silence scan on every finished render.
+    #[serde(default)]
+    pub deep: bool,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        WatchConfig {
+            enabled: false,
+            folders: Vec::new(),
+            preset_ids: Vec::new(),
+            recursive: true,
+            notify: true,
+            deep: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchResult {
+    pub path: String,
+    pub verdict: Option<Verdict>,
+    pub analysis: Option<AnalyzeResult>,
+    pub results: Vec<PresetResult>,
+    pub error: Option<ExportDoctorError>,
+}
+
+/// (size, mtime) of a file when it was last analyzed.
+type Fingerprint = (u64, Option<SystemTime>);
+
+/// Managed state: the live watcher plus bookkeeping that keeps one file from
+/// being analyzed twice for the same render.
+#[derive(Default)]
+pub struct WatchState {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    /// Bumped whenever the watcher is replaced or stopped; waiters queued
+    /// under an older generation give up.
+    generation: Arc<AtomicU64>,
+    /// Files with a waiter, and the generation it was queued under.
+    pending: Arc<Mutex<HashMap<PathBuf, u64>>>,
+    analyzed: Arc<Mutex<HashMap<PathBuf, Fingerprint>>>,
+}
+
+/// Takes a file off `pending` when its waiter ends, however it ends, unless
+/// a newer generation's waiter has taken its place.
+struct PendingGuard {
+    pending: Arc<Mutex<HashMap<PathBuf, u64>>>,
+    path: PathBuf,
+    generation: u64,
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            if pending.get(&self.path) == Some(&self.generation) {
+                pending.remove(&self.path);
+            }
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// ── Config ──
+
+fn read_config(app: &tauri::AppHandle) -> WatchConfig {
+    let store = app.store(STORE_FILE).unwrap_or_else(|_| {
+        app.store_builder(STORE_FILE)
+            .build()
+            .expect("failed to build store")
+    });
+    store
+        .get(STORE_KEY)
+        .and_then(|val| serde_json::from_value(val).ok())
+        .unwrap_or_default()
+}
+
+fn save_config(app: &tauri::AppHandle, config: &WatchConfig) -> Result<(), ExportDoctorError> {
+    let store = app.store(STORE_FILE).unwrap_or_else(|_| {
+        app.store_builder(STORE_FILE)
+            .build()
+            .expect("failed to build store")
+    });
+    let val = serde_json::to_value(config).map_err(ExportDoctorError::storage)?;
+    store.set(STORE_KEY, val);
+    store.save().map_err(ExportDoctorError::storage)
+}
+
+fn validate_config(app: &tauri::AppHandle, config: &WatchConfig) -> Result<(), ExportDoctorError> {
+    if let Some(folder) = config.folders.iter().find(|f| !Path::new(f).is_dir()) {
+        return Err(ExportDoctorError::InvalidInput {
+            detail: format!("Not a folder: {}", folder),
+        });
+    }
+    let catalog = presets::catalog(app);
+    if let Some(id) = config
+        .preset_ids
+        .iter()
+        .find(|id| !catalog.iter().any(|p| &p.id == *id))
+    {
+        return Err(ExportDoctorError::UnknownPreset { id: id.clone() });
+    }
+    if config.enabled && config.preset_ids.is_empty() {
+        return Err(ExportDoctorError::InvalidInput {
+            detail: "Select at least one preset to watch with".to_string(),
+        });
+    }
+    Ok(())
+}
+
+// ── Watcher ──
+
+/// Start watching with the saved configuration. Called once at startup;
+/// a missing folder is logged rather than treated as fatal.
+pub fn restore(app: &tauri::AppHandle) {
+    let config = read_config(app);
+    if let Err(e) = apply(app, &config) {
+        eprintln!("[export-doctor] watch folders not started: {}", e);
+    }
+}
+
+/// Replace the running watcher (if any) with one for `config`. Files still
+/// waiting to finish under the old one are dropped.
+fn apply(app: &tauri::AppHandle, config: &WatchConfig) -> Result<(), ExportDoctorError> {
+    let state = app.state::<WatchState>();
+    let mut slot = state
+        .watcher
+        .lock()
+        .map_err(|e| ExportDoctorError::WatchFailed {
+            detail: e.to_string(),
+        })?;
+    *slot = None;
+    state.generation.fetch_add(1, Ordering::SeqCst);
+    if !config.enabled || config.folders.is_empty() {
+        return Ok(());
+    }
+
+    let handle = app.clone();
+    let mut watcher =
+        notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+            Ok(event) => {
+                if matches!(
+                    event.kind,
+                    EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+                ) {
+                    forget_missing(&handle, &event.paths);
+                }
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    for path in event.paths {
+                        schedule(&handle, path);
+                    }
+                }
+            }
+            Err(e) => eprintln!("[export-doctor] watch error: {}", e),
+        })
+        .map_err(|e| ExportDoctorError::WatchFailed {
+            detail: e.to_string(),
+        })?;
+
+    let mode = if config.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    for folder in &config.folders {
+        watcher
+            .watch(Path::new(folder), mode)
+            .map_err(|e| ExportDoctorError::WatchFailed {
+                detail: format!("{}: {}", folder, e),
+            })?;
+    }
+    eprintln!(
+        "[export-doctor] watching {} folder(s)",
+        config.folders.len()
+    );
+    *slot = Some(watcher);
+    Ok(())
+}
+
+/// Queue a changed file. Each file gets at most one waiter at a time; the
+/// waiter analyzes it once its size stops changing.
+fn schedule(app: &tauri::AppHandle, path: PathBuf) {
+    if !path.is_file() || !batch::is_media_file(&path) {
+        return;
+    }
+    let state = app.state::<WatchState>();
+    let generation = state.generation.clone();
+    let started = generation.load(Ordering::SeqCst);
+    let pending = state.pending.clone();
+    let analyzed = state.analyzed.clone();
+    let queued = pending
+        .lock()
+        .map(|mut p| match p.get(&path) {
+            Some(queued) if *queued == started => false,
+            _ => {
+                p.insert(path.clone(), started);
+                true
+            }
+        })
+        .unwrap_or(false);
+    if !queued {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _guard = PendingGuard {
+            pending,
+            path: path.clone(),
+            generation: started,
+        };
+        let fingerprint = wait_until_stable(&path, &generation, started).await;
+        let unchanged = fingerprint.is_some_and(|fp| {
+            analyzed
+                .lock()
+                .map(|done| done.get(&path) == Some(&fp))
+                .unwrap_or(false)
+        });
+        if let (Some(fp), false) = (fingerprint, unchanged) {
+            let result = check(&app, &path).await;
+            report(&app, &result);
+            record(&analyzed, path, fp).await;
+        }
+    });
+}
+
+/// Drop deleted or renamed-away files from the analyzed set. A file that
+/// reappears under the same name is then checked again.
+fn forget_missing(app: &tauri::AppHandle, paths: &[PathBuf]) {
+    let gone: Vec<&PathBuf> = paths.iter().filter(|p| !p.exists()).collect();
+    if gone.is_empty() {
+        return;
+    }
+    let analyzed = app.state::<WatchState>().analyzed.clone();
+    if let Ok(mut done) = analyzed.lock() {
+        for path in gone {
+            done.remove(path);
+        }
+    };
+}
+
+/// Remember an analyzed render. When the set is full, the files that are
+/// gone are looked up before taking the lock, not while holding it.
+async fn record(analyzed: &Mutex<HashMap<PathBuf, Fingerprint>>, path: PathBuf, fp: Fingerprint) {
+    let known: Vec<PathBuf> = analyzed
+        .lock()
+        .map(|done| {
+            if done.len() >= MAX_ANALYZED {
+                done.keys().cloned().collect()
+            } else {
+                Vec::new()
+            }
+        })
+        .unwrap_or_default();
+    let missing = if known.is_empty() {
+        HashSet::new()
+    } else {
+        tauri::async_runtime::spawn_blocking(move || {
+            known.into_iter().filter(|p| !p.is_file()).collect()
+        })
+        .await
+        .unwrap_or_default()
+    };
+    if let Ok(mut done) = analyzed.lock() {
+        remember(&mut done, path, fp, &missing);
+    }
+}
+
+/// Past `MAX_ANALYZED`, drop the `missing` files first, then the least
+/// recently modified.
+fn remember(
+    done: &mut HashMap<PathBuf, Fingerprint>,
+    path: PathBuf,
+    fp: Fingerprint,
+    missing: &HashSet<PathBuf>,
+) {
+    done.insert(path, fp);
+    if done.len() <= MAX_ANALYZED {
+        return;
+    }
+    done.retain(|p, _| !missing.contains(p));
+    while done.len() > MAX_ANALYZED {
+        let Some(oldest) = done
+            .iter()
+            .min_by_key(|(_, (_, modified))| *modified)
+            .map(|(p, _)| p.clone())
+        else {
+            break;
+        };
+        done.remove(&oldest);
+    }
+}
+
+/// Poll until the file size has been identical for `STABLE_SAMPLES` polls.
+/// Returns `None` if the file disappears (e.g. a renderer's temp file), if
+/// it stays empty (its first write queues it again), or once the watcher
+/// moves past `started`.
+async fn wait_until_stable(
+    path: &Path,
+    generation: &AtomicU64,
+    started: u64,
+) -> Option<Fingerprint> {
+    let mut last = None;
+    let mut stable = 0;
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        if generation.load(Ordering::SeqCst) != started {
+            return None;
+        }
+        let meta = tokio::fs::metadata(path).await.ok()?;
+        let size = meta.len();
+        if last == Some(size) {
+            stable += 1;
+            if stable >= STABLE_SAMPLES {
+                return (size > 0).then(|| (size, meta.modified().ok()));
+            }
+        } else {
+            stable = 0;
+            last = Some(size);
+        }
+    }
+}
+
+async fn check(app: &tauri::AppHandle, path: &Path) -> WatchResult {
+    let config = read_config(app);
+    let path_str = path.to_string_lossy().to_string();
+    let outcome = match license::check_license_valid(app) {
+        Ok(()) => {
+            let catalog = presets::catalog(app);
+            evaluate_and_record(app, &path_str, &catalog, &config.preset_ids, config.deep).await
+        }
+        Err(e) => Err(e),
+    };
+    match outcome {
+        Ok((analysis, results)) => {
+            let verdict = if results.iter().any(|r| r.result.verdict == Verdict::Flawed) {
+                Verdict::Flawed
+            } else {
+                Verdict::Perfect
+            };
+            WatchResult {
+                path: path_str,
+                verdict: Some(verdict),
+                analysis: Some(analysis),
+                results,
+                error: None,
+            }
+        }
+        Err(e) => WatchResult {
+            path: path_str,
+            verdict: None,
+            analysis: None,
+            results: Vec::new(),
+            error: Some(e),
+        },
+    }
+}
+
+fn report(app: &tauri::AppHandle, result: &WatchResult) {
+    if let Err(e) = app.emit(RESULT_EVENT, result) {
+        eprintln!("[export-doctor] failed to emit watch result: {}", e);
+    }
+    if !read_config(app).notify {
+        return;
+    }
+
+    let file_name = Path::new(&result.path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| result.path.clone());
+    let (title, body) = match (&result.verdict, &result.error) {
+        (Some(Verdict::Perfect), _) => (
+            format!("PERFECT — {}", file_name),
+            "Passes every watched preset".to_string(),
+        ),
+        (Some(Verdict::Flawed), _) => {
+            let failed: Vec<&str> = result
+                .results
+                .iter()
+                .filter(|r| r.result.verdict == Verdict::Flawed)
+                .map(|r| r.platform_name.as_str())
+                .collect();
+            (
+                format!("FLAWED — {}", file_name),
+                format!("Fails: {}", failed.join(", ")),
+            )
+        }
+        (None, error) => (
+            format!("Could not check {}", file_name),
+            error.as_ref().map(|e| e.to_string()).unwrap_or_default(),
+        ),
+    };
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("[export-doctor] notification failed: {}", e);
+    }
+}
+
+// ── Commands ──
+
+#[tauri::command]
+pub async fn get_watch_config(app: tauri::AppHandle) -> Result<WatchConfig, ExportDoctorError> {
+    Ok(read_config(&app))
+}
+
+/// Validate, persist and apply a new watch configuration.
+#[tauri::command]
+pub async fn set_watch_config(
+    app: tauri::AppHandle,
+    config: WatchConfig,
+) -> Result<WatchConfig, ExportDoctorError> {
+    validate_config(&app, &config)?;
+    apply(&app, &config)?;
+    save_config(&app, &config)?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(i: usize) -> PathBuf {
+        PathBuf::from(format!("/renders/{}.mov", i))
+    }
+
+    fn modified_at(secs: u64) -> Fingerprint {
+        (1, Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs)))
+    }
+
+    fn full() -> HashMap<PathBuf, Fingerprint> {
+        (0..MAX_ANALYZED)
+            .map(|i| (render(i), modified_at(1_000 + i as u64)))
+            .collect()
+    }
+
+    #[test]
+    fn remember_keeps_everything_under_the_limit() {
+        let mut done = HashMap::new();
+        let missing = HashSet::from([render(0)]);
+        remember(&mut done, render(0), modified_at(1), &missing);
+        assert!(done.contains_key(&render(0)));
+    }
+
+    #[test]
+    fn remember_evicts_the_least_recently_modified() {
+        let mut done = full();
+        remember(
+            &mut done,
+            render(9_000),
+            modified_at(9_000),
+            &HashSet::new(),
+        );
+        assert_eq!(done.len(), MAX_ANALYZED);
+        assert!(!done.contains_key(&render(0)));
+        assert!(done.contains_key(&render(1)));
+        assert!(done.contains_key(&render(9_000)));
+
+        // No modification time sorts before any.
+        done.insert(render(1), (1, None));
+        remember(
+            &mut done,
+            render(9_001),
+            modified_at(9_001),
+            &HashSet::new(),
+        );
+        assert!(!done.contains_key(&render(1)));
+        assert!(done.contains_key(&render(2)));
+    }
+
+    #[test]
+    fn remember_evicts_missing_files_first() {
+        let mut done = full();
+        let missing = HashSet::from([render(100), render(200)]);
+        remember(&mut done, render(9_000), modified_at(9_000), &missing);
+        assert_eq!(done.len(), MAX_ANALYZED - 1);
+        assert!(!done.contains_key(&render(100)));
+        assert!(!done.contains_key(&render(200)));
+        assert!(done.contains_key(&render(0)));
+    }
+}
@@ -1,35 +1,29 @@
-import { useState, useEffect, useCallback } from 'react';
+import { useState, useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
 import type { ExtendedMetadata, PerfectResult } from '@/lib/rules/evaluate';
-import {
-  evaluateIgReelsPerfect,
-  evaluateTikTok,
-  evaluateYouTube,
-  evaluateYouTubeShorts,
-  evaluateCinema,
-  evaluateIgPost,
-  evaluateIgStory,
-  evaluateMxfPal,
-  evaluateMxfNtsc,
-  evaluateDcp,
-  evaluateIgPostImage,
-  evaluateIgStoryImage,
-  evaluateIgReelsCover,
-  evaluateFacebookCover,
-  evaluateLinkedInBanner,
-} from '@/lib/rules/evaluate';
 import { useLicense } from '@/lib/license';
+import { errorMessage } from '@/lib/errors';
 import FileDropZone from '@/components/FileDropZone';
 import AnalysisProgress from '@/components/AnalysisProgress';
 import PlatformCard from '@/components/PlatformCard';
 import ExportButtons from '@/components/ExportButtons';
 import MetadataGrid from '@/components/MetadataGrid';
+import QcTimeline from '@/components/QcTimeline';
 import FfprobeStatus from '@/components/FfprobeStatus';
 import PresetPicker from '@/components/PresetPicker';
 import UpdateChecker from '@/components/UpdateChecker';
 import AboutDialog from '@/components/AboutDialog';
 import SubscriptionDialog from '@/components/SubscriptionDialog';
+import HistoryDialog from '@/components/HistoryDialog';
+import CompareView from '@/components/CompareView';
+import BatchResults from '@/components/BatchResults';
+import WatchDialog from '@/components/WatchDialog';
+import PresetManagerDialog from '@/components/PresetManagerDialog';
+import type { HistoryEntry, HistorySummary } from '@/lib/history';
+import { compareFiles, type CompareResult, type CompareSource } from '@/lib/compare';
+import { analyzeBatch, type BatchFileResult, type BatchSummary } from '@/lib/batch';
+import { onWatchResult, type WatchResult } from '@/lib/watch';
 
 interface AnalyzeResult {
   metadata: ExtendedMetadata;
@@ -43,28 +37,27 @@ interface PlatformResult {
   result: PerfectResult;
 }
 
-const EVALUATORS: Record<string, {
-  name: string;
-  fn: (meta: ExtendedMetadata) => PerfectResult;
-}> = {
-  ig_reels: { name: 'Instagram Reels', fn: evaluateIgReelsPerfect },
-  ig_post: { name: 'Instagram Post', fn: evaluateIgPost },
-  ig_story: { name: 'Instagram Story', fn: evaluateIgStory },
-  tiktok: { name: 'TikTok', fn: evaluateTikTok },
-  yt_shorts: { name: 'YouTube Shorts', fn: evaluateYouTubeShorts },
-  youtube: { name: 'YouTube', fn: evaluateYouTube },
-  mxf_pal: { name: 'MXF PAL', fn: evaluateMxfPal },
-  mxf_ntsc: { name: 'MXF NTSC', fn: evaluateMxfNtsc },
-  cinema: { name: 'Cinema / Netflix', fn: evaluateCinema },
-  dcp: { name: 'DCP Readiness', fn: evaluateDcp },
-  ig_post_image: { name: 'IG Post Image', fn: evaluateIgPostImage },
-  ig_story_image: { name: 'IG Story Image', fn: evaluateIgStoryImage },
-  ig_reels_cover: { name: 'IG Reels Cover', fn: evaluateIgReelsCover },
-  fb_cover: { name: 'Facebook Cover', fn: evaluateFacebookCover },
-  linkedin_banner: { name: 'LinkedIn Banner', fn: evaluateLinkedInBanner },
-};
-
-type AppPhase = 'upload' | 'pick' | 'loading' | 'results';
+interface EvaluateResult {
+  analysis: AnalyzeResult;
+  results: PlatformResult[];
+}
+
+type AppPhase = 'upload' | 'pick' | 'loading' | 'batch' | 'results';
+
+const MEDIA_EXTENSIONS = ['mp4', 'mov', 'mkv', 'avi', 'wmv', 'flv', 'webm', 'mts', 'm2ts', 'mxf', 'ts',
+                          'jpg', 'jpeg', 'png', 'webp', 'tiff', 'bmp'];
+
+function isMediaFile(path: string): boolean {
+  const extension = path.split('.').pop()?.toLowerCase() ?? '';
+  return MEDIA_EXTENSIONS.includes(extension);
+}
+
+// How many watch-folder results are kept for the watch dialog.
+const MAX_WATCH_RESULTS = 50;
+
+function baseName(path: string): string {
+  return path.split(/[\\/]/).pop() || path;
+}
 
 export default function App() {
   const [ffprobeOk, setFfprobeOk] = useState<boolean | null>(null);
@@ -76,8 +69,24 @@ export default function App() {
   const [selectedPresets, setSelectedPresets] = useState<string[]>([
     'ig_reels', 'tiktok', 'yt_shorts', 'youtube', 'cinema',
   ]);
+  const [deepQc, setDeepQc] = useState(false);
   const [showAbout, setShowAbout] = useState(false);
   const [showSubscription, setShowSubscription] = useState(false);
+  const [showHistory, setShowHistory] = useState(false);
+  const [showWatch, setShowWatch] = useState(false);
+  const [showPresetManager, setShowPresetManager] = useState(false);
+  const [watchResults, setWatchResults] = useState<WatchResult[]>([]);
+  // Set while showing a stored analysis rather than a fresh one.
+  const [recorded, setRecorded] = useState<HistorySummary | null>(null);
+  const [comparison, setComparison] = useState<CompareResult | null>(null);
+  const [comparing, setComparing] = useState(false);
+  const [compareError, setCompareError] = useState<string | null>(null);
+  // Several files or a folder: analyzed as a batch instead of one file.
+  const [batchPaths, setBatchPaths] = useState<string[]>([]);
+  const [batchFiles, setBatchFiles] = useState<BatchFileResult[]>([]);
+  const [batchSummary, setBatchSummary] = useState<BatchSummary | null>(null);
+  // Bumped per batch and on reset, so a batch left behind can't update the screen.
+  const batchRun = useRef(0);
   const { license, startCheckout, activateKey, manage, deactivate } = useLicense();
 
   useEffect(() => {
@@ -86,67 +95,213 @@ export default function App() {
       .catch(() => setFfprobeOk(false));
   }, []);
 
+  useEffect(() => {
+    const unlisten = onWatchResult((result) => {
+      setWatchResults((results) => [result, ...results].slice(0, MAX_WATCH_RESULTS));
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const handleFileSelected = useCallback((path: string) => {
     setFilePath(path);
+    setBatchPaths([]);
+    setBatchFiles([]);
+    setBatchSummary(null);
     setError(null);
     setAnalyzeResult(null);
     setPlatformResults([]);
+    setRecorded(null);
+    setComparison(null);
     setPhase('pick');
   }, []);
 
+  // A single media file opens the usual report; anything else (several
+  // files, a folder) is checked as a batch.
+  const handlePathsSelected = useCallback((paths: string[]) => {
+    if (paths.length === 1 && isMediaFile(paths[0])) {
+      handleFileSelected(paths[0]);
+      return;
+    }
+    setFilePath(null);
+    setBatchPaths(paths);
+    setBatchFiles([]);
+    setBatchSummary(null);
+    setError(null);
+    setAnalyzeResult(null);
+    setPlatformResults([]);
+    setRecorded(null);
+    setComparison(null);
+    setPhase('pick');
+  }, [handleFileSelected]);
+
+  const runBatch = useCallback(async () => {
+    const run = ++batchRun.current;
+    setPhase('batch');
+    setError(null);
+    setBatchFiles([]);
+    setBatchSummary(null);
+
+    try {
+      const summary = await analyzeBatch(
+        batchPaths,
+        selectedPresets,
+        (file) => {
+          if (run === batchRun.current) setBatchFiles((files) => [...files, file]);
+        },
+        { deep: deepQc },
+      );
+      if (run === batchRun.current) setBatchSummary(summary);
+    } catch (err) {
+      if (run !== batchRun.current) return;
+      setError(errorMessage(err));
+      setPhase('pick');
+    }
+  }, [batchPaths, selectedPresets, deepQc]);
+
   const runAnalysis = useCallback(async () => {
+    if (batchPaths.length > 0) {
+      await runBatch();
+      return;
+    }
     if (!filePath) return;
     setPhase('loading');
     setError(null);
 
     try {
-      const result = await invoke<AnalyzeResult>('analyze_video', { path: filePath });
-      setAnalyzeResult(result);
-
-      const results: PlatformResult[] = selectedPresets
-        .filter((id) => EVALUATORS[id])
-        .map((id) => {
-          const ev = EVALUATORS[id];
-          return {
-            platformId: id,
-            platformName: ev.name,
-            result: ev.fn(result.metadata),
-          };
-        });
+      const { analysis, results } = await invoke<EvaluateResult>('evaluate_presets', {
+        path: filePath,
+        presetIds: selectedPresets,
+        deep: deepQc,
+      });
+      setAnalyzeResult(analysis);
       setPlatformResults(results);
+      setRecorded(null);
+      setComparison(null);
+      setCompareError(null);
       setPhase('results');
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
       setPhase('pick');
     }
-  }, [filePath, selectedPresets]);
+  }, [filePath, batchPaths, runBatch, selectedPresets, deepQc]);
 
   async function handleFilePick() {
     const selected = await open({
-      multiple: false,
+      multiple: true,
       directory: false,
-      filters: [
-        {
-          name: 'Media Files',
-          extensions: ['mp4', 'mov', 'mkv', 'avi', 'wmv', 'flv', 'webm', 'mts', 'm2ts', 'mxf', 'ts',
-                       'jpg', 'jpeg', 'png', 'webp', 'tiff', 'bmp'],
-        },
-      ],
+      filters: [{ name: 'Media Files', extensions: MEDIA_EXTENSIONS }],
     });
+    if (selected && selected.length > 0) {
+      handlePathsSelected(selected);
+    }
+  }
+
+  async function handleFolderPick() {
+    const selected = await open({ multiple: false, directory: true });
     if (selected) {
-      handleFileSelected(selected);
+      handlePathsSelected([selected]);
     }
   }
 
   function handleReset() {
+    batchRun.current++;
     setPhase('upload');
     setFilePath(null);
+    setBatchPaths([]);
+    setBatchFiles([]);
+    setBatchSummary(null);
     setAnalyzeResult(null);
     setPlatformResults([]);
+    setRecorded(null);
+    setComparison(null);
     setError(null);
   }
 
+  // Open one batch file in the full report; "Back to batch" returns.
+  function handleOpenBatchFile(file: BatchFileResult) {
+    if (!file.analysis) return;
+    setFilePath(file.path);
+    setAnalyzeResult(file.analysis);
+    setPlatformResults(file.results);
+    setRecorded(null);
+    setComparison(null);
+    setCompareError(null);
+    setPhase('results');
+  }
+
+  function handleBackToBatch() {
+    setFilePath(null);
+    setAnalyzeResult(null);
+    setPlatformResults([]);
+    setComparison(null);
+    setPhase('batch');
+  }
+
+  function handleOpenHistory(entry: HistoryEntry) {
+    setFilePath(entry.path);
+    setAnalyzeResult(entry.analysis);
+    setPlatformResults(entry.results);
+    setRecorded(entry);
+    setComparison(null);
+    setCompareError(null);
+    setError(null);
+    setShowHistory(false);
+    setPhase('results');
+  }
+
+  function handleOpenWatchResult(result: WatchResult) {
+    if (!result.analysis) return;
+    batchRun.current++;
+    setFilePath(result.path);
+    setBatchPaths([]);
+    setBatchFiles([]);
+    setBatchSummary(null);
+    setAnalyzeResult(result.analysis);
+    setPlatformResults(result.results);
+    setRecorded(null);
+    setComparison(null);
+    setCompareError(null);
+    setError(null);
+    setShowWatch(false);
+    setPhase('results');
+  }
+
+  async function runComparison(a: CompareSource, b: CompareSource) {
+    setComparing(true);
+    setCompareError(null);
+    try {
+      setComparison(await compareFiles(a, b, platformResults.map((pr) => pr.platformId), deepQc));
+    } catch (err) {
+      setCompareError(errorMessage(err));
+    } finally {
+      setComparing(false);
+    }
+  }
+
+  // The result on screen is the "before"; the picked file is the re-export.
+  async function handleCompareFile() {
+    if (!filePath) return;
+    const selected = await open({ multiple: false, directory: false });
+    if (!selected) return;
+    const before: CompareSource = recorded ? { history: recorded.id } : { file: filePath };
+    await runComparison(before, { file: selected });
+  }
+
+  // A stored delivery against the file on screen.
+  async function handleCompareHistory(entry: HistorySummary) {
+    if (!filePath) return;
+    setShowHistory(false);
+    await runComparison({ history: entry.id }, { file: filePath });
+  }
+
   function handleChangePresets() {
+    // From a file opened out of a batch, re-check just that file.
+    batchRun.current++;
+    setBatchPaths([]);
+    setBatchFiles([]);
+    setBatchSummary(null);
     setPhase('pick');
     setPlatformResults([]);
   }
@@ -169,6 +324,30 @@ export default function App() {
       <div className="relative z-10 max-w-4xl mx-auto px-4 sm:px-6 py-8 sm:py-12">
         {/* Header */}
         <header className="mb-10 text-center animate-fade-in-up relative">
+          {/* History button — top left */}
+          <button
+            onClick={() => setShowHistory(true)}
+            className="absolute left-0 top-1 p-2 text-[#4b5563] hover:text-[#9ca3af] transition-colors rounded-lg hover:bg-white/[0.04]"
+            title="History"
+          >
+            <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round">
+              <circle cx="12" cy="12" r="10" />
+              <polyline points="12 6 12 12 16 14" />
+            </svg>
+          </button>
+
+          {/* Watch folders button — next to history */}
+          <button
+            onClick={() => setShowWatch(true)}
+            className="absolute left-9 top-1 p-2 text-[#4b5563] hover:text-[#9ca3af] transition-colors rounded-lg hover:bg-white/[0.04]"
+            title="Watch Folders"
+          >
+            <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round">
+              <path d="M22 19a2 2 0 01-2 2H4a2 2 0 01-2-2V5a2 2 0 012-2h5l2 3h9a2 2 0 012 2z" />
+              <circle cx="12" cy="13" r="2" />
+            </svg>
+          </button>
+
           {/* About button — top right */}
           <button
             onClick={() => setShowAbout(true)}
@@ -199,11 +378,11 @@ export default function App() {
 
         {/* Phase: Upload */}
         {phase === 'upload' && (
-          <FileDropZone onFilePick={handleFilePick} onDrop={handleFileSelected} />
+          <FileDropZone onFilePick={handleFilePick} onFolderPick={handleFolderPick} onDrop={handlePathsSelected} />
         )}
 
         {/* Phase: Preset Picker */}
-        {phase === 'pick' && filePath && (
+        {phase === 'pick' && (filePath || batchPaths.length > 0) && (
           <div className="space-y-4">
             {/* File info pill */}
             <div className="text-center animate-fade-in-up">
@@ -213,7 +392,11 @@ export default function App() {
                   <rect x="1" y="5" width="15" height="14" rx="2" ry="2" />
                 </svg>
                 <span className="text-xs text-[#9ca3af] max-w-[300px] truncate">
-                  {filePath.split('/').pop() || filePath}
+                  {filePath
+                    ? baseName(filePath)
+                    : batchPaths.length === 1
+                      ? baseName(batchPaths[0])
+                      : `${batchPaths.length} items`}
                 </span>
                 <button onClick={handleReset} className="text-[#6b7280] hover:text-red-400 transition-colors">
                   <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round">
@@ -242,7 +425,10 @@ export default function App() {
             <PresetPicker
               selected={selectedPresets}
               onSelectionChange={setSelectedPresets}
+              deep={deepQc}
+              onDeepChange={setDeepQc}
               onAnalyze={runAnalysis}
+              onManage={() => setShowPresetManager(true)}
             />
           </div>
         )}
@@ -250,6 +436,16 @@ export default function App() {
         {/* Phase: Loading */}
         {phase === 'loading' && <AnalysisProgress />}
 
+        {/* Phase: Batch */}
+        {phase === 'batch' && (
+          <BatchResults
+            files={batchFiles}
+            summary={batchSummary}
+            onOpenFile={handleOpenBatchFile}
+            onReset={handleReset}
+          />
+        )}
+
         {/* Phase: Results */}
         {phase === 'results' && platformResults.length > 0 && analyzeResult && (
           <div className="space-y-5 animate-fade-in">
@@ -263,23 +459,55 @@ export default function App() {
                 <span className={`text-xs font-semibold ${passCount === totalPlatforms ? 'text-emerald-400' : 'text-amber-400'}`}>
                   {passCount}/{totalPlatforms} ready
                 </span>
+                {recorded && (
+                  <>
+                    <span className="w-px h-3 bg-white/[0.1]" />
+                    <span className="text-xs text-indigo-300/80">
+                      Recorded {new Date(recorded.analyzedAt).toLocaleString(undefined, { dateStyle: 'medium', timeStyle: 'short' })}
+                    </span>
+                  </>
+                )}
               </div>
             </div>
 
             {/* Metadata */}
             <MetadataGrid metadata={analyzeResult.metadata} fileName={analyzeResult.fileName} fileSize={analyzeResult.fileSize} />
 
+            {/* QC timeline */}
+            {analyzeResult.metadata.qc && (
+              <QcTimeline report={analyzeResult.metadata.qc} durationSec={analyzeResult.metadata.durationSec} />
+            )}
+
             {/* Platform cards */}
             <div className="space-y-3">
               <div className="flex items-center justify-between px-1">
                 <h2 className="text-xs font-semibold text-[#6b7280] uppercase tracking-wider">Platform Compliance</h2>
-                <button
-                  onClick={handleChangePresets}
-                  className="text-[11px] text-indigo-400 hover:text-indigo-300 transition-colors"
-                >
-                  Change presets
-                </button>
+                <div className="flex items-center gap-4">
+                  {batchFiles.length > 0 && (
+                    <button
+                      onClick={handleBackToBatch}
+                      className="text-[11px] text-indigo-400 hover:text-indigo-300 transition-colors"
+                    >
+                      Back to batch
+                    </button>
+                  )}
+                  <button
+                    onClick={handleCompareFile}
+                    disabled={comparing}
+                    className="text-[11px] text-indigo-400 hover:text-indigo-300 disabled:text-[#4b5563] transition-colors"
+                  >
+                    {comparing ? 'Comparing…' : 'Compare with…'}
+                  </button>
+                  <button
+                    onClick={handleChangePresets}
+                    className="text-[11px] text-indigo-400 hover:text-indigo-300 transition-colors"
+                  >
+                    Change presets
+                  </button>
+                </div>
               </div>
+              {compareError && <p className="px-1 text-[11px] text-red-400/80">{compareError}</p>}
+              {comparison && <CompareView comparison={comparison} onClose={() => setComparison(null)} />}
               <div className="stagger-children space-y-2">
                 {platformResults.map((pr) => (
                   <PlatformCard
@@ -288,6 +516,8 @@ export default function App() {
                     platformName={pr.platformName}
                     result={pr.result}
                     metadata={analyzeResult.metadata}
+                    fileName={analyzeResult.fileName}
+                    filePath={filePath ?? ''}
                   />
                 ))}
               </div>
@@ -324,6 +554,29 @@ export default function App() {
         onManageSubscription={() => { setShowAbout(false); setShowSubscription(true); }}
       />
 
+      {/* History dialog */}
+      <HistoryDialog
+        open={showHistory}
+        onClose={() => setShowHistory(false)}
+        onOpenEntry={handleOpenHistory}
+        onCompareEntry={phase === 'results' && filePath ? handleCompareHistory : undefined}
+      />
+
+      {/* Watch folders dialog */}
+      <WatchDialog
+        open={showWatch}
+        onClose={() => setShowWatch(false)}
+        results={watchResults}
+        onOpenResult={handleOpenWatchResult}
+      />
+
+      {/* Preset manager dialog */}
+      <PresetManagerDialog
+        open={showPresetManager}
+        onClose={() => setShowPresetManager(false)}
+        onDeleted={(id) => setSelectedPresets((ids) => ids.filter((s) => s !== id))}
+      />
+
       {/* Subscription dialog */}
       <SubscriptionDialog
         open={showSubscription}
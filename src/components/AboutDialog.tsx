@@ -2,6 +2,8 @@ import { useState, useEffect } from 'react';
 import { getVersion } from '@tauri-apps/api/app';
 import type { LicenseData } from '@/lib/license';
 import { daysRemaining } from '@/lib/license';
+import { clearProbeCache } from '@/lib/cache';
+import { errorMessage } from '@/lib/errors';
 
 interface AboutDialogProps {
   open: boolean;
@@ -12,6 +14,7 @@ interface AboutDialogProps {
 
 export default function AboutDialog({ open, onClose, license, onManageSubscription }: AboutDialogProps) {
   const [appVersion, setAppVersion] = useState<string>('');
+  const [cacheStatus, setCacheStatus] = useState<string | null>(null);
 
   useEffect(() => {
     getVersion().then(setAppVersion).catch(() => setAppVersion('1.0.0'));
@@ -19,6 +22,15 @@ export default function AboutDialog({ open, onClose, license, onManageSubscripti
 
   if (!open) return null;
 
+  async function handleClearCache() {
+    try {
+      const removed = await clearProbeCache();
+      setCacheStatus(`Cleared ${removed} file${removed !== 1 ? 's' : ''}`);
+    } catch (err) {
+      setCacheStatus(errorMessage(err));
+    }
+  }
+
   const statusColor =
     license?.status === 'active'
       ? 'bg-emerald-500/20 text-emerald-400 border-emerald-500/30'
@@ -105,6 +117,21 @@ export default function AboutDialog({ open, onClose, license, onManageSubscripti
             </div>
           )}
 
+          {/* Probe cache */}
+          <div className="mb-5">
+            <div className="flex items-center justify-between">
+              <span className="text-[11px] text-[#6b7280] uppercase tracking-wider">Analysis cache</span>
+              <button
+                onClick={handleClearCache}
+                className="px-2.5 py-1 bg-white/[0.04] hover:bg-white/[0.08] border border-white/[0.08] rounded-lg text-[11px] text-[#9ca3af] hover:text-white transition-all"
+              >
+                Clear
+              </button>
+            </div>
+            {cacheStatus && <p className="text-[10px] text-[#6b7280] text-right mt-1.5">{cacheStatus}</p>}
+            <div className="h-px bg-white/[0.06] mt-4" />
+          </div>
+
           {/* Creator */}
           <div className="mb-5">
             <p className="text-[11px] text-[#6b7280] uppercase tracking-wider mb-2">Created by</p>
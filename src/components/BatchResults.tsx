@@ -0,0 +1,116 @@
+import type { BatchFileResult, BatchSummary } from '@/lib/batch';
+
+interface BatchResultsProps {
+  /** Finished files, in completion order. */
+  files: BatchFileResult[];
+  /** Set once the whole batch is done. */
+  summary: BatchSummary | null;
+  onOpenFile: (file: BatchFileResult) => void;
+  onReset: () => void;
+}
+
+function fileName(path: string): string {
+  return path.split(/[\\/]/).pop() || path;
+}
+
+function fileVerdict(file: BatchFileResult): 'PERFECT' | 'FLAWED' | 'ERROR' {
+  if (file.error || !file.analysis) return 'ERROR';
+  return file.results.every((r) => r.result.verdict === 'PERFECT') ? 'PERFECT' : 'FLAWED';
+}
+
+export default function BatchResults({ files, summary, onOpenFile, onReset }: BatchResultsProps) {
+  const total = summary?.total ?? files[0]?.total ?? 0;
+  const done = summary ? summary.total : files.length;
+  const percent = total > 0 ? Math.round((done / total) * 100) : 0;
+  // Flawed and failed files first, so problems don't scroll out of view.
+  const order = { ERROR: 0, FLAWED: 1, PERFECT: 2 };
+  const sorted = [...files].sort((a, b) => order[fileVerdict(a)] - order[fileVerdict(b)] || a.index - b.index);
+
+  return (
+    <div className="space-y-5 animate-fade-in">
+      {/* Progress / summary bar */}
+      <div className="bg-white/[0.02] border border-white/[0.08] rounded-2xl px-5 py-4 space-y-3">
+        <div className="flex items-center justify-between">
+          <h2 className="text-xs font-semibold text-[#6b7280] uppercase tracking-wider">
+            {summary ? 'Batch complete' : 'Analyzing batch…'}
+          </h2>
+          <span className="text-xs text-[#9ca3af]">
+            {done}/{total || '…'} files
+          </span>
+        </div>
+        <div className="h-1 rounded-full bg-white/[0.05] overflow-hidden">
+          <div className="h-full bg-indigo-500 transition-all duration-300" style={{ width: `${percent}%` }} />
+        </div>
+        {summary && (
+          <>
+            <div className="flex items-center gap-4 text-xs">
+              <span className="text-emerald-400">{summary.perfectFiles} ready</span>
+              <span className="text-red-400">{summary.flawedFiles} flawed</span>
+              {summary.failures.length > 0 && (
+                <span className="text-amber-400">{summary.failures.length} could not be analyzed</span>
+              )}
+            </div>
+            <div className="flex flex-wrap gap-2">
+              {summary.presets.map((tally) => (
+                <span
+                  key={tally.platformId}
+                  className="px-2.5 py-1 rounded-lg bg-white/[0.03] border border-white/[0.06] text-[10px] text-[#9ca3af]"
+                >
+                  {tally.platformName}{' '}
+                  <span className="text-emerald-400">{tally.perfect} ✓</span>{' '}
+                  <span className="text-red-400">{tally.flawed} ✗</span>
+                </span>
+              ))}
+            </div>
+          </>
+        )}
+      </div>
+
+      {/* Files */}
+      <div className="bg-white/[0.02] border border-white/[0.08] rounded-2xl overflow-hidden divide-y divide-white/[0.04]">
+        {files.length === 0 && (
+          <p className="px-5 py-8 text-center text-xs text-[#6b7280]">Waiting for the first result…</p>
+        )}
+        {sorted.map((file) => {
+          const verdict = fileVerdict(file);
+          return (
+            <div key={file.index} className="px-5 py-3 flex items-center gap-3 hover:bg-white/[0.02]">
+              <span
+                className={`flex-shrink-0 w-2 h-2 rounded-full ${
+                  verdict === 'PERFECT' ? 'bg-emerald-400' : verdict === 'FLAWED' ? 'bg-red-400' : 'bg-amber-400'
+                }`}
+              />
+              <div className="flex-1 min-w-0">
+                <p className="text-xs font-medium text-white truncate" title={file.path}>{fileName(file.path)}</p>
+                <p className="text-[10px] text-[#6b7280] truncate">
+                  {file.error
+                    ? file.error.message
+                    : file.results.map((r) => `${r.platformName} ${r.result.verdict === 'PERFECT' ? '✓' : '✗'}`).join(' · ')}
+                </p>
+              </div>
+              {file.analysis && (
+                <button
+                  onClick={() => onOpenFile(file)}
+                  className="px-2.5 py-1 rounded-lg bg-white/[0.04] hover:bg-white/[0.08] border border-white/[0.06] text-[10px] font-medium text-[#9ca3af] hover:text-white transition-all"
+                >
+                  Open
+                </button>
+              )}
+            </div>
+          );
+        })}
+      </div>
+
+      {summary && (
+        <div className="flex justify-center pt-2">
+          <button
+            onClick={onReset}
+            className="px-6 py-3 bg-white/[0.04] hover:bg-white/[0.08] border border-white/[0.08] hover:border-white/[0.15] rounded-xl text-sm font-medium text-[#9ca3af] hover:text-white transition-all duration-200"
+          >
+            Analyze Other Files
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
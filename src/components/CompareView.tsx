@@ -0,0 +1,93 @@
+import type { CompareResult, CompareSide } from '@/lib/compare';
+
+interface CompareViewProps {
+  comparison: CompareResult;
+  onClose: () => void;
+}
+
+function sideLabel(side: CompareSide): string {
+  if (side.analyzedAt !== null) {
+    const when = new Date(side.analyzedAt).toLocaleString(undefined, { dateStyle: 'medium', timeStyle: 'short' });
+    return `${side.analysis.fileName} (recorded ${when})`;
+  }
+  return side.analysis.fileName;
+}
+
+function formatValue(value: unknown): string {
+  if (value === null || value === undefined) return '—';
+  if (typeof value === 'object') return JSON.stringify(value);
+  return String(value);
+}
+
+export default function CompareView({ comparison, onClose }: CompareViewProps) {
+  const { a, b, changes, presets } = comparison;
+
+  return (
+    <div className="bg-white/[0.02] border border-white/[0.08] rounded-2xl overflow-hidden animate-fade-in-up">
+      <div className="px-4 py-3 border-b border-white/[0.05] flex items-center gap-3">
+        <h3 className="text-[10px] font-semibold text-[#9ca3af] uppercase tracking-wider">Comparison</h3>
+        <p className="flex-1 min-w-0 text-[11px] text-[#6b7280] truncate">
+          {sideLabel(a)} → {sideLabel(b)}
+        </p>
+        <button onClick={onClose} className="text-[11px] text-indigo-400 hover:text-indigo-300 transition-colors">
+          Close
+        </button>
+      </div>
+
+      {/* Verdict changes */}
+      <div className="px-4 py-3 space-y-2 border-b border-white/[0.05]">
+        {presets.map((preset) => (
+          <div key={preset.platformId}>
+            <div className="flex items-center gap-2 text-xs">
+              <span className="font-medium text-white">{preset.platformName}</span>
+              <span className={preset.before === 'PERFECT' ? 'text-emerald-400' : 'text-red-400'}>{preset.before}</span>
+              <span className="text-[#4b5563]">→</span>
+              <span className={preset.after === 'PERFECT' ? 'text-emerald-400' : 'text-red-400'}>{preset.after}</span>
+            </div>
+            {preset.fixed.map((flip) => (
+              <p key={`fixed-${flip.field}`} className="text-[10px] text-emerald-400/80 pl-3">
+                ✓ {flip.field}: {flip.before.value} → {flip.after.value}
+              </p>
+            ))}
+            {preset.broken.map((flip) => (
+              <p key={`broken-${flip.field}`} className="text-[10px] text-red-400/80 pl-3">
+                ✗ {flip.field}: {flip.before.value} → {flip.after.value} ({flip.after.reason})
+              </p>
+            ))}
+            {preset.notMeasured.length > 0 && (
+              <p className="text-[10px] text-[#6b7280] pl-3">
+                Not measured on both sides: {preset.notMeasured.join(', ')}
+              </p>
+            )}
+          </div>
+        ))}
+      </div>
+
+      {/* Metadata diff */}
+      <div className="px-4 py-3">
+        {changes.length === 0 ? (
+          <p className="text-[11px] text-[#6b7280]">Metadata is identical</p>
+        ) : (
+          <table className="w-full text-[10px] font-mono">
+            <thead>
+              <tr className="text-left text-[#6b7280]">
+                <th className="font-medium pb-1.5">Field</th>
+                <th className="font-medium pb-1.5">Before</th>
+                <th className="font-medium pb-1.5">After</th>
+              </tr>
+            </thead>
+            <tbody>
+              {changes.map((change) => (
+                <tr key={change.field} className="align-top">
+                  <td className="pr-3 py-0.5 text-[#9ca3af]">{change.field}</td>
+                  <td className="pr-3 py-0.5 text-red-300/80 break-all">{formatValue(change.before)}</td>
+                  <td className="py-0.5 text-emerald-300/80 break-all">{formatValue(change.after)}</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -43,7 +43,7 @@ export default function ExportButtons({ metadata, platformResults, fileName }: E
     txt += `File: ${r.fileName}\n`;
     txt += `Date: ${r.analyzedAt}\n\n`;
     txt += `--- METADATA ---\n`;
-    txt += `Resolution: ${metadata.width}x${metadata.height}\n`;
+    txt += `Resolution: ${metadata.displayWidth || metadata.width}x${metadata.displayHeight || metadata.height}\n`;
     txt += `Codec: ${metadata.videoCodec}\n`;
     txt += `Container: ${metadata.container}\n`;
     txt += `FPS: ${metadata.fpsAvg}\n`;
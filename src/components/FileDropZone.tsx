@@ -3,10 +3,12 @@ import { getCurrentWebview } from '@tauri-apps/api/webview';
 
 interface FileDropZoneProps {
   onFilePick: () => void;
-  onDrop: (path: string) => void;
+  onFolderPick: () => void;
+  /** Every dropped file and folder. */
+  onDrop: (paths: string[]) => void;
 }
 
-export default function FileDropZone({ onFilePick, onDrop }: FileDropZoneProps) {
+export default function FileDropZone({ onFilePick, onFolderPick, onDrop }: FileDropZoneProps) {
   const [dragOver, setDragOver] = useState(false);
 
   useEffect(() => {
@@ -18,7 +20,7 @@ export default function FileDropZone({ onFilePick, onDrop }: FileDropZoneProps)
       } else if (event.payload.type === 'drop') {
         setDragOver(false);
         if (event.payload.paths.length > 0) {
-          onDrop(event.payload.paths[0]);
+          onDrop(event.payload.paths);
         }
       }
     });
@@ -47,8 +49,19 @@ export default function FileDropZone({ onFilePick, onDrop }: FileDropZoneProps)
               </svg>
             </div>
             <div>
-              <p className="text-base font-medium text-white">Drop your file here</p>
-              <p className="text-sm text-[#6b7280] mt-1">or click to browse files</p>
+              <p className="text-base font-medium text-white">Drop your files here</p>
+              <p className="text-sm text-[#6b7280] mt-1">
+                or click to browse files, or{' '}
+                <button
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    onFolderPick();
+                  }}
+                  className="text-indigo-400 hover:text-indigo-300 transition-colors"
+                >
+                  choose a folder
+                </button>
+              </p>
               <p className="text-xs text-[#4b5563] mt-3">MP4, MOV, MKV, JPG, PNG, WebP, and more</p>
             </div>
           </div>
@@ -0,0 +1,165 @@
+import { useState, useEffect } from 'react';
+import { usePresets } from '@/lib/presets';
+import { errorMessage } from '@/lib/errors';
+import {
+  listHistory,
+  getHistoryEntry,
+  deleteHistoryEntry,
+  type HistoryEntry,
+  type HistorySummary,
+  type Verdict,
+} from '@/lib/history';
+
+interface HistoryDialogProps {
+  open: boolean;
+  onClose: () => void;
+  onOpenEntry: (entry: HistoryEntry) => void;
+  /** Offered while a result is on screen: compare the entry against it. */
+  onCompareEntry?: (entry: HistorySummary) => void;
+}
+
+function formatDate(ms: number): string {
+  return new Date(ms).toLocaleString(undefined, { dateStyle: 'medium', timeStyle: 'short' });
+}
+
+export default function HistoryDialog({ open, onClose, onOpenEntry, onCompareEntry }: HistoryDialogProps) {
+  const { presets } = usePresets();
+  const [search, setSearch] = useState('');
+  const [verdict, setVerdict] = useState<Verdict | ''>('');
+  const [presetId, setPresetId] = useState('');
+  const [entries, setEntries] = useState<HistorySummary[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!open) return;
+    let cancelled = false;
+    listHistory({
+      search: search || undefined,
+      verdict: verdict || undefined,
+      presetId: presetId || undefined,
+    })
+      .then((rows) => {
+        if (!cancelled) {
+          setEntries(rows);
+          setError(null);
+        }
+      })
+      .catch((err) => {
+        if (!cancelled) setError(errorMessage(err));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [open, search, verdict, presetId]);
+
+  if (!open) return null;
+
+  async function handleOpen(id: number) {
+    try {
+      onOpenEntry(await getHistoryEntry(id));
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  }
+
+  async function handleDelete(id: number) {
+    try {
+      await deleteHistoryEntry(id);
+      setEntries((rows) => rows.filter((row) => row.id !== id));
+    } catch (err) {
+      setError(errorMessage(err));
+    }
+  }
+
+  const presetName = (id: string) => presets.find((p) => p.id === id)?.name ?? id;
+
+  return (
+    <div className="fixed inset-0 z-[60] flex items-center justify-center">
+      {/* Backdrop */}
+      <div className="absolute inset-0 bg-black/60 backdrop-blur-sm animate-fade-in" onClick={onClose} />
+
+      {/* Dialog */}
+      <div className="relative bg-[#0d0d15] border border-white/[0.1] rounded-2xl w-[640px] max-h-[80vh] flex flex-col overflow-hidden animate-scale-in shadow-2xl shadow-black/50">
+        <div className="h-1 bg-gradient-to-r from-indigo-500 via-violet-500 to-indigo-500" />
+
+        <div className="px-5 pt-4 pb-3 border-b border-white/[0.05] space-y-3">
+          <div className="flex items-center justify-between">
+            <h2 className="text-sm font-semibold text-white">History</h2>
+            <button onClick={onClose} className="text-[#6b7280] hover:text-white transition-colors text-xs">
+              Close
+            </button>
+          </div>
+          <div className="flex gap-2">
+            <input
+              value={search}
+              onChange={(e) => setSearch(e.target.value)}
+              placeholder="Search path, name or hash"
+              className="flex-1 bg-white/[0.04] border border-white/[0.08] rounded-lg px-3 py-1.5 text-xs text-white placeholder-[#4b5563] outline-none focus:border-indigo-500/40"
+            />
+            <select
+              value={presetId}
+              onChange={(e) => setPresetId(e.target.value)}
+              className="bg-white/[0.04] border border-white/[0.08] rounded-lg px-2 py-1.5 text-xs text-[#d1d5db] outline-none"
+            >
+              <option value="">All presets</option>
+              {presets.map((p) => (
+                <option key={p.id} value={p.id}>{p.name}</option>
+              ))}
+            </select>
+            <select
+              value={verdict}
+              onChange={(e) => setVerdict(e.target.value as Verdict | '')}
+              className="bg-white/[0.04] border border-white/[0.08] rounded-lg px-2 py-1.5 text-xs text-[#d1d5db] outline-none"
+            >
+              <option value="">Any verdict</option>
+              <option value="PERFECT">Perfect</option>
+              <option value="FLAWED">Flawed</option>
+            </select>
+          </div>
+          {error && <p className="text-[11px] text-red-400/80">{error}</p>}
+        </div>
+
+        <div className="overflow-y-auto divide-y divide-white/[0.04]">
+          {entries.length === 0 && (
+            <p className="px-5 py-8 text-center text-xs text-[#6b7280]">No analyses recorded yet</p>
+          )}
+          {entries.map((entry) => (
+            <div key={entry.id} className="px-5 py-3 flex items-center gap-3 hover:bg-white/[0.02]">
+              <span
+                className={`flex-shrink-0 w-2 h-2 rounded-full ${
+                  entry.verdict === 'PERFECT' ? 'bg-emerald-400' : 'bg-red-400'
+                }`}
+              />
+              <div className="flex-1 min-w-0">
+                <p className="text-xs font-medium text-white truncate" title={entry.path}>{entry.fileName}</p>
+                <p className="text-[10px] text-[#6b7280] truncate">
+                  {formatDate(entry.analyzedAt)} · {entry.presets.map((p) => `${presetName(p.presetId)} ${p.verdict === 'PERFECT' ? '✓' : '✗'}`).join(' · ')}
+                </p>
+              </div>
+              <button
+                onClick={() => handleOpen(entry.id)}
+                className="px-2.5 py-1 rounded-lg bg-white/[0.04] hover:bg-white/[0.08] border border-white/[0.06] text-[10px] font-medium text-[#9ca3af] hover:text-white transition-all"
+              >
+                Open
+              </button>
+              {onCompareEntry && (
+                <button
+                  onClick={() => onCompareEntry(entry)}
+                  className="px-2.5 py-1 rounded-lg bg-white/[0.04] hover:bg-white/[0.08] border border-white/[0.06] text-[10px] font-medium text-[#9ca3af] hover:text-white transition-all"
+                >
+                  Compare
+                </button>
+              )}
+              <button
+                onClick={() => handleDelete(entry.id)}
+                className="px-2.5 py-1 rounded-lg bg-white/[0.04] hover:bg-red-500/10 border border-white/[0.06] text-[10px] font-medium text-[#9ca3af] hover:text-red-300 transition-all"
+              >
+                Delete
+              </button>
+            </div>
+          ))}
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -1,5 +1,6 @@
 import { useState } from 'react';
 import { useLicense, isLicenseValid, daysRemaining } from '@/lib/license';
+import { errorMessage } from '@/lib/errors';
 
 interface LicenseGateProps {
   children: React.ReactNode;
@@ -21,7 +22,7 @@ export default function LicenseGate({ children }: LicenseGateProps) {
       console.log('[LicenseGate] startCheckout completed successfully');
     } catch (err) {
       console.error('[LicenseGate] startCheckout failed:', err);
-      setSubscribeError(String(err));
+      setSubscribeError(errorMessage(err));
     }
   };
 
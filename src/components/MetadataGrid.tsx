@@ -18,6 +18,96 @@ function formatBitrate(bps?: number): string | null {
   return `${(bps / 1_000_000).toFixed(2)} Mbps`;
 }
 
+function formatStreams(metadata: ExtendedMetadata): string | null {
+  const counts = new Map<string, number>();
+  for (const stream of metadata.streams ?? []) {
+    const type = stream.disposition.includes('attached_pic') ? 'cover' : stream.codecType;
+    counts.set(type, (counts.get(type) ?? 0) + 1);
+  }
+  if (counts.size === 0) return null;
+  return [...counts].map(([type, n]) => `${n} ${type}`).join(' · ');
+}
+
+function formatSubtitles(metadata: ExtendedMetadata): string | null {
+  const streams = metadata.streams ?? [];
+  const tracks = streams
+    .filter((s) => s.codecType === 'subtitle')
+    .map((s) => `${s.codecName ?? 'unknown'}${s.language ? ` (${s.language})` : ''}`);
+  if (streams.some((s) => s.closedCaptions)) tracks.unshift('CEA-608/708');
+  return tracks.length > 0 ? tracks.join(' · ') : null;
+}
+
+function formatFrameTiming(metadata: ExtendedMetadata): string | null {
+  const t = metadata.frameTiming;
+  if (!t) return null;
+  if (!t.vfr && t.droppedFrames === 0) return `CFR ${t.measuredFps} FPS`;
+  const drops = t.droppedFrames > 0 ? `, ${t.droppedFrames} dropped` : '';
+  return `${t.vfr ? 'VFR' : 'CFR'} ${t.minIntervalMs.toFixed(1)}–${t.maxIntervalMs.toFixed(1)} ms${drops}`;
+}
+
+function formatGop(metadata: ExtendedMetadata): string | null {
+  const g = metadata.gop;
+  if (!g) return null;
+  if (g.maxGopFrames <= 1) return 'I-frame only';
+  return `${g.openGops > 0 ? 'Open' : 'Closed'} ${g.maxGopFrames} (${g.pattern.slice(0, 12)})`;
+}
+
+function formatLoudness(metadata: ExtendedMetadata): string | null {
+  const l = metadata.loudness;
+  if (l?.integratedLufs == null) return null;
+  const peak = l.truePeakDbtp != null ? ` · ${l.truePeakDbtp.toFixed(1)} dBTP` : '';
+  return `${l.integratedLufs.toFixed(1)} LUFS${peak}`;
+}
+
+function formatTimecode(metadata: ExtendedMetadata): string | null {
+  const tc = metadata.timecode;
+  if (!tc) return null;
+  return `${tc.start} ${tc.dropFrame ? 'DF' : 'NDF'} @ ${tc.rate}`;
+}
+
+function formatHdr(metadata: ExtendedMetadata): string | null {
+  const h = metadata.hdr;
+  if (!h) return null;
+  const parts = [h.format];
+  if (h.dolbyVision) parts[0] += ` ${h.dolbyVision.profile}`;
+  if (h.masteringDisplay) {
+    parts.push(`${h.masteringDisplay.primaries ?? 'custom'} ${Math.round(h.masteringDisplay.maxLuminance)} nits`);
+  }
+  if (h.contentLight) parts.push(`MaxCLL ${h.contentLight.maxCll} / MaxFALL ${h.contentLight.maxFall}`);
+  return parts.join(' · ');
+}
+
+function formatCrop(metadata: ExtendedMetadata): string | null {
+  const c = metadata.crop;
+  if (!c) return null;
+  const bars = c.letterbox && c.pillarbox ? 'windowbox' : c.letterbox ? 'letterbox' : c.pillarbox ? 'pillarbox' : null;
+  return bars ? `${c.width} x ${c.height} (${bars})` : 'Full frame';
+}
+
+function formatInterlace(metadata: ExtendedMetadata): string | null {
+  const i = metadata.interlace;
+  if (!i) return null;
+  const interlaced = i.tff + i.bff;
+  if (interlaced + i.progressive === 0) return 'Undetermined';
+  if (interlaced <= i.progressive) return `Progressive (${interlaced} interlaced)`;
+  return `Interlaced ${i.tff >= i.bff ? 'TFF' : 'BFF'} (${interlaced}/${interlaced + i.progressive})`;
+}
+
+function formatMp4(metadata: ExtendedMetadata): string | null {
+  const m = metadata.mp4;
+  if (!m) return null;
+  if (m.truncated) return 'Truncated';
+  const layout = m.fragmented ? `Fragmented (${m.fragments})` : m.faststart ? 'Fast start' : 'moov at end';
+  return m.majorBrand ? `${layout} · ${m.majorBrand}` : layout;
+}
+
+function formatResolution(metadata: ExtendedMetadata): string {
+  const { width, height, displayWidth, displayHeight, rotation } = metadata;
+  if (!displayWidth || (displayWidth === width && displayHeight === height)) return `${width} x ${height}`;
+  const why = rotation ? `rotated ${rotation}°` : `SAR ${metadata.sampleAspectRatio}`;
+  return `${displayWidth} x ${displayHeight} (${width} x ${height}, ${why})`;
+}
+
 function formatSize(bytes: number): string {
   if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
   if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
@@ -31,7 +121,7 @@ export default function MetadataGrid({ metadata, fileName, fileSize }: MetadataG
     { label: 'File', value: fileName },
     { label: 'Size', value: formatSize(fileSize) },
     { label: 'Type', value: isImage ? 'Image' : 'Video' },
-    { label: 'Resolution', value: `${metadata.width} x ${metadata.height}` },
+    { label: 'Resolution', value: formatResolution(metadata) },
     ...(!isImage ? [{ label: 'Frame Rate', value: `${metadata.fps} FPS` }] : []),
     { label: isImage ? 'Format' : 'Video Codec', value: metadata.videoCodec.toUpperCase() },
     ...(!isImage ? [{ label: 'Container', value: metadata.container.toUpperCase() }] : []),
@@ -40,6 +130,19 @@ export default function MetadataGrid({ metadata, fileName, fileSize }: MetadataG
     ...(formatBitrate(metadata.videoBitrate || metadata.formatBitrate) ? [{ label: 'Bitrate', value: formatBitrate(metadata.videoBitrate || metadata.formatBitrate)! }] : []),
     ...(metadata.profile ? [{ label: 'Profile', value: metadata.profile }] : []),
     ...(metadata.pixFmt ? [{ label: 'Pixel Fmt', value: metadata.pixFmt }] : []),
+    ...(metadata.encoder ? [{ label: 'Encoder', value: metadata.encoder }] : []),
+    ...(metadata.creationTime ? [{ label: 'Created', value: metadata.creationTime }] : []),
+    ...(metadata.chapters?.length ? [{ label: 'Chapters', value: `${metadata.chapters.length}` }] : []),
+    ...(formatTimecode(metadata) ? [{ label: 'Timecode', value: formatTimecode(metadata)! }] : []),
+    ...(formatHdr(metadata) ? [{ label: 'HDR', value: formatHdr(metadata)! }] : []),
+    ...(formatCrop(metadata) ? [{ label: 'Active Picture', value: formatCrop(metadata)! }] : []),
+    ...(formatInterlace(metadata) ? [{ label: 'Picture Scan', value: formatInterlace(metadata)! }] : []),
+    ...(formatMp4(metadata) ? [{ label: 'MP4 Layout', value: formatMp4(metadata)! }] : []),
+    ...(formatFrameTiming(metadata) ? [{ label: 'Frame Timing', value: formatFrameTiming(metadata)! }] : []),
+    ...(formatGop(metadata) ? [{ label: 'GOP', value: formatGop(metadata)! }] : []),
+    ...(formatLoudness(metadata) ? [{ label: 'Loudness', value: formatLoudness(metadata)! }] : []),
+    ...(formatSubtitles(metadata) ? [{ label: 'Captions', value: formatSubtitles(metadata)! }] : []),
+    ...(!isImage && formatStreams(metadata) ? [{ label: 'Streams', value: formatStreams(metadata)! }] : []),
   ];
 
   return (
@@ -3,6 +3,7 @@ import { PerfectResult, ExtendedMetadata } from '@/lib/rules/evaluate';
 import ScoreGauge from './ScoreGauge';
 import ComplianceChecks from './ComplianceChecks';
 import Recommendations from './Recommendations';
+import RemediationCommand from './RemediationCommand';
 import PlatformSpecs from './PlatformSpecs';
 import PlatformIcon from './icons/PlatformIcon';
 
@@ -11,9 +12,11 @@ interface PlatformCardProps {
   platformName: string;
   result: PerfectResult;
   metadata: ExtendedMetadata;
+  fileName: string;
+  filePath: string;
 }
 
-export default function PlatformCard({ platformId, platformName, result }: PlatformCardProps) {
+export default function PlatformCard({ platformId, platformName, result, fileName, filePath }: PlatformCardProps) {
   const [expanded, setExpanded] = useState(false);
   const isPerfect = result.verdict === 'PERFECT';
   const total = result.checks.length;
@@ -57,6 +60,14 @@ export default function PlatformCard({ platformId, platformName, result }: Platf
           </div>
           <ComplianceChecks checks={result.checks} reasons={result.reasons} />
           <Recommendations checks={result.checks} />
+          {result.remediation && (
+            <RemediationCommand
+              remediation={result.remediation}
+              fileName={fileName}
+              filePath={filePath}
+              presetId={platformId}
+            />
+          )}
           <PlatformSpecs platform={platformId} />
         </div>
       )}
@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { platformSpecs } from '@/lib/rules/platformSpecs';
+import { usePresets } from '@/lib/presets';
 
 interface PlatformSpecsProps {
   platform: string;
@@ -7,7 +7,8 @@ interface PlatformSpecsProps {
 
 export default function PlatformSpecs({ platform }: PlatformSpecsProps) {
   const [copied, setCopied] = useState(false);
-  const spec = platformSpecs[platform];
+  const { presets } = usePresets();
+  const spec = presets.find((p) => p.id === platform);
   if (!spec) return null;
 
   function handleCopyAll() {
@@ -0,0 +1,228 @@
+import { useState, useEffect } from 'react';
+import { open as openDialog, save } from '@tauri-apps/plugin-dialog';
+import { errorMessage } from '@/lib/errors';
+import {
+  usePresets,
+  createCustomPreset,
+  updateCustomPreset,
+  deleteCustomPreset,
+  importCustomPresets,
+  exportPreset,
+  type PresetDefinition,
+} from '@/lib/presets';
+
+interface PresetManagerDialogProps {
+  open: boolean;
+  onClose: () => void;
+  /** A custom preset id went away (deleted or renamed), so it can be dropped from the selection. */
+  onDeleted: (id: string) => void;
+}
+
+/** The preset being edited: `id` is the custom preset it replaces, or null for a new one. */
+interface Draft {
+  id: string | null;
+  text: string;
+}
+
+function toEditable(preset: PresetDefinition): string {
+  const { custom: _custom, ...definition } = preset;
+  return JSON.stringify(definition, null, 2);
+}
+
+export default function PresetManagerDialog({ open, onClose, onDeleted }: PresetManagerDialogProps) {
+  const { presets } = usePresets();
+  const [draft, setDraft] = useState<Draft | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!open) return;
+    setDraft(null);
+    setError(null);
+    setNotice(null);
+  }, [open]);
+
+  if (!open) return null;
+
+  const custom = presets.filter((p) => p.custom);
+  const builtin = presets.filter((p) => !p.custom);
+
+  function report(message: string) {
+    setError(null);
+    setNotice(message);
+  }
+
+  function fail(err: unknown) {
+    setNotice(null);
+    setError(errorMessage(err));
+  }
+
+  function handleDuplicate(preset: PresetDefinition) {
+    setDraft({ id: null, text: toEditable({ ...preset, id: `${preset.id}-custom`, name: `${preset.name} (Custom)` }) });
+    setError(null);
+  }
+
+  async function handleImport() {
+    const selected = await openDialog({
+      multiple: true,
+      directory: false,
+      filters: [{ name: 'Presets', extensions: ['toml', 'json'] }],
+    });
+    if (!selected || selected.length === 0) return;
+    try {
+      const imported = await importCustomPresets(selected);
+      report(`Imported ${imported.map((p) => p.name).join(', ')}`);
+    } catch (err) {
+      fail(err);
+    }
+  }
+
+  async function handleExport(preset: PresetDefinition) {
+    const path = await save({
+      filters: [{ name: 'Preset', extensions: ['toml', 'json'] }],
+      defaultPath: `${preset.id}.toml`,
+    });
+    if (!path) return;
+    try {
+      await exportPreset(preset.id, path);
+      report(`Exported ${preset.name}`);
+    } catch (err) {
+      fail(err);
+    }
+  }
+
+  async function handleDelete(preset: PresetDefinition) {
+    try {
+      await deleteCustomPreset(preset.id);
+      onDeleted(preset.id);
+      if (draft?.id === preset.id) setDraft(null);
+      report(`Deleted ${preset.name}`);
+    } catch (err) {
+      fail(err);
+    }
+  }
+
+  async function handleSaveDraft() {
+    if (!draft) return;
+    let definition: PresetDefinition;
+    try {
+      definition = JSON.parse(draft.text);
+    } catch (err) {
+      setError(`Not valid JSON: ${err instanceof Error ? err.message : String(err)}`);
+      return;
+    }
+    try {
+      const saved = draft.id === null
+        ? await createCustomPreset(definition)
+        : await updateCustomPreset(draft.id, definition);
+      // A renamed preset's old id is gone, just like a deleted one.
+      if (draft.id !== null && saved.id !== draft.id) onDeleted(draft.id);
+      setDraft(null);
+      report(`Saved ${saved.name}`);
+    } catch (err) {
+      fail(err);
+    }
+  }
+
+  const rowButton =
+    'px-2.5 py-1 rounded-lg bg-white/[0.04] hover:bg-white/[0.08] border border-white/[0.06] text-[10px] font-medium text-[#9ca3af] hover:text-white transition-all';
+
+  function renderRow(preset: PresetDefinition) {
+    return (
+      <div key={preset.id} className="px-5 py-2.5 flex items-center gap-3 hover:bg-white/[0.02]">
+        <div className="flex-1 min-w-0">
+          <p className="text-xs font-medium text-white truncate">{preset.name}</p>
+          <p className="text-[10px] text-[#6b7280] truncate">{preset.id} · {preset.category}</p>
+        </div>
+        {preset.custom && (
+          <button onClick={() => setDraft({ id: preset.id, text: toEditable(preset) })} className={rowButton}>
+            Edit
+          </button>
+        )}
+        <button onClick={() => handleDuplicate(preset)} className={rowButton}>
+          Duplicate
+        </button>
+        <button onClick={() => handleExport(preset)} className={rowButton}>
+          Export
+        </button>
+        {preset.custom && (
+          <button
+            onClick={() => handleDelete(preset)}
+            className="px-2.5 py-1 rounded-lg bg-white/[0.04] hover:bg-red-500/10 border border-white/[0.06] text-[10px] font-medium text-[#9ca3af] hover:text-red-300 transition-all"
+          >
+            Delete
+          </button>
+        )}
+      </div>
+    );
+  }
+
+  return (
+    <div className="fixed inset-0 z-[60] flex items-center justify-center">
+      {/* Backdrop */}
+      <div className="absolute inset-0 bg-black/60 backdrop-blur-sm animate-fade-in" onClick={onClose} />
+
+      {/* Dialog */}
+      <div className="relative bg-[#0d0d15] border border-white/[0.1] rounded-2xl w-[640px] max-h-[80vh] flex flex-col overflow-hidden animate-scale-in shadow-2xl shadow-black/50">
+        <div className="h-1 bg-gradient-to-r from-indigo-500 via-violet-500 to-indigo-500" />
+
+        <div className="px-5 pt-4 pb-3 border-b border-white/[0.05] space-y-2">
+          <div className="flex items-center justify-between">
+            <h2 className="text-sm font-semibold text-white">Presets</h2>
+            <div className="flex items-center gap-4">
+              <button onClick={handleImport} className="text-[11px] text-indigo-400 hover:text-indigo-300 transition-colors">
+                Import…
+              </button>
+              <button onClick={onClose} className="text-[#6b7280] hover:text-white transition-colors text-xs">
+                Close
+              </button>
+            </div>
+          </div>
+          {error && <p className="text-[11px] text-red-400/80">{error}</p>}
+          {notice && <p className="text-[11px] text-emerald-400/80">{notice}</p>}
+        </div>
+
+        {draft ? (
+          <div className="p-5 flex flex-col gap-3 min-h-0">
+            <p className="text-[11px] text-[#6b7280]">
+              {draft.id === null ? 'New custom preset' : `Editing ${draft.id}`} — the same fields as an exported preset, as JSON.
+            </p>
+            <textarea
+              value={draft.text}
+              onChange={(e) => setDraft({ ...draft, text: e.target.value })}
+              spellCheck={false}
+              className="h-[45vh] bg-white/[0.04] border border-white/[0.08] rounded-lg px-3 py-2 text-[11px] font-mono text-[#d1d5db] outline-none focus:border-indigo-500/40 resize-none"
+            />
+            <div className="flex justify-end gap-2">
+              <button onClick={() => setDraft(null)} className={rowButton}>
+                Cancel
+              </button>
+              <button
+                onClick={handleSaveDraft}
+                className="px-4 py-1.5 rounded-lg bg-indigo-500 hover:bg-indigo-400 text-xs font-semibold text-white transition-all"
+              >
+                Save
+              </button>
+            </div>
+          </div>
+        ) : (
+          <div className="overflow-y-auto divide-y divide-white/[0.04]">
+            <h3 className="px-5 pt-3 pb-2 text-[10px] font-semibold text-[#9ca3af] uppercase tracking-wider">
+              Custom ({custom.length})
+            </h3>
+            {custom.length === 0 && (
+              <p className="px-5 py-4 text-[11px] text-[#6b7280]">
+                No custom presets yet. Import a preset file, or duplicate a built-in one to start from.
+              </p>
+            )}
+            {custom.map(renderRow)}
+            <h3 className="px-5 pt-3 pb-2 text-[10px] font-semibold text-[#9ca3af] uppercase tracking-wider">
+              Built-in
+            </h3>
+            {builtin.map(renderRow)}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -1,32 +1,7 @@
 import { useState } from 'react';
 import PlatformIcon from './icons/PlatformIcon';
 import CategoryIcon from './icons/CategoryIcon';
-
-export interface PresetDef {
-  id: string;
-  name: string;
-  category: 'social' | 'broadcast' | 'cinema' | 'images';
-  description: string;
-  recommended?: boolean;
-}
-
-const PRESETS: PresetDef[] = [
-  { id: 'ig_reels', name: 'Instagram Reels', category: 'social', description: 'Vertical 9:16, no-transcode target', recommended: true },
-  { id: 'ig_post', name: 'Instagram Post', category: 'social', description: 'Square 1:1 or portrait 4:5 feed video' },
-  { id: 'ig_story', name: 'Instagram Story', category: 'social', description: 'Vertical 9:16, up to 60s' },
-  { id: 'tiktok', name: 'TikTok', category: 'social', description: 'Vertical short-form video' },
-  { id: 'yt_shorts', name: 'YouTube Shorts', category: 'social', description: 'Vertical 9:16, up to 60s' },
-  { id: 'youtube', name: 'YouTube', category: 'social', description: 'Standard horizontal video', recommended: true },
-  { id: 'ig_post_image', name: 'IG Post Image', category: 'images', description: '1080x1080 or 1080x1350, JPEG/PNG', recommended: true },
-  { id: 'ig_story_image', name: 'IG Story Image', category: 'images', description: '1080x1920 vertical, JPEG/PNG' },
-  { id: 'ig_reels_cover', name: 'IG Reels Cover', category: 'images', description: '1080x1920 thumbnail, JPEG/PNG' },
-  { id: 'fb_cover', name: 'Facebook Cover', category: 'images', description: '1200x628 or 820x312, JPEG/PNG' },
-  { id: 'linkedin_banner', name: 'LinkedIn Banner', category: 'images', description: '1584x396 or 1200x627, JPEG/PNG' },
-  { id: 'mxf_pal', name: 'MXF PAL', category: 'broadcast', description: 'EBU broadcast, 25fps, MXF container' },
-  { id: 'mxf_ntsc', name: 'MXF NTSC', category: 'broadcast', description: 'NTSC broadcast, 29.97fps, MXF container' },
-  { id: 'cinema', name: 'Cinema / Netflix', category: 'cinema', description: 'ProRes/DNxHR, 4K preferred' },
-  { id: 'dcp', name: 'DCP Readiness', category: 'cinema', description: 'JPEG 2000, DCI resolution, 5.1/7.1 audio' },
-];
+import { usePresets, type PresetCategory } from '@/lib/presets';
 
 const CATEGORIES = [
   { key: 'social' as const, label: 'Social Video' },
@@ -38,13 +13,18 @@ const CATEGORIES = [
 interface PresetPickerProps {
   selected: string[];
   onSelectionChange: (ids: string[]) => void;
+  deep: boolean;
+  onDeepChange: (deep: boolean) => void;
   onAnalyze: () => void;
+  /** Opens the custom preset manager. */
+  onManage: () => void;
 }
 
-export default function PresetPicker({ selected, onSelectionChange, onAnalyze }: PresetPickerProps) {
-  const [activeTab, setActiveTab] = useState<'social' | 'images' | 'broadcast' | 'cinema'>('social');
+export default function PresetPicker({ selected, onSelectionChange, deep, onDeepChange, onAnalyze, onManage }: PresetPickerProps) {
+  const [activeTab, setActiveTab] = useState<PresetCategory>('social');
+  const { presets } = usePresets();
 
-  const filtered = PRESETS.filter((p) => p.category === activeTab);
+  const filtered = presets.filter((p) => p.category === activeTab);
 
   function toggle(id: string) {
     onSelectionChange(
@@ -55,7 +35,7 @@ export default function PresetPicker({ selected, onSelectionChange, onAnalyze }:
   }
 
   function selectAll() {
-    onSelectionChange(PRESETS.map((p) => p.id));
+    onSelectionChange(presets.map((p) => p.id));
   }
 
   function selectNone() {
@@ -74,8 +54,8 @@ export default function PresetPicker({ selected, onSelectionChange, onAnalyze }:
         {/* Category tabs */}
         <div className="px-5 flex gap-1 mb-3">
           {CATEGORIES.map((cat) => {
-            const count = PRESETS.filter((p) => p.category === cat.key && selected.includes(p.id)).length;
-            const total = PRESETS.filter((p) => p.category === cat.key).length;
+            const count = presets.filter((p) => p.category === cat.key && selected.includes(p.id)).length;
+            const total = presets.filter((p) => p.category === cat.key).length;
             return (
               <button
                 key={cat.key}
@@ -163,6 +143,26 @@ export default function PresetPicker({ selected, onSelectionChange, onAnalyze }:
             >
               Clear
             </button>
+            <span className="text-[#6b7280]">·</span>
+            <button
+              onClick={onManage}
+              className="text-[11px] text-[#6b7280] hover:text-indigo-300 transition-colors"
+            >
+              Manage
+            </button>
+            <span className="text-[#6b7280]">·</span>
+            <label
+              title="Scan every frame for black gaps, frozen picture and silence, and check for black bars and interlacing"
+              className="flex items-center gap-1.5 text-[11px] text-[#6b7280] hover:text-indigo-300 transition-colors cursor-pointer"
+            >
+              <input
+                type="checkbox"
+                checked={deep}
+                onChange={(e) => onDeepChange(e.target.checked)}
+                className="accent-indigo-500"
+              />
+              Deep QC
+            </label>
           </div>
           <button
             onClick={onAnalyze}
@@ -180,5 +180,3 @@ export default function PresetPicker({ selected, onSelectionChange, onAnalyze }:
     </div>
   );
 }
-
-export { PRESETS };
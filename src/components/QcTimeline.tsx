@@ -0,0 +1,71 @@
+import { QcEvent, QcReport } from '@/lib/rules/evaluate';
+
+interface QcTimelineProps {
+  report: QcReport;
+  durationSec: number;
+}
+
+const KINDS: Record<QcEvent['kind'], { label: string; color: string }> = {
+  black: { label: 'Black', color: 'bg-slate-400' },
+  freeze: { label: 'Freeze', color: 'bg-sky-400' },
+  silence: { label: 'Silence', color: 'bg-amber-400' },
+};
+
+function formatTime(sec: number): string {
+  const m = Math.floor(sec / 60);
+  const s = (sec % 60).toFixed(1).padStart(4, '0');
+  return `${m}:${s}`;
+}
+
+export default function QcTimeline({ report, durationSec }: QcTimelineProps) {
+  const { events } = report;
+  const total = Math.max(durationSec, ...events.map((e) => e.endSec), 0.001);
+
+  return (
+    <div className="animate-fade-in-up bg-white/[0.02] border border-white/[0.08] rounded-2xl overflow-hidden" style={{ animationDelay: '250ms' }}>
+      <div className="px-5 py-3 border-b border-white/[0.05] flex items-center gap-2.5">
+        <div className="w-5 h-5 rounded-md bg-sky-500/10 flex items-center justify-center">
+          <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="#0ea5e9" strokeWidth="2.5" strokeLinecap="round">
+            <path d="M3 12h4l3-8 4 16 3-8h4" />
+          </svg>
+        </div>
+        <h3 className="text-xs font-semibold text-[#9ca3af] uppercase tracking-wider">QC Timeline</h3>
+        <span className={`ml-auto text-[11px] font-semibold ${events.length === 0 ? 'text-emerald-400' : 'text-amber-400'}`}>
+          {events.length === 0 ? 'No issues' : `${events.length} issue${events.length === 1 ? '' : 's'}`}
+        </span>
+      </div>
+      <div className="p-4 space-y-3">
+        {(Object.keys(KINDS) as QcEvent['kind'][]).map((kind) => (
+          <div key={kind} className="flex items-center gap-3">
+            <div className="w-14 text-[9px] uppercase tracking-wider text-[#6b7280]">{KINDS[kind].label}</div>
+            <div className="relative flex-1 h-2.5 bg-white/[0.04] rounded-full overflow-hidden">
+              {events.filter((e) => e.kind === kind).map((e, i) => (
+                <div
+                  key={i}
+                  title={`${formatTime(e.startSec)} – ${formatTime(e.endSec)}`}
+                  className={`absolute inset-y-0 ${KINDS[kind].color} rounded-full`}
+                  style={{
+                    left: `${(e.startSec / total) * 100}%`,
+                    width: `max(${(e.durationSec / total) * 100}%, 3px)`,
+                  }}
+                />
+              ))}
+            </div>
+          </div>
+        ))}
+        {events.length > 0 && (
+          <div className="pt-1 space-y-1">
+            {events.map((e, i) => (
+              <div key={i} className="flex items-center gap-2 text-[11px]">
+                <span className={`w-1.5 h-1.5 rounded-full ${KINDS[e.kind].color}`} />
+                <span className="text-white font-semibold w-14">{KINDS[e.kind].label}</span>
+                <span className="text-[#9ca3af] font-mono">{formatTime(e.startSec)} – {formatTime(e.endSec)}</span>
+                <span className="text-[#6b7280]">({e.durationSec.toFixed(1)}s)</span>
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
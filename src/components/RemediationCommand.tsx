@@ -0,0 +1,134 @@
+import { useState } from 'react';
+import { save } from '@tauri-apps/plugin-dialog';
+import { Remediation } from '@/lib/rules/evaluate';
+import { cancelFix, fixForPreset, type FixProgress, type FixResult } from '@/lib/fix';
+import { errorMessage, isAppError } from '@/lib/errors';
+
+interface RemediationCommandProps {
+  remediation: Remediation;
+  fileName: string;
+  filePath: string;
+  presetId: string;
+}
+
+const isWindows = navigator.userAgent.includes('Windows');
+
+// Literal single quotes for POSIX shells and PowerShell, so `$var`, backticks
+// and `%VAR%` in a path are never expanded (same as remedy.rs).
+function quote(arg: string): string {
+  if (isWindows) {
+    return /^[\w\-./:=+\\]+$/.test(arg) ? arg : `'${arg.replace(/'/g, "''")}'`;
+  }
+  return /^[\w\-./:=+,]+$/.test(arg) ? arg : `'${arg.replace(/'/g, `'\\''`)}'`;
+}
+
+function outputName(fileName: string, extension: string): string {
+  const dot = fileName.lastIndexOf('.');
+  const stem = dot > 0 ? fileName.slice(0, dot) : fileName;
+  return `${stem}_fixed.${extension}`;
+}
+
+function formatEta(sec: number): string {
+  const s = Math.round(sec);
+  return s >= 60 ? `${Math.floor(s / 60)}m ${s % 60}s` : `${s}s`;
+}
+
+export default function RemediationCommand({ remediation, fileName, filePath, presetId }: RemediationCommandProps) {
+  const [copied, setCopied] = useState(false);
+  const [outputPath, setOutputPath] = useState<string | null>(null);
+  const [progress, setProgress] = useState<FixProgress | null>(null);
+  const [fixed, setFixed] = useState<FixResult | null>(null);
+  const [fixError, setFixError] = useState<string | null>(null);
+  const command = ['ffmpeg', '-hide_banner', '-i', fileName, ...remediation.args, outputName(fileName, remediation.extension)]
+    .map(quote)
+    .join(' ');
+
+  function handleCopy() {
+    navigator.clipboard.writeText(command);
+    setCopied(true);
+    setTimeout(() => setCopied(false), 2000);
+  }
+
+  async function handleFix() {
+    const dir = filePath.slice(0, filePath.length - fileName.length);
+    const target = await save({
+      filters: [{ name: remediation.extension.toUpperCase(), extensions: [remediation.extension] }],
+      defaultPath: `${dir}${outputName(fileName, remediation.extension)}`,
+    });
+    if (!target) return;
+    setOutputPath(target);
+    setProgress(null);
+    setFixed(null);
+    setFixError(null);
+    try {
+      setFixed(await fixForPreset(filePath, presetId, target, setProgress));
+    } catch (err) {
+      if (!(isAppError(err) && err.code === 'FixCancelled')) setFixError(errorMessage(err));
+    } finally {
+      setOutputPath(null);
+    }
+  }
+
+  return (
+    <div className="bg-white/[0.02] border border-white/[0.08] rounded-2xl overflow-hidden">
+      <div className="px-4 py-2.5 border-b border-white/[0.05] flex items-center gap-2">
+        <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="#818cf8" strokeWidth="2.5" strokeLinecap="round">
+          <path d="M4 17l6-6-6-6M12 19h8" />
+        </svg>
+        <h3 className="text-[10px] font-semibold text-[#9ca3af] uppercase tracking-wider">Fix with ffmpeg</h3>
+        <span className="ml-auto text-[10px] font-medium text-indigo-300/80 bg-indigo-500/10 px-2 py-0.5 rounded-full">
+          {remediation.remuxOnly ? 'Remux only' : 'Re-encode'}
+        </span>
+      </div>
+      <div className="px-4 py-3 space-y-2">
+        <div className="flex items-start gap-2">
+          <code className="flex-1 text-[10px] font-mono text-[#d1d5db] break-all leading-relaxed">{command}</code>
+          <button
+            onClick={handleCopy}
+            className="flex-shrink-0 px-2 py-1 rounded-lg bg-white/[0.04] hover:bg-white/[0.08] border border-white/[0.06] text-[9px] font-medium text-[#9ca3af] hover:text-white transition-all"
+          >
+            {copied ? 'Copied!' : 'Copy'}
+          </button>
+        </div>
+        <div className="flex items-center gap-2">
+          {outputPath ? (
+            <>
+              <div className="flex-1 h-1.5 bg-white/[0.06] rounded-full overflow-hidden">
+                <div className="h-full bg-indigo-400 transition-all" style={{ width: `${progress?.percent ?? 0}%` }} />
+              </div>
+              <span className="text-[10px] font-mono text-[#9ca3af] w-24 text-right">
+                {Math.round(progress?.percent ?? 0)}%
+                {progress?.etaSec != null && progress.percent < 100 && ` · ${formatEta(progress.etaSec)}`}
+              </span>
+              <button
+                onClick={() => cancelFix(outputPath)}
+                className="px-2 py-1 rounded-lg bg-white/[0.04] hover:bg-red-500/10 border border-white/[0.06] text-[9px] font-medium text-[#9ca3af] hover:text-red-300 transition-all"
+              >
+                Cancel
+              </button>
+            </>
+          ) : (
+            <button
+              onClick={handleFix}
+              className="px-3 py-1.5 rounded-lg bg-indigo-500/15 hover:bg-indigo-500/25 border border-indigo-500/20 text-[10px] font-semibold text-indigo-200 transition-all"
+            >
+              Fix it
+            </button>
+          )}
+        </div>
+        {fixed && (
+          <p className={`text-[10px] leading-relaxed ${fixed.result.result.verdict === 'PERFECT' ? 'text-emerald-400/90' : 'text-amber-400/80'}`}>
+            {fixed.analysis.fileName}: {fixed.result.result.verdict}
+            {fixed.result.result.verdict !== 'PERFECT' && ` (${(fixed.result.result.reasons ?? []).join('; ')})`}
+          </p>
+        )}
+        {fixError && <p className="text-[10px] text-red-400/80 leading-relaxed">{fixError}</p>}
+        {remediation.unresolved.length > 0 && (
+          <p className="text-[10px] text-amber-400/80 leading-relaxed">
+            Still needs attention: {remediation.unresolved.join(', ')}
+          </p>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,248 @@
+import { useState, useEffect } from 'react';
+import { open as openDialog } from '@tauri-apps/plugin-dialog';
+import { usePresets } from '@/lib/presets';
+import { errorMessage } from '@/lib/errors';
+import { getWatchConfig, setWatchConfig, type WatchConfig, type WatchResult } from '@/lib/watch';
+
+interface WatchDialogProps {
+  open: boolean;
+  onClose: () => void;
+  /** Renders the watcher has checked since launch, newest first. */
+  results: WatchResult[];
+  onOpenResult: (result: WatchResult) => void;
+}
+
+function fileName(path: string): string {
+  return path.split(/[\\/]/).pop() || path;
+}
+
+export default function WatchDialog({ open, onClose, results, onOpenResult }: WatchDialogProps) {
+  const { presets } = usePresets();
+  const [config, setConfig] = useState<WatchConfig | null>(null);
+  const [saving, setSaving] = useState(false);
+  const [saved, setSaved] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!open) return;
+    let cancelled = false;
+    setSaved(false);
+    getWatchConfig()
+      .then((loaded) => {
+        if (!cancelled) {
+          setConfig(loaded);
+          setError(null);
+        }
+      })
+      .catch((err) => {
+        if (!cancelled) setError(errorMessage(err));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [open]);
+
+  if (!open) return null;
+
+  function update(patch: Partial<WatchConfig>) {
+    setConfig((current) => (current ? { ...current, ...patch } : current));
+    setSaved(false);
+  }
+
+  async function handleAddFolder() {
+    if (!config) return;
+    const selected = await openDialog({ multiple: true, directory: true });
+    if (selected && selected.length > 0) {
+      update({ folders: [...config.folders, ...selected.filter((f) => !config.folders.includes(f))] });
+    }
+  }
+
+  function togglePreset(id: string) {
+    if (!config) return;
+    update({
+      presetIds: config.presetIds.includes(id)
+        ? config.presetIds.filter((p) => p !== id)
+        : [...config.presetIds, id],
+    });
+  }
+
+  async function handleSave() {
+    if (!config) return;
+    setSaving(true);
+    try {
+      setConfig(await setWatchConfig(config));
+      setError(null);
+      setSaved(true);
+    } catch (err) {
+      setError(errorMessage(err));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  const toggleClass = 'flex items-center gap-1.5 text-[11px] text-[#9ca3af] cursor-pointer';
+
+  return (
+    <div className="fixed inset-0 z-[60] flex items-center justify-center">
+      {/* Backdrop */}
+      <div className="absolute inset-0 bg-black/60 backdrop-blur-sm animate-fade-in" onClick={onClose} />
+
+      {/* Dialog */}
+      <div className="relative bg-[#0d0d15] border border-white/[0.1] rounded-2xl w-[640px] max-h-[80vh] flex flex-col overflow-hidden animate-scale-in shadow-2xl shadow-black/50">
+        <div className="h-1 bg-gradient-to-r from-indigo-500 via-violet-500 to-indigo-500" />
+
+        <div className="px-5 pt-4 pb-3 border-b border-white/[0.05] flex items-center justify-between">
+          <h2 className="text-sm font-semibold text-white">Watch Folders</h2>
+          <button onClick={onClose} className="text-[#6b7280] hover:text-white transition-colors text-xs">
+            Close
+          </button>
+        </div>
+
+        <div className="overflow-y-auto">
+          {!config ? (
+            <p className="px-5 py-8 text-center text-xs text-[#6b7280]">{error ?? 'Loading…'}</p>
+          ) : (
+            <div className="px-5 py-4 space-y-4 border-b border-white/[0.05]">
+              <p className="text-[11px] text-[#6b7280]">
+                Finished renders that land in these folders are checked automatically.
+              </p>
+
+              {/* Folders */}
+              <div className="space-y-1.5">
+                {config.folders.length === 0 && (
+                  <p className="text-[11px] text-[#4b5563]">No folders yet</p>
+                )}
+                {config.folders.map((folder) => (
+                  <div key={folder} className="flex items-center gap-2 bg-white/[0.03] border border-white/[0.06] rounded-lg px-3 py-1.5">
+                    <span className="flex-1 min-w-0 text-xs text-[#d1d5db] truncate" title={folder}>{folder}</span>
+                    <button
+                      onClick={() => update({ folders: config.folders.filter((f) => f !== folder) })}
+                      className="text-[10px] text-[#6b7280] hover:text-red-300 transition-colors"
+                    >
+                      Remove
+                    </button>
+                  </div>
+                ))}
+                <button
+                  onClick={handleAddFolder}
+                  className="text-[11px] text-indigo-400 hover:text-indigo-300 transition-colors"
+                >
+                  + Add folder
+                </button>
+              </div>
+
+              {/* Presets */}
+              <div>
+                <h3 className="text-[10px] font-semibold text-[#9ca3af] uppercase tracking-wider mb-2">
+                  Check against ({config.presetIds.length})
+                </h3>
+                <div className="grid grid-cols-3 gap-1.5 max-h-36 overflow-y-auto">
+                  {presets.map((preset) => (
+                    <label key={preset.id} className={toggleClass} title={preset.title}>
+                      <input
+                        type="checkbox"
+                        checked={config.presetIds.includes(preset.id)}
+                        onChange={() => togglePreset(preset.id)}
+                        className="accent-indigo-500"
+                      />
+                      <span className="truncate">{preset.name}</span>
+                    </label>
+                  ))}
+                </div>
+              </div>
+
+              {/* Options */}
+              <div className="flex flex-wrap items-center gap-4">
+                <label className={toggleClass}>
+                  <input
+                    type="checkbox"
+                    checked={config.recursive}
+                    onChange={(e) => update({ recursive: e.target.checked })}
+                    className="accent-indigo-500"
+                  />
+                  Include subfolders
+                </label>
+                <label className={toggleClass}>
+                  <input
+                    type="checkbox"
+                    checked={config.notify}
+                    onChange={(e) => update({ notify: e.target.checked })}
+                    className="accent-indigo-500"
+                  />
+                  Desktop notifications
+                </label>
+                <label className={toggleClass} title="Scan every frame for black gaps, frozen picture and silence">
+                  <input
+                    type="checkbox"
+                    checked={config.deep}
+                    onChange={(e) => update({ deep: e.target.checked })}
+                    className="accent-indigo-500"
+                  />
+                  Deep QC
+                </label>
+              </div>
+
+              <div className="flex items-center gap-3">
+                <label className={toggleClass}>
+                  <input
+                    type="checkbox"
+                    checked={config.enabled}
+                    onChange={(e) => update({ enabled: e.target.checked })}
+                    className="accent-indigo-500"
+                  />
+                  Watching enabled
+                </label>
+                <span className="flex-1 text-[11px] truncate">
+                  {error ? (
+                    <span className="text-red-400/80">{error}</span>
+                  ) : saved ? (
+                    <span className="text-emerald-400/80">Saved</span>
+                  ) : null}
+                </span>
+                <button
+                  onClick={handleSave}
+                  disabled={saving}
+                  className="px-4 py-1.5 rounded-lg bg-indigo-500 hover:bg-indigo-400 disabled:opacity-50 text-xs font-semibold text-white transition-all"
+                >
+                  {saving ? 'Saving…' : 'Save'}
+                </button>
+              </div>
+            </div>
+          )}
+
+          {/* Recent results */}
+          <div className="divide-y divide-white/[0.04]">
+            {results.length === 0 && (
+              <p className="px-5 py-6 text-center text-xs text-[#6b7280]">No renders checked yet</p>
+            )}
+            {results.map((result, i) => (
+              <div key={`${result.path}-${i}`} className="px-5 py-3 flex items-center gap-3 hover:bg-white/[0.02]">
+                <span
+                  className={`flex-shrink-0 w-2 h-2 rounded-full ${
+                    result.verdict === 'PERFECT' ? 'bg-emerald-400' : result.verdict === 'FLAWED' ? 'bg-red-400' : 'bg-amber-400'
+                  }`}
+                />
+                <div className="flex-1 min-w-0">
+                  <p className="text-xs font-medium text-white truncate" title={result.path}>{fileName(result.path)}</p>
+                  <p className="text-[10px] text-[#6b7280] truncate">
+                    {result.error
+                      ? result.error.message
+                      : result.results.map((r) => `${r.platformName} ${r.result.verdict === 'PERFECT' ? '✓' : '✗'}`).join(' · ')}
+                  </p>
+                </div>
+                {result.analysis && (
+                  <button
+                    onClick={() => onOpenResult(result)}
+                    className="px-2.5 py-1 rounded-lg bg-white/[0.04] hover:bg-white/[0.08] border border-white/[0.06] text-[10px] font-medium text-[#9ca3af] hover:text-white transition-all"
+                  >
+                    Open
+                  </button>
+                )}
+              </div>
+            ))}
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
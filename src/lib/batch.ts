@@ -0,0 +1,60 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { ExtendedMetadata, PerfectResult } from '@/lib/rules/evaluate';
+import type { AppError } from '@/lib/errors';
+
+export interface BatchFileResult {
+  index: number;
+  total: number;
+  path: string;
+  analysis: { metadata: ExtendedMetadata; fileName: string; fileSize: number } | null;
+  results: { platformId: string; platformName: string; result: PerfectResult }[];
+  error: AppError | null;
+}
+
+export interface PresetTally {
+  platformId: string;
+  platformName: string;
+  perfect: number;
+  flawed: number;
+}
+
+export interface BatchSummary {
+  total: number;
+  analyzed: number;
+  perfectFiles: number;
+  flawedFiles: number;
+  presets: PresetTally[];
+  failures: { path: string; error: AppError }[];
+}
+
+export interface BatchOptions {
+  recursive?: boolean;
+  concurrency?: number;
+  deep?: boolean;
+}
+
+/**
+ * Analyze files and/or folders. `onFile` fires as each file finishes (in
+ * completion order, not selection order); the promise resolves with the
+ * per-preset summary once every file is done.
+ */
+export async function analyzeBatch(
+  paths: string[],
+  presetIds: string[],
+  onFile: (file: BatchFileResult) => void,
+  options: BatchOptions = {},
+): Promise<BatchSummary> {
+  const unlisten = await listen<BatchFileResult>('batch://file', (event) => onFile(event.payload));
+  try {
+    return await invoke<BatchSummary>('analyze_batch', {
+      paths,
+      presetIds,
+      recursive: options.recursive,
+      concurrency: options.concurrency,
+      deep: options.deep,
+    });
+  } finally {
+    unlisten();
+  }
+}
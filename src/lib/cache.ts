@@ -0,0 +1,9 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Drop every cached probe, so the next analysis of each file runs ffprobe
+ * and the slower passes again. Resolves with the number of entries removed.
+ */
+export function clearProbeCache() {
+  return invoke<number>('clear_probe_cache');
+}
@@ -0,0 +1,57 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ExtendedMetadata, FieldCheck, PerfectResult } from '@/lib/rules/evaluate';
+import type { Verdict } from '@/lib/history';
+
+/** A file on disk or a stored history entry. */
+export type CompareSource = { file: string } | { history: number };
+
+export interface CompareSide {
+  path: string;
+  historyId: number | null;
+  analyzedAt: number | null;
+  analysis: { metadata: ExtendedMetadata; fileName: string; fileSize: number };
+  results: { platformId: string; platformName: string; result: PerfectResult }[];
+}
+
+export interface FieldChange {
+  /**
+   * Dotted path into the metadata, e.g. `hdr.maxCll` or `streams[1].codecName`.
+   * `streams[n]` is the stream with container index n on both sides; GOP
+   * keyframes are compared as a summary (`gop.keyframes.maxInterval`).
+   */
+  field: string;
+  before: unknown;
+  after: unknown;
+}
+
+export interface CheckFlip {
+  field: string;
+  before: FieldCheck;
+  after: FieldCheck;
+}
+
+export interface PresetDiff {
+  platformId: string;
+  platformName: string;
+  before: Verdict;
+  after: Verdict;
+  fixed: CheckFlip[];
+  broken: CheckFlip[];
+  /** Checks not compared because one side lacks the measurement they read. */
+  notMeasured: string[];
+}
+
+export interface CompareResult {
+  a: CompareSide;
+  b: CompareSide;
+  changes: FieldChange[];
+  presets: PresetDiff[];
+}
+
+/**
+ * Diff `a` (before) against `b` (after). History entries are re-checked
+ * against the current presets, so flips reflect changes to the file.
+ */
+export function compareFiles(a: CompareSource, b: CompareSource, presetIds: string[], deep = false) {
+  return invoke<CompareResult>('compare_files', { a, b, presetIds, deep });
+}
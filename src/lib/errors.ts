@@ -0,0 +1,55 @@
+// Shape of every command error (src-tauri/src/error.rs). `code` is stable
+// and safe to branch on; `message` is ready to show to the user.
+
+export type AppErrorCode =
+  | 'FfprobeNotFound'
+  | 'FfprobeFailed'
+  | 'FfmpegNotFound'
+  | 'LoudnessFailed'
+  | 'QcFailed'
+  | 'CropFailed'
+  | 'InterlaceFailed'
+  | 'NotMediaFile'
+  | 'NoVideoStream'
+  | 'FileUnreadable'
+  | 'FileWriteFailed'
+  | 'NoMediaFiles'
+  | 'LicenseExpired'
+  | 'LicenseInvalid'
+  | 'NoLicenseKey'
+  | 'Network'
+  | 'Server'
+  | 'OpenUrlFailed'
+  | 'UnknownPreset'
+  | 'InvalidPreset'
+  | 'PresetConflict'
+  | 'InvalidInput'
+  | 'WatchFailed'
+  | 'Storage'
+  | 'FixFailed'
+  | 'FixCancelled'
+  | 'History';
+
+export interface AppError {
+  code: AppErrorCode;
+  message: string;
+  stderr?: string;
+  path?: string;
+  id?: string;
+}
+
+export function isAppError(err: unknown): err is AppError {
+  return (
+    typeof err === 'object' &&
+    err !== null &&
+    typeof (err as AppError).code === 'string' &&
+    typeof (err as AppError).message === 'string'
+  );
+}
+
+/** User-facing text for anything a command or plugin may reject with. */
+export function errorMessage(err: unknown): string {
+  if (isAppError(err)) return err.message;
+  if (err instanceof Error) return err.message;
+  return String(err);
+}
@@ -0,0 +1,43 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { ExtendedMetadata, PerfectResult } from '@/lib/rules/evaluate';
+
+export interface FixProgress {
+  outputPath: string;
+  percent: number;
+  outTimeSec: number;
+  speed: number | null;
+  etaSec: number | null;
+}
+
+export interface FixResult {
+  outputPath: string;
+  args: string[];
+  analysis: { metadata: ExtendedMetadata; fileName: string; fileSize: number };
+  result: { platformId: string; platformName: string; result: PerfectResult };
+}
+
+/**
+ * Run the preset's remediation on `path`, writing `outputPath`, then
+ * re-check the output against the same preset. Rejects with `FixCancelled`
+ * when `cancelFix` stops it.
+ */
+export async function fixForPreset(
+  path: string,
+  presetId: string,
+  outputPath: string,
+  onProgress: (progress: FixProgress) => void,
+): Promise<FixResult> {
+  const unlisten = await listen<FixProgress>('fix://progress', (event) => {
+    if (event.payload.outputPath === outputPath) onProgress(event.payload);
+  });
+  try {
+    return await invoke<FixResult>('fix_for_preset', { path, presetId, outputPath });
+  } finally {
+    unlisten();
+  }
+}
+
+export function cancelFix(outputPath: string) {
+  return invoke<void>('cancel_fix', { outputPath });
+}
@@ -0,0 +1,43 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ExtendedMetadata, PerfectResult } from '@/lib/rules/evaluate';
+
+export type Verdict = 'PERFECT' | 'FLAWED';
+
+export interface HistorySummary {
+  id: number;
+  path: string;
+  fileName: string;
+  fileSize: number;
+  contentHash: string;
+  /** Unix time in milliseconds. */
+  analyzedAt: number;
+  verdict: Verdict;
+  presets: { presetId: string; verdict: Verdict }[];
+}
+
+export interface HistoryEntry extends HistorySummary {
+  analysis: { metadata: ExtendedMetadata; fileName: string; fileSize: number };
+  results: { platformId: string; platformName: string; result: PerfectResult }[];
+}
+
+export interface HistoryQuery {
+  search?: string;
+  /** Applies to `presetId` when that is set, otherwise to the whole entry. */
+  verdict?: Verdict;
+  presetId?: string;
+  limit?: number;
+  offset?: number;
+}
+
+/** Newest first. */
+export function listHistory(query: HistoryQuery = {}) {
+  return invoke<HistorySummary[]>('list_history', { query });
+}
+
+export function getHistoryEntry(id: number) {
+  return invoke<HistoryEntry>('get_history_entry', { id });
+}
+
+export function deleteHistoryEntry(id: number) {
+  return invoke<void>('delete_history_entry', { id });
+}
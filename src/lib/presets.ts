@@ -0,0 +1,105 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+
+// Preset definitions live in src-tauri/presets/*.toml and are served by the
+// `list_presets` command, so the picker, the spec sheet and the rule engine
+// all read from the same source.
+
+export type PresetCategory = 'social' | 'broadcast' | 'cinema' | 'images';
+
+export interface SpecItem {
+  label: string;
+  value: string;
+}
+
+export interface PresetDefinition {
+  id: string;
+  name: string;
+  title: string;
+  category: PresetCategory;
+  description: string;
+  recommended: boolean;
+  custom?: boolean;
+  specs: SpecItem[];
+  // Check definitions are opaque to the UI; they round-trip through the
+  // custom preset commands unchanged.
+  checks: unknown[];
+}
+
+let cache: Promise<PresetDefinition[]> | null = null;
+const listeners = new Set<() => void>();
+
+function invalidate() {
+  cache = null;
+  listeners.forEach((fn) => fn());
+}
+
+function loadPresets(): Promise<PresetDefinition[]> {
+  if (!cache) {
+    cache = invoke<PresetDefinition[]>('list_presets').catch((err) => {
+      cache = null;
+      throw err;
+    });
+  }
+  return cache;
+}
+
+export function usePresets() {
+  const [presets, setPresets] = useState<PresetDefinition[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [version, setVersion] = useState(0);
+
+  useEffect(() => {
+    const onChange = () => setVersion((v) => v + 1);
+    listeners.add(onChange);
+    return () => {
+      listeners.delete(onChange);
+    };
+  }, []);
+
+  useEffect(() => {
+    let cancelled = false;
+    loadPresets()
+      .then((data) => {
+        if (!cancelled) setPresets(data);
+      })
+      .catch((err) => console.error('[Presets] failed to load:', err))
+      .finally(() => {
+        if (!cancelled) setLoading(false);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [version]);
+
+  return { presets, loading };
+}
+
+// ── Custom presets ──
+
+export async function createCustomPreset(preset: PresetDefinition) {
+  const saved = await invoke<PresetDefinition>('create_custom_preset', { preset });
+  invalidate();
+  return saved;
+}
+
+export async function updateCustomPreset(id: string, preset: PresetDefinition) {
+  const saved = await invoke<PresetDefinition>('update_custom_preset', { id, preset });
+  invalidate();
+  return saved;
+}
+
+export async function deleteCustomPreset(id: string) {
+  await invoke('delete_custom_preset', { id });
+  invalidate();
+}
+
+export async function importCustomPresets(paths: string[]) {
+  const imported = await invoke<PresetDefinition[]>('import_custom_presets', { paths });
+  invalidate();
+  return imported;
+}
+
+export async function exportPreset(id: string, path: string) {
+  await invoke('export_preset', { id, path });
+}
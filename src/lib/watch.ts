@@ -0,0 +1,35 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { ExtendedMetadata, PerfectResult } from '@/lib/rules/evaluate';
+import type { AppError } from '@/lib/errors';
+
+export interface WatchConfig {
+  enabled: boolean;
+  folders: string[];
+  presetIds: string[];
+  recursive: boolean;
+  notify: boolean;
+  deep: boolean;
+}
+
+export interface WatchResult {
+  path: string;
+  verdict: 'PERFECT' | 'FLAWED' | null;
+  analysis: { metadata: ExtendedMetadata; fileName: string; fileSize: number } | null;
+  results: { platformId: string; platformName: string; result: PerfectResult }[];
+  error: AppError | null;
+}
+
+export function getWatchConfig() {
+  return invoke<WatchConfig>('get_watch_config');
+}
+
+/** Saves the config and restarts the watcher with it. */
+export function setWatchConfig(config: WatchConfig) {
+  return invoke<WatchConfig>('set_watch_config', { config });
+}
+
+/** Fires once per finished render the watcher has checked. */
+export function onWatchResult(handler: (result: WatchResult) => void): Promise<UnlistenFn> {
+  return listen<WatchResult>('watch://result', (event) => handler(event.payload));
+}